@@ -0,0 +1,1234 @@
+// Portable bundles of applications, their shortcut lists and (optionally)
+// settings, for moving curated lists between machines.
+//
+// Applications are keyed by `process_name` rather than their per-install ids,
+// and list/shortcut ids are left out entirely; fresh ids are assigned on import.
+
+use crate::key_combo;
+use crate::matcher;
+use crate::storage::{
+    self, Application, GlobalAction, MatchRule, Settings, ShortcutList, StorageError,
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// Marker stored in every bundle so arbitrary JSON isn't mistaken for one
+pub const BUNDLE_FORMAT: &str = "will-shortcut-bundle";
+
+// Increment when the bundle layout changes
+pub const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bundle {
+    pub format: String,
+    pub bundle_version: u32,
+    pub exported_at: String,
+    pub applications: Vec<BundleApplication>,
+    #[serde(default)]
+    pub settings: Option<Settings>,
+    // The lists that OpenList hotkeys in `settings` point to, since list ids
+    // differ between installs
+    #[serde(default)]
+    pub list_refs: Vec<BundleListRef>,
+}
+
+// A list id from the exporting machine and the list it stands for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleListRef {
+    pub list_id: String,
+    pub process_name: String,
+    pub list_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleApplication {
+    pub process_name: String,
+    pub name: String,
+    pub detection_name: String,
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub match_rules: Vec<MatchRule>,
+    #[serde(default)]
+    pub is_browser: bool,
+    pub lists: Vec<BundleList>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleList {
+    pub name: String,
+    pub shortcuts: Vec<BundleShortcut>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(default)]
+    pub match_rules: Vec<MatchRule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleShortcut {
+    pub key_combo: String,
+    pub description: String,
+    pub order: i32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    // Application ids to export. None exports every application that has lists.
+    pub application_ids: Option<Vec<String>>,
+    pub include_settings: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportSummary {
+    pub application_count: usize,
+    pub list_count: usize,
+    pub shortcut_count: usize,
+}
+
+impl BundleList {
+    fn from_list(list: &ShortcutList) -> Self {
+        let mut shortcuts: Vec<BundleShortcut> = list
+            .shortcuts
+            .iter()
+            .map(|s| BundleShortcut {
+                key_combo: s.key_combo.clone(),
+                description: s.description.clone(),
+                order: s.order,
+            })
+            .collect();
+        shortcuts.sort_by_key(|s| s.order);
+        BundleList {
+            name: list.name.clone(),
+            shortcuts,
+            created_at: list.created_at.clone(),
+            updated_at: list.updated_at.clone(),
+            match_rules: list.match_rules.clone(),
+        }
+    }
+}
+
+impl BundleApplication {
+    fn from_application(app: &Application, lists: &[ShortcutList]) -> Self {
+        BundleApplication {
+            process_name: app.process_name.clone(),
+            name: app.name.clone(),
+            detection_name: app.detection_name.clone(),
+            icon: app.icon.clone(),
+            match_rules: app.match_rules.clone(),
+            is_browser: app.is_browser,
+            lists: lists
+                .iter()
+                .filter(|l| l.application_id == app.id)
+                .map(BundleList::from_list)
+                .collect(),
+        }
+    }
+}
+
+// Build a bundle from the current storage
+pub fn build_bundle(options: &ExportOptions) -> Result<Bundle, StorageError> {
+    let apps = storage::load_applications()?;
+    let lists = storage::load_lists()?;
+
+    let applications: Vec<BundleApplication> = match &options.application_ids {
+        Some(ids) => {
+            if let Some(unknown) = ids.iter().find(|id| !apps.iter().any(|a| &a.id == *id)) {
+                return Err(StorageError::validation(format!(
+                    "Unknown application id: {unknown}"
+                )));
+            }
+            apps.iter()
+                .filter(|a| ids.contains(&a.id))
+                .map(|a| BundleApplication::from_application(a, &lists))
+                .collect()
+        }
+        None => apps
+            .iter()
+            .map(|a| BundleApplication::from_application(a, &lists))
+            .filter(|a| !a.lists.is_empty())
+            .collect(),
+    };
+
+    let settings = if options.include_settings {
+        Some(storage::load_settings()?)
+    } else {
+        None
+    };
+    let list_refs = settings
+        .iter()
+        .flat_map(|s| &s.global_actions)
+        .filter_map(|binding| match &binding.action {
+            GlobalAction::OpenList { list_id } => {
+                let list = lists.iter().find(|l| &l.id == list_id)?;
+                let app = apps.iter().find(|a| a.id == list.application_id)?;
+                Some(BundleListRef {
+                    list_id: list_id.clone(),
+                    process_name: app.process_name.clone(),
+                    list_name: list.name.clone(),
+                })
+            }
+            _ => None,
+        })
+        .collect();
+
+    Ok(Bundle {
+        format: BUNDLE_FORMAT.to_string(),
+        bundle_version: BUNDLE_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        applications,
+        settings,
+        list_refs,
+    })
+}
+
+impl Bundle {
+    pub fn summary(&self) -> ExportSummary {
+        ExportSummary {
+            application_count: self.applications.len(),
+            list_count: self.applications.iter().map(|a| a.lists.len()).sum(),
+            shortcut_count: self
+                .applications
+                .iter()
+                .flat_map(|a| &a.lists)
+                .map(|l| l.shortcuts.len())
+                .sum(),
+        }
+    }
+}
+
+// Export the selected applications to a bundle file at `path`
+pub fn export_bundle(path: &Path, options: &ExportOptions) -> Result<ExportSummary, StorageError> {
+    let bundle = build_bundle(options)?;
+    storage::write_json(path, &bundle)?;
+    Ok(bundle.summary())
+}
+
+// How lists that already exist locally (same application + list name, the
+// rule initialize_defaults uses) are handled on import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    // Leave the existing list untouched
+    SkipExisting,
+    // Replace the existing list's shortcuts with the bundle's
+    Overwrite,
+    // Add shortcuts whose key_combo is new; update descriptions of matching ones
+    MergeByKeyCombo,
+    // Import the bundle list alongside the existing one under a new name
+    ImportAsCopy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListImportAction {
+    Created,
+    Updated,
+    Skipped,
+    Copied,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListImportReport {
+    pub process_name: String,
+    pub list_name: String,
+    pub action: ListImportAction,
+    pub added: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub removed: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub mode: ImportMode,
+    // Names of applications that don't exist locally and will be created
+    pub new_applications: Vec<String>,
+    pub lists: Vec<ListImportReport>,
+    pub settings_imported: bool,
+    // OpenList hotkeys left out of the imported settings because their list
+    // exists neither in the bundle nor on this machine
+    pub dropped_hotkeys: Vec<String>,
+}
+
+// Read and validate a bundle file
+pub fn read_bundle(path: &Path) -> Result<Bundle, StorageError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| StorageError::io(path, e))?;
+    let bundle: Bundle =
+        serde_json::from_str(&contents).map_err(|e| StorageError::parse(path, e))?;
+    if bundle.format != BUNDLE_FORMAT {
+        return Err(StorageError::validation(format!(
+            "{} is not a Will-Shortcut bundle",
+            path.display()
+        )));
+    }
+    if bundle.bundle_version > BUNDLE_VERSION {
+        return Err(StorageError::validation(format!(
+            "{} uses bundle version {}, newer than this app supports ({BUNDLE_VERSION})",
+            path.display(),
+            bundle.bundle_version
+        )));
+    }
+    Ok(bundle)
+}
+
+fn now_timestamp() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+fn new_shortcut(shortcut: &BundleShortcut, order: i32) -> storage::Shortcut {
+    storage::Shortcut {
+        id: uuid::Uuid::new_v4().to_string(),
+        key_combo: shortcut.key_combo.clone(),
+        description: shortcut.description.clone(),
+        order,
+    }
+}
+
+// A local copy of a bundle list. Its timestamps are kept, since the
+// shortcuts are the ones they describe.
+fn new_list(app_id: &str, name: String, list: &BundleList) -> ShortcutList {
+    ShortcutList {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        application_id: app_id.to_string(),
+        shortcuts: list
+            .shortcuts
+            .iter()
+            .enumerate()
+            .map(|(i, s)| new_shortcut(s, i as i32))
+            .collect(),
+        created_at: list.created_at.clone(),
+        updated_at: list.updated_at.clone(),
+        match_rules: list.match_rules.clone(),
+    }
+}
+
+// "Name (imported)", then "Name (imported 2)", ... until unused for the app
+fn copy_name(lists: &[ShortcutList], app_id: &str, name: &str) -> String {
+    let taken = |candidate: &str| {
+        lists
+            .iter()
+            .any(|l| l.application_id == app_id && l.name == candidate)
+    };
+    let mut candidate = format!("{name} (imported)");
+    let mut n = 2;
+    while taken(&candidate) {
+        candidate = format!("{name} (imported {n})");
+        n += 1;
+    }
+    candidate
+}
+
+// Apply `incoming` to an existing list according to `mode`
+fn merge_into(
+    existing: &mut ShortcutList,
+    incoming: &BundleList,
+    mode: ImportMode,
+) -> ListImportReport {
+    let mut report = ListImportReport {
+        process_name: String::new(),
+        list_name: existing.name.clone(),
+        action: ListImportAction::Skipped,
+        added: 0,
+        updated: 0,
+        skipped: 0,
+        removed: 0,
+    };
+
+    match mode {
+        ImportMode::SkipExisting | ImportMode::ImportAsCopy => {
+            report.skipped = incoming.shortcuts.len();
+        }
+        ImportMode::Overwrite => {
+            for shortcut in &incoming.shortcuts {
+                match existing
+                    .shortcuts
+                    .iter()
+                    .find(|s| key_combo::same_combo(&s.key_combo, &shortcut.key_combo))
+                {
+                    Some(local) if local.description == shortcut.description => report.skipped += 1,
+                    Some(_) => report.updated += 1,
+                    None => report.added += 1,
+                }
+            }
+            report.removed = existing
+                .shortcuts
+                .iter()
+                .filter(|s| {
+                    !incoming
+                        .shortcuts
+                        .iter()
+                        .any(|i| key_combo::same_combo(&i.key_combo, &s.key_combo))
+                })
+                .count();
+            if report.added > 0 || report.updated > 0 || report.removed > 0 {
+                existing.shortcuts = incoming
+                    .shortcuts
+                    .iter()
+                    .enumerate()
+                    .map(|(i, s)| new_shortcut(s, i as i32))
+                    .collect();
+                report.action = ListImportAction::Updated;
+            }
+        }
+        ImportMode::MergeByKeyCombo => {
+            let mut max_order = existing
+                .shortcuts
+                .iter()
+                .map(|s| s.order)
+                .max()
+                .unwrap_or(-1);
+            for shortcut in &incoming.shortcuts {
+                match existing
+                    .shortcuts
+                    .iter_mut()
+                    .find(|s| key_combo::same_combo(&s.key_combo, &shortcut.key_combo))
+                {
+                    Some(local) if local.description != shortcut.description => {
+                        local.description = shortcut.description.clone();
+                        report.updated += 1;
+                    }
+                    Some(_) => report.skipped += 1,
+                    None => {
+                        max_order += 1;
+                        existing.shortcuts.push(new_shortcut(shortcut, max_order));
+                        report.added += 1;
+                    }
+                }
+            }
+            if report.added > 0 || report.updated > 0 {
+                report.action = ListImportAction::Updated;
+            }
+        }
+    }
+
+    if report.action == ListImportAction::Updated {
+        existing.updated_at = now_timestamp();
+    }
+    report
+}
+
+// Merge a bundle into storage. With `dry_run` nothing is written and the
+// report describes what would happen.
+//
+// Bundle settings are only applied in Overwrite mode, and never replace the
+// local defaults seed version. They're handed to `apply_settings`, on a dry
+// run too: the caller checks them there and, on a real import, registers their
+// hotkeys and saves them. That happens after the applications and lists are
+// written; if it (or any write) fails, the files written so far are restored
+// and the error is returned.
+pub fn import_bundle<E: From<StorageError>>(
+    path: &Path,
+    mode: ImportMode,
+    dry_run: bool,
+    apply_settings: impl FnOnce(&Settings) -> Result<(), E>,
+) -> Result<ImportReport, E> {
+    let bundle = read_bundle(path)?;
+    validate_match_rules(&bundle)?;
+    let mut apps = storage::load_applications()?;
+    let mut lists = storage::load_lists()?;
+    let previous_lists = lists.clone();
+
+    let mut report = ImportReport {
+        dry_run,
+        mode,
+        new_applications: Vec::new(),
+        lists: Vec::new(),
+        settings_imported: false,
+        dropped_hotkeys: Vec::new(),
+    };
+    let mut new_user_apps: Vec<Application> = Vec::new();
+    let mut changed_app_ids: Vec<String> = Vec::new();
+
+    for bundle_app in &bundle.applications {
+        let app_id = match apps
+            .iter()
+            .find(|a| a.process_name == bundle_app.process_name)
+        {
+            Some(app) => app.id.clone(),
+            None => {
+                let app = Application {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    name: bundle_app.name.clone(),
+                    process_name: bundle_app.process_name.clone(),
+                    detection_name: bundle_app.detection_name.clone(),
+                    icon: bundle_app.icon.clone(),
+                    last_used_list_id: None,
+                    match_rules: bundle_app.match_rules.clone(),
+                    is_browser: bundle_app.is_browser,
+                };
+                report.new_applications.push(app.name.clone());
+                new_user_apps.push(app.clone());
+                apps.push(app.clone());
+                app.id
+            }
+        };
+
+        for bundle_list in &bundle_app.lists {
+            let existing_index = lists
+                .iter()
+                .position(|l| l.application_id == app_id && l.name == bundle_list.name);
+
+            let mut list_report = match existing_index {
+                None => {
+                    lists.push(new_list(&app_id, bundle_list.name.clone(), bundle_list));
+                    ListImportReport {
+                        process_name: String::new(),
+                        list_name: bundle_list.name.clone(),
+                        action: ListImportAction::Created,
+                        added: bundle_list.shortcuts.len(),
+                        updated: 0,
+                        skipped: 0,
+                        removed: 0,
+                    }
+                }
+                Some(_) if mode == ImportMode::ImportAsCopy => {
+                    let name = copy_name(&lists, &app_id, &bundle_list.name);
+                    lists.push(new_list(&app_id, name.clone(), bundle_list));
+                    ListImportReport {
+                        process_name: String::new(),
+                        list_name: name,
+                        action: ListImportAction::Copied,
+                        added: bundle_list.shortcuts.len(),
+                        updated: 0,
+                        skipped: 0,
+                        removed: 0,
+                    }
+                }
+                Some(index) => merge_into(&mut lists[index], bundle_list, mode),
+            };
+            list_report.process_name = bundle_app.process_name.clone();
+
+            if list_report.action != ListImportAction::Skipped && !changed_app_ids.contains(&app_id)
+            {
+                changed_app_ids.push(app_id.clone());
+            }
+            report.lists.push(list_report);
+        }
+    }
+
+    let imported_settings = match (&bundle.settings, mode) {
+        (Some(settings), ImportMode::Overwrite) => {
+            let mut settings = settings.clone();
+            settings.defaults_seed_version = storage::load_settings()?.defaults_seed_version;
+            report.dropped_hotkeys =
+                remap_open_lists(&mut settings, &bundle.list_refs, &apps, &lists);
+            Some(settings)
+        }
+        _ => None,
+    };
+    report.settings_imported = imported_settings.is_some();
+
+    if dry_run {
+        if let Some(settings) = &imported_settings {
+            apply_settings(settings)?;
+        }
+        return Ok(report);
+    }
+
+    // Snapshot first so an import can be undone with restore_backup
+    storage::create_backup()?;
+
+    let previous_user_apps = storage::load_user_applications()?;
+    let result = write_import(&previous_user_apps, new_user_apps, &changed_app_ids, &lists)
+        .map_err(E::from)
+        .and_then(|()| match &imported_settings {
+            Some(settings) => apply_settings(settings),
+            None => Ok(()),
+        });
+    if let Err(e) = result {
+        let user_apps = (!report.new_applications.is_empty()).then_some(&previous_user_apps);
+        if let Err(restore_error) = restore_import(user_apps, &changed_app_ids, &previous_lists) {
+            eprintln!("Failed to roll back the import: {restore_error}");
+        }
+        return Err(e);
+    }
+
+    Ok(report)
+}
+
+// Save the new applications and the lists of every changed application
+fn write_import(
+    previous_user_apps: &[Application],
+    mut new_user_apps: Vec<Application>,
+    changed_app_ids: &[String],
+    lists: &[ShortcutList],
+) -> Result<(), StorageError> {
+    if !new_user_apps.is_empty() {
+        let mut user_apps = previous_user_apps.to_vec();
+        user_apps.append(&mut new_user_apps);
+        storage::save_user_applications(&user_apps)?;
+    }
+    for app_id in changed_app_ids {
+        save_app_lists(app_id, lists)?;
+    }
+    Ok(())
+}
+
+// Put back what write_import replaced. Every file is attempted; the first
+// error is returned.
+fn restore_import(
+    previous_user_apps: Option<&Vec<Application>>,
+    changed_app_ids: &[String],
+    previous_lists: &[ShortcutList],
+) -> Result<(), StorageError> {
+    let mut result = match previous_user_apps {
+        Some(apps) => storage::save_user_applications(apps),
+        None => Ok(()),
+    };
+    for app_id in changed_app_ids {
+        let restored = if previous_lists.iter().any(|l| &l.application_id == app_id) {
+            save_app_lists(app_id, previous_lists)
+        } else {
+            storage::remove_lists_for_application(app_id)
+        };
+        result = result.and(restored);
+    }
+    result
+}
+
+fn save_app_lists(app_id: &str, lists: &[ShortcutList]) -> Result<(), StorageError> {
+    let app_lists: Vec<ShortcutList> = lists
+        .iter()
+        .filter(|l| l.application_id == app_id)
+        .cloned()
+        .collect();
+    storage::save_lists_for_application(app_id, &app_lists)
+}
+
+// Reject the bundle if any application or list carries a match rule that
+// save_application / save_list would refuse
+fn validate_match_rules(bundle: &Bundle) -> Result<(), StorageError> {
+    for app in &bundle.applications {
+        matcher::validate_rules(&app.match_rules)
+            .map_err(|e| StorageError::validation(format!("{}: {e}", app.name)))?;
+        for list in &app.lists {
+            matcher::validate_rules(&list.match_rules).map_err(|e| {
+                StorageError::validation(format!("{} / {}: {e}", app.name, list.name))
+            })?;
+        }
+    }
+    Ok(())
+}
+
+// Point OpenList hotkeys at the local list with the same application and name.
+// Ids that already exist locally (a bundle from this machine) are kept; other
+// bindings are removed, and their hotkeys returned.
+fn remap_open_lists(
+    settings: &mut Settings,
+    list_refs: &[BundleListRef],
+    apps: &[Application],
+    lists: &[ShortcutList],
+) -> Vec<String> {
+    let local_id = |list_ref: &BundleListRef| {
+        let app = apps
+            .iter()
+            .find(|a| a.process_name == list_ref.process_name)?;
+        lists
+            .iter()
+            .find(|l| l.application_id == app.id && l.name == list_ref.list_name)
+            .map(|l| l.id.clone())
+    };
+
+    let mut dropped = Vec::new();
+    settings.global_actions.retain_mut(|binding| {
+        let GlobalAction::OpenList { list_id } = &mut binding.action else {
+            return true;
+        };
+        let remapped = list_refs
+            .iter()
+            .find(|r| &r.list_id == list_id)
+            .and_then(local_id)
+            .or_else(|| {
+                lists
+                    .iter()
+                    .any(|l| &l.id == list_id)
+                    .then(|| list_id.clone())
+            });
+        match remapped {
+            Some(id) => {
+                *list_id = id;
+                true
+            }
+            None => {
+                dropped.push(binding.hotkey.clone());
+                false
+            }
+        }
+    });
+    dropped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::tests::use_scratch_data_dir;
+    use crate::storage::{GlobalHotkey, MatchField, MatchKind};
+    use std::path::PathBuf;
+
+    fn bundle_list(name: &str) -> BundleList {
+        BundleList {
+            name: name.to_string(),
+            shortcuts: vec![BundleShortcut {
+                key_combo: "Ctrl+K".to_string(),
+                description: "Command menu".to_string(),
+                order: 0,
+            }],
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: "2025-01-01T00:00:00Z".to_string(),
+            match_rules: Vec::new(),
+        }
+    }
+
+    fn bundle(lists: Vec<BundleList>) -> Bundle {
+        Bundle {
+            format: BUNDLE_FORMAT.to_string(),
+            bundle_version: BUNDLE_VERSION,
+            exported_at: "2025-01-01T00:00:00Z".to_string(),
+            applications: vec![BundleApplication {
+                process_name: "tool.exe".to_string(),
+                name: "Tool".to_string(),
+                detection_name: "Tool".to_string(),
+                icon: None,
+                match_rules: Vec::new(),
+                is_browser: false,
+                lists,
+            }],
+            settings: None,
+            list_refs: Vec::new(),
+        }
+    }
+
+    fn open_list(hotkey: &str, list_id: &str) -> GlobalHotkey {
+        GlobalHotkey {
+            hotkey: hotkey.to_string(),
+            action: GlobalAction::OpenList {
+                list_id: list_id.to_string(),
+            },
+            peek: false,
+        }
+    }
+
+    fn no_settings(_: &Settings) -> Result<(), StorageError> {
+        panic!("settings aren't part of this import")
+    }
+
+    #[test]
+    fn open_list_hotkeys_are_remapped_to_local_lists() {
+        let dir = use_scratch_data_dir("bundle-open-list");
+        let mut settings = storage::default_settings();
+        settings.global_actions = vec![
+            open_list("Ctrl+Alt+1", "remote-general"),
+            open_list("Ctrl+Alt+2", "remote-deleted"),
+        ];
+        let mut bundle = bundle(vec![bundle_list("General")]);
+        bundle.settings = Some(settings);
+        bundle.list_refs.push(BundleListRef {
+            list_id: "remote-general".to_string(),
+            process_name: "tool.exe".to_string(),
+            list_name: "General".to_string(),
+        });
+        let path = dir.join("bundle.json");
+        storage::write_json(&path, &bundle).unwrap();
+
+        let report =
+            import_bundle(&path, ImportMode::Overwrite, false, storage::save_settings).unwrap();
+        assert!(report.settings_imported);
+        assert_eq!(report.dropped_hotkeys, vec!["Ctrl+Alt+2"]);
+
+        let general = storage::load_lists()
+            .unwrap()
+            .into_iter()
+            .find(|l| l.name == "General")
+            .unwrap();
+        let actions = storage::load_settings().unwrap().global_actions;
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions[0].action,
+            GlobalAction::OpenList {
+                list_id: general.id
+            }
+        );
+    }
+
+    fn shortcut(key_combo: &str, description: &str) -> BundleShortcut {
+        BundleShortcut {
+            key_combo: key_combo.to_string(),
+            description: description.to_string(),
+            order: 0,
+        }
+    }
+
+    fn list_with(name: &str, shortcuts: Vec<BundleShortcut>) -> BundleList {
+        BundleList {
+            shortcuts,
+            ..bundle_list(name)
+        }
+    }
+
+    // Write `bundle` into the data dir and import it
+    fn import(
+        dir: &Path,
+        bundle: &Bundle,
+        mode: ImportMode,
+        dry_run: bool,
+    ) -> Result<ImportReport, StorageError> {
+        let path = dir.join("bundle.json");
+        storage::write_json(&path, bundle).unwrap();
+        import_bundle(&path, mode, dry_run, no_settings)
+    }
+
+    // Import a local "General" list, then the same list with one description
+    // changed, one shortcut dropped and one added
+    fn import_over_local_list(name: &str, mode: ImportMode) -> (PathBuf, ImportReport) {
+        let dir = use_scratch_data_dir(name);
+        let local = bundle(vec![list_with(
+            "General",
+            vec![shortcut("Ctrl+K", "Open menu"), shortcut("Ctrl+P", "Print")],
+        )]);
+        import(&dir, &local, ImportMode::SkipExisting, false).unwrap();
+
+        let incoming = bundle(vec![list_with(
+            "General",
+            vec![
+                shortcut("Ctrl+K", "Command menu"),
+                shortcut("Ctrl+S", "Save"),
+            ],
+        )]);
+        let report = import(&dir, &incoming, mode, false).unwrap();
+        (dir, report)
+    }
+
+    // (list name, [(key_combo, description)]) for every list, by name
+    fn stored_lists() -> Vec<(String, Vec<(String, String)>)> {
+        let mut lists: Vec<(String, Vec<(String, String)>)> = storage::load_lists()
+            .unwrap()
+            .into_iter()
+            .map(|mut list| {
+                list.shortcuts.sort_by_key(|s| s.order);
+                let shortcuts = list
+                    .shortcuts
+                    .into_iter()
+                    .map(|s| (s.key_combo, s.description))
+                    .collect();
+                (list.name, shortcuts)
+            })
+            .collect();
+        lists.sort();
+        lists
+    }
+
+    fn pairs(shortcuts: &[(&str, &str)]) -> Vec<(String, String)> {
+        shortcuts
+            .iter()
+            .map(|(k, d)| (k.to_string(), d.to_string()))
+            .collect()
+    }
+
+    // Every file under `dir` with its contents
+    fn data_files(dir: &Path) -> Vec<(PathBuf, Vec<u8>)> {
+        let mut files = Vec::new();
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(current) = pending.pop() {
+            for entry in std::fs::read_dir(&current).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else {
+                    let contents = std::fs::read(&path).unwrap();
+                    files.push((path, contents));
+                }
+            }
+        }
+        files.sort();
+        files
+    }
+
+    fn counts(report: &ListImportReport) -> (ListImportAction, usize, usize, usize, usize) {
+        (
+            report.action,
+            report.added,
+            report.updated,
+            report.skipped,
+            report.removed,
+        )
+    }
+
+    #[test]
+    fn skip_existing_leaves_local_lists_alone() {
+        let (_, report) = import_over_local_list("bundle-skip", ImportMode::SkipExisting);
+        assert_eq!(
+            counts(&report.lists[0]),
+            (ListImportAction::Skipped, 0, 0, 2, 0)
+        );
+        assert!(report.new_applications.is_empty());
+        assert_eq!(
+            stored_lists(),
+            vec![(
+                "General".to_string(),
+                pairs(&[("Ctrl+K", "Open menu"), ("Ctrl+P", "Print")])
+            )]
+        );
+    }
+
+    #[test]
+    fn overwrite_replaces_local_shortcuts() {
+        let (_, report) = import_over_local_list("bundle-overwrite", ImportMode::Overwrite);
+        assert_eq!(
+            counts(&report.lists[0]),
+            (ListImportAction::Updated, 1, 1, 0, 1)
+        );
+        assert_eq!(
+            stored_lists(),
+            vec![(
+                "General".to_string(),
+                pairs(&[("Ctrl+K", "Command menu"), ("Ctrl+S", "Save")])
+            )]
+        );
+    }
+
+    #[test]
+    fn merge_adds_new_key_combos_and_updates_descriptions() {
+        let (_, report) = import_over_local_list("bundle-merge", ImportMode::MergeByKeyCombo);
+        assert_eq!(
+            counts(&report.lists[0]),
+            (ListImportAction::Updated, 1, 1, 0, 0)
+        );
+        assert_eq!(
+            stored_lists(),
+            vec![(
+                "General".to_string(),
+                pairs(&[
+                    ("Ctrl+K", "Command menu"),
+                    ("Ctrl+P", "Print"),
+                    ("Ctrl+S", "Save")
+                ])
+            )]
+        );
+    }
+
+    #[test]
+    fn import_as_copy_adds_numbered_copies() {
+        let (dir, report) = import_over_local_list("bundle-copy", ImportMode::ImportAsCopy);
+        assert_eq!(
+            counts(&report.lists[0]),
+            (ListImportAction::Copied, 2, 0, 0, 0)
+        );
+        assert_eq!(report.lists[0].list_name, "General (imported)");
+
+        let again = import(
+            &dir,
+            &bundle(vec![bundle_list("General")]),
+            ImportMode::ImportAsCopy,
+            false,
+        )
+        .unwrap();
+        assert_eq!(again.lists[0].list_name, "General (imported 2)");
+
+        let names: Vec<String> = stored_lists().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(
+            names,
+            vec!["General", "General (imported 2)", "General (imported)"]
+        );
+        assert_eq!(
+            stored_lists()[0].1,
+            pairs(&[("Ctrl+K", "Open menu"), ("Ctrl+P", "Print")])
+        );
+    }
+
+    #[test]
+    fn copy_names_skip_taken_names_of_the_same_application() {
+        let list = |app_id: &str, name: &str| ShortcutList {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            application_id: app_id.to_string(),
+            shortcuts: Vec::new(),
+            created_at: String::new(),
+            updated_at: String::new(),
+            match_rules: Vec::new(),
+        };
+        let lists = vec![
+            list("a", "General"),
+            list("a", "General (imported)"),
+            list("a", "General (imported 2)"),
+            list("b", "General (imported 3)"),
+        ];
+        assert_eq!(copy_name(&lists, "a", "General"), "General (imported 3)");
+        assert_eq!(copy_name(&lists, "b", "General"), "General (imported)");
+        assert_eq!(copy_name(&lists, "a", "Other"), "Other (imported)");
+    }
+
+    #[test]
+    fn dry_run_leaves_the_data_dir_untouched() {
+        let (dir, _) = import_over_local_list("bundle-dry-run", ImportMode::SkipExisting);
+        let mut incoming = bundle(vec![
+            list_with("General", vec![shortcut("Ctrl+S", "Save")]),
+            bundle_list("Extra"),
+        ]);
+        let mut other = incoming.applications[0].clone();
+        other.process_name = "other.exe".to_string();
+        incoming.applications.push(other);
+        let path = dir.join("bundle.json");
+        storage::write_json(&path, &incoming).unwrap();
+        let before = data_files(&dir);
+
+        for mode in [
+            ImportMode::SkipExisting,
+            ImportMode::Overwrite,
+            ImportMode::MergeByKeyCombo,
+            ImportMode::ImportAsCopy,
+        ] {
+            let report = import_bundle(&path, mode, true, no_settings).unwrap();
+            assert!(report.dry_run);
+            assert_eq!(report.new_applications, vec!["Tool"]);
+            assert_eq!(data_files(&dir), before, "{mode:?}");
+        }
+    }
+
+    fn local_app(process_name: &str, name: &str) -> Application {
+        Application {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            process_name: process_name.to_string(),
+            detection_name: name.to_string(),
+            icon: Some("🛠".to_string()),
+            last_used_list_id: None,
+            match_rules: vec![MatchRule {
+                field: MatchField::WindowTitle,
+                kind: MatchKind::Glob,
+                pattern: format!("* - {name}"),
+                priority: 1,
+            }],
+            is_browser: false,
+        }
+    }
+
+    fn local_list(app_id: &str, name: &str, key_combos: &[&str]) -> ShortcutList {
+        ShortcutList {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            application_id: app_id.to_string(),
+            // Stored out of order; bundles sort by `order`
+            shortcuts: key_combos
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(i, key_combo)| storage::Shortcut {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    key_combo: key_combo.to_string(),
+                    description: format!("Action {i}"),
+                    order: i as i32,
+                })
+                .collect(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: "2025-02-01T00:00:00Z".to_string(),
+            match_rules: Vec::new(),
+        }
+    }
+
+    // Two user applications with lists plus a list for the first bundled one.
+    // Returns the user applications.
+    fn seed_export_data(name: &str) -> (PathBuf, Vec<Application>) {
+        let dir = use_scratch_data_dir(name);
+        let apps = vec![
+            local_app("tool.exe", "Tool"),
+            local_app("other.exe", "Other"),
+        ];
+        storage::save_user_applications(&apps).unwrap();
+        storage::save_lists_for_application(
+            &apps[0].id,
+            &vec![
+                local_list(&apps[0].id, "General", &["Ctrl+K", "Ctrl+S"]),
+                local_list(&apps[0].id, "Editing", &["Ctrl+D"]),
+            ],
+        )
+        .unwrap();
+        storage::save_lists_for_application(
+            &apps[1].id,
+            &vec![local_list(&apps[1].id, "General", &["F5"])],
+        )
+        .unwrap();
+        let bundled = &storage::load_applications().unwrap()[0];
+        storage::save_lists_for_application(
+            &bundled.id,
+            &vec![local_list(&bundled.id, "Mine", &["Alt+1"])],
+        )
+        .unwrap();
+        (dir, apps)
+    }
+
+    fn list_summary(bundle: &Bundle) -> Vec<(String, String, Vec<String>)> {
+        bundle
+            .applications
+            .iter()
+            .flat_map(|app| {
+                app.lists.iter().map(|list| {
+                    (
+                        app.process_name.clone(),
+                        list.name.clone(),
+                        list.shortcuts.iter().map(|s| s.key_combo.clone()).collect(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn export_keys_lists_by_process_name() {
+        let (_, apps) = seed_export_data("bundle-export");
+        let bundled = storage::load_applications().unwrap()[0].clone();
+        let bundle = build_bundle(&ExportOptions::default()).unwrap();
+
+        // Applications without lists are left out
+        let process_names: Vec<&str> = bundle
+            .applications
+            .iter()
+            .map(|a| a.process_name.as_str())
+            .collect();
+        assert_eq!(
+            process_names,
+            vec![bundled.process_name.as_str(), "tool.exe", "other.exe"]
+        );
+        assert_eq!(
+            list_summary(&bundle),
+            vec![
+                (
+                    bundled.process_name.clone(),
+                    "Mine".to_string(),
+                    vec!["Alt+1".to_string()]
+                ),
+                (
+                    "tool.exe".to_string(),
+                    "General".to_string(),
+                    vec!["Ctrl+K".to_string(), "Ctrl+S".to_string()]
+                ),
+                (
+                    "tool.exe".to_string(),
+                    "Editing".to_string(),
+                    vec!["Ctrl+D".to_string()]
+                ),
+                (
+                    "other.exe".to_string(),
+                    "General".to_string(),
+                    vec!["F5".to_string()]
+                ),
+            ]
+        );
+        assert_eq!(bundle.applications[1].match_rules, apps[0].match_rules);
+        assert!(bundle.settings.is_none());
+        assert_eq!(bundle.summary().shortcut_count, 5);
+
+        // Ids stay on this machine
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(!json.contains(&apps[0].id));
+    }
+
+    #[test]
+    fn export_only_includes_the_selected_applications() {
+        let (_, apps) = seed_export_data("bundle-export-filter");
+        let options = ExportOptions {
+            application_ids: Some(vec![apps[1].id.clone()]),
+            include_settings: false,
+        };
+        let bundle = build_bundle(&options).unwrap();
+        assert_eq!(
+            list_summary(&bundle),
+            vec![(
+                "other.exe".to_string(),
+                "General".to_string(),
+                vec!["F5".to_string()]
+            )]
+        );
+
+        let unknown = ExportOptions {
+            application_ids: Some(vec!["no-such-app".to_string()]),
+            include_settings: false,
+        };
+        assert!(matches!(
+            build_bundle(&unknown),
+            Err(StorageError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn exported_bundles_import_unchanged() {
+        let (dir, _) = seed_export_data("bundle-round-trip");
+        let exported = build_bundle(&ExportOptions::default()).unwrap();
+        let path = dir.join("bundle.json");
+        storage::write_json(&path, &exported).unwrap();
+
+        use_scratch_data_dir("bundle-round-trip-target");
+        let report = import_bundle(&path, ImportMode::SkipExisting, false, no_settings).unwrap();
+        assert_eq!(report.new_applications, vec!["Tool", "Other"]);
+        assert!(report
+            .lists
+            .iter()
+            .all(|l| l.action == ListImportAction::Created));
+
+        let reexported = build_bundle(&ExportOptions::default()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&reexported.applications).unwrap(),
+            serde_json::to_value(&exported.applications).unwrap()
+        );
+    }
+
+    #[test]
+    fn failing_settings_step_aborts_the_import() {
+        let dir = use_scratch_data_dir("bundle-settings-rejected");
+        let mut bundle = bundle(vec![bundle_list("General")]);
+        bundle.settings = Some(storage::default_settings());
+        let path = dir.join("bundle.json");
+        storage::write_json(&path, &bundle).unwrap();
+
+        let result = import_bundle(&path, ImportMode::Overwrite, false, |_| {
+            Err(StorageError::validation("hotkey taken"))
+        });
+        assert!(matches!(result, Err(StorageError::Validation { .. })));
+        assert!(storage::load_lists().unwrap().is_empty());
+        assert!(storage::load_user_applications().unwrap().is_empty());
+    }
+
+    #[test]
+    fn failing_settings_step_restores_the_lists_it_replaced() {
+        let (dir, _) = import_over_local_list("bundle-settings-rollback", ImportMode::SkipExisting);
+        let mut incoming = bundle(vec![list_with("General", vec![shortcut("Ctrl+S", "Save")])]);
+        incoming.settings = Some(storage::default_settings());
+        let path = dir.join("bundle.json");
+        storage::write_json(&path, &incoming).unwrap();
+
+        let result = import_bundle(&path, ImportMode::Overwrite, false, |_| {
+            Err(StorageError::validation("hotkey taken"))
+        });
+        assert!(result.is_err());
+        assert_eq!(
+            stored_lists(),
+            vec![(
+                "General".to_string(),
+                pairs(&[("Ctrl+K", "Open menu"), ("Ctrl+P", "Print")])
+            )]
+        );
+        assert_eq!(storage::load_user_applications().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn invalid_match_rules_are_rejected_before_anything_is_written() {
+        let dir = use_scratch_data_dir("bundle-bad-rule");
+        let mut list = bundle_list("Docs");
+        list.match_rules.push(MatchRule {
+            field: MatchField::WindowTitle,
+            kind: MatchKind::Regex,
+            pattern: "(unclosed".to_string(),
+            priority: 0,
+        });
+        let path = dir.join("bundle.json");
+        storage::write_json(&path, &bundle(vec![list])).unwrap();
+
+        for dry_run in [true, false] {
+            let result = import_bundle(&path, ImportMode::SkipExisting, dry_run, no_settings);
+            assert!(matches!(result, Err(StorageError::Validation { .. })));
+        }
+        assert!(storage::load_lists().unwrap().is_empty());
+        assert!(storage::load_user_applications().unwrap().is_empty());
+    }
+}
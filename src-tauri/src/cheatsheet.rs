@@ -0,0 +1,432 @@
+// Printable cheat sheets: render selected shortcut lists to Markdown tables or
+// a self-contained HTML page (with a print stylesheet, so "Print → Save as
+// PDF" gives a clean handout).
+//
+// Lists are grouped under their application's name and shortcuts are ordered
+// by `Shortcut.order`, matching what the popup shows.
+
+use crate::key_combo::{self, KeyStyle, Platform};
+use crate::storage::{self, Application, ShortcutList, StorageError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const DEFAULT_TITLE: &str = "Keyboard Shortcuts";
+const MAX_COLUMNS: u8 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheatSheetFormat {
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheatSheetOptions {
+    // Page heading; defaults to "Keyboard Shortcuts"
+    pub title: Option<String>,
+    // Number of columns lists flow into (HTML only, 1-4). 0 is treated as 1.
+    pub columns: u8,
+    // Platform to show keys for (CmdOrCtrl → Cmd/Ctrl); defaults to this one
+    pub platform: Option<Platform>,
+}
+
+struct Section<'a> {
+    application: String,
+    lists: Vec<&'a ShortcutList>,
+}
+
+// Render the given lists to a string in `format`
+pub fn render_cheatsheet(
+    list_ids: &[String],
+    format: CheatSheetFormat,
+    options: &CheatSheetOptions,
+) -> Result<String, StorageError> {
+    if list_ids.is_empty() {
+        return Err(StorageError::validation("No lists selected"));
+    }
+    let apps = storage::load_applications()?;
+    let lists = storage::load_lists()?;
+    let sections = build_sections(list_ids, &apps, &lists)?;
+    let title = options.title.as_deref().unwrap_or(DEFAULT_TITLE);
+    let platform = options.platform.unwrap_or_else(Platform::current);
+    Ok(match format {
+        CheatSheetFormat::Markdown => render_markdown(title, &sections, platform),
+        CheatSheetFormat::Html => render_html(title, &sections, options.columns, platform),
+    })
+}
+
+// Render the given lists and write the result to `path`
+pub fn export_cheatsheet(
+    path: &Path,
+    list_ids: &[String],
+    format: CheatSheetFormat,
+    options: &CheatSheetOptions,
+) -> Result<(), StorageError> {
+    let contents = render_cheatsheet(list_ids, format, options)?;
+    storage::write_atomic(path, contents.as_bytes())
+}
+
+// Group the selected lists by application name (sorted), keeping the
+// selection order of lists within each application
+fn build_sections<'a>(
+    list_ids: &[String],
+    apps: &[Application],
+    lists: &'a [ShortcutList],
+) -> Result<Vec<Section<'a>>, StorageError> {
+    let mut sections: Vec<Section<'a>> = Vec::new();
+    for id in list_ids {
+        let list = lists
+            .iter()
+            .find(|l| &l.id == id)
+            .ok_or_else(|| StorageError::validation(format!("Unknown list id: {id}")))?;
+        let application = apps
+            .iter()
+            .find(|a| a.id == list.application_id)
+            .map_or_else(|| list.application_id.clone(), |a| a.name.clone());
+        match sections.iter_mut().find(|s| s.application == application) {
+            Some(section) => section.lists.push(list),
+            None => sections.push(Section {
+                application,
+                lists: vec![list],
+            }),
+        }
+    }
+    sections.sort_by_key(|s| s.application.to_lowercase());
+    Ok(sections)
+}
+
+fn sorted_shortcuts(list: &ShortcutList) -> Vec<&storage::Shortcut> {
+    let mut shortcuts: Vec<_> = list.shortcuts.iter().collect();
+    shortcuts.sort_by_key(|s| s.order);
+    shortcuts
+}
+
+// Split a key_combo into steps and keys: "Ctrl+K, Ctrl+S" → [[Ctrl, K], [Ctrl, S]].
+// A trailing "+" is the plus key itself ("Ctrl++").
+fn combo_keys(key_combo: &str) -> Vec<Vec<&str>> {
+    key_combo
+        .split(", ")
+        .map(|step| {
+            if step == "+" {
+                return vec!["+"];
+            }
+            match step.strip_suffix("++") {
+                Some(modifiers) => {
+                    let mut keys: Vec<&str> = modifiers.split('+').collect();
+                    keys.push("+");
+                    keys
+                }
+                None => step.split('+').collect(),
+            }
+        })
+        .collect()
+}
+
+fn render_markdown(title: &str, sections: &[Section], platform: Platform) -> String {
+    let mut out = format!("# {}\n", escape_markdown(title));
+    for section in sections {
+        out.push_str(&format!("\n## {}\n", escape_markdown(&section.application)));
+        for list in &section.lists {
+            out.push_str(&format!("\n### {}\n\n", escape_markdown(&list.name)));
+            out.push_str("| Shortcut | Description |\n| --- | --- |\n");
+            for shortcut in sorted_shortcuts(list) {
+                out.push_str(&format!(
+                    "| {} | {} |\n",
+                    markdown_code(&key_combo::render(
+                        &shortcut.key_combo,
+                        platform,
+                        KeyStyle::Text
+                    )),
+                    escape_markdown(&shortcut.description)
+                ));
+            }
+        }
+    }
+    out
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+// Inline code that survives backticks and pipes inside a table cell
+fn markdown_code(text: &str) -> String {
+    let text = text.replace('|', "\\|");
+    if text.contains('`') {
+        format!("`` {text} ``")
+    } else {
+        format!("`{text}`")
+    }
+}
+
+fn render_html(title: &str, sections: &[Section], columns: u8, platform: Platform) -> String {
+    let columns = columns.clamp(1, MAX_COLUMNS);
+    let mut body = String::new();
+    for section in sections {
+        body.push_str(&format!(
+            "<section class=\"app\">\n<h2>{}</h2>\n<div class=\"lists\">\n",
+            escape_html(&section.application)
+        ));
+        for list in &section.lists {
+            body.push_str(&format!(
+                "<div class=\"list\">\n<h3>{}</h3>\n<table>\n<tbody>\n",
+                escape_html(&list.name)
+            ));
+            for shortcut in sorted_shortcuts(list) {
+                body.push_str(&format!(
+                    "<tr><td class=\"keys\">{}</td><td>{}</td></tr>\n",
+                    html_keys(&key_combo::render(
+                        &shortcut.key_combo,
+                        platform,
+                        KeyStyle::Text
+                    )),
+                    escape_html(&shortcut.description)
+                ));
+            }
+            body.push_str("</tbody>\n</table>\n</div>\n");
+        }
+        body.push_str("</div>\n</section>\n");
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+{style}
+.lists {{ column-count: {columns}; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+{body}</body>
+</html>
+"#,
+        title = escape_html(title),
+        style = HTML_STYLE,
+    )
+}
+
+// "Ctrl+K, Ctrl+S" → <kbd>Ctrl</kbd>+<kbd>K</kbd> <span class="then">then</span> ...
+// Alternatives written as "F5 or Ctrl+R" are kept apart.
+fn html_keys(key_combo: &str) -> String {
+    key_combo
+        .split(" or ")
+        .map(|alternative| {
+            combo_keys(alternative)
+                .iter()
+                .map(|step| {
+                    step.iter()
+                        .map(|key| format!("<kbd>{}</kbd>", escape_html(key)))
+                        .collect::<Vec<_>>()
+                        .join("+")
+                })
+                .collect::<Vec<_>>()
+                .join(" <span class=\"then\">then</span> ")
+        })
+        .collect::<Vec<_>>()
+        .join(" <span class=\"then\">or</span> ")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+const HTML_STYLE: &str = r#"* { box-sizing: border-box; }
+body {
+  font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, sans-serif;
+  color: #1f2328;
+  margin: 2rem;
+  font-size: 14px;
+}
+h1 { font-size: 1.6rem; margin: 0 0 1rem; }
+h2 { font-size: 1.2rem; margin: 1.5rem 0 0.5rem; border-bottom: 2px solid #d0d7de; padding-bottom: 0.25rem; }
+h3 { font-size: 1rem; margin: 0 0 0.4rem; color: #57606a; }
+.lists { column-gap: 2rem; }
+.list { break-inside: avoid; margin-bottom: 1rem; }
+table { border-collapse: collapse; width: 100%; }
+td { padding: 0.2rem 0.4rem; border-bottom: 1px solid #eaeef2; vertical-align: top; }
+td.keys { white-space: nowrap; width: 1%; }
+kbd {
+  display: inline-block;
+  font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace;
+  font-size: 0.85em;
+  padding: 0.05rem 0.35rem;
+  border: 1px solid #d0d7de;
+  border-bottom-width: 2px;
+  border-radius: 4px;
+  background: #f6f8fa;
+}
+.then { color: #8c959f; font-size: 0.85em; }
+@media print {
+  @page { margin: 1.5cm; }
+  body { margin: 0; font-size: 10pt; }
+  h2 { break-after: avoid; }
+  kbd { background: none; }
+}"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Shortcut;
+
+    fn app(id: &str, name: &str) -> Application {
+        Application {
+            id: id.to_string(),
+            name: name.to_string(),
+            process_name: String::new(),
+            detection_name: String::new(),
+            icon: None,
+            last_used_list_id: None,
+            match_rules: Vec::new(),
+            is_browser: false,
+        }
+    }
+
+    fn list(id: &str, application_id: &str, shortcuts: &[(&str, &str, i32)]) -> ShortcutList {
+        ShortcutList {
+            id: id.to_string(),
+            name: id.to_string(),
+            application_id: application_id.to_string(),
+            shortcuts: shortcuts
+                .iter()
+                .map(|(key_combo, description, order)| Shortcut {
+                    id: format!("{id}-{order}"),
+                    key_combo: key_combo.to_string(),
+                    description: description.to_string(),
+                    order: *order,
+                })
+                .collect(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: "2025-01-01T00:00:00Z".to_string(),
+            match_rules: Vec::new(),
+        }
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn combo_keys_splits_steps_and_keeps_the_plus_key() {
+        assert_eq!(combo_keys("Ctrl++"), vec![vec!["Ctrl", "+"]]);
+        assert_eq!(
+            combo_keys("Ctrl+K, Ctrl+S"),
+            vec![vec!["Ctrl", "K"], vec!["Ctrl", "S"]]
+        );
+        assert_eq!(combo_keys("+"), vec![vec!["+"]]);
+        assert_eq!(
+            combo_keys("Ctrl+,, Ctrl+S"),
+            vec![vec!["Ctrl", ","], vec!["Ctrl", "S"]]
+        );
+    }
+
+    #[test]
+    fn markdown_table_cells_escape_pipes_and_backticks() {
+        assert_eq!(escape_markdown("a | b `c`"), "a \\| b \\`c\\`");
+        assert_eq!(escape_markdown("Go to #1_*x*"), "Go to \\#1\\_\\*x\\*");
+        assert_eq!(markdown_code("Ctrl+|"), "`Ctrl+\\|`");
+        assert_eq!(markdown_code("Ctrl+`"), "`` Ctrl+` ``");
+
+        let apps = vec![app("app-a", "Editor")];
+        let lists = vec![list(
+            "General",
+            "app-a",
+            &[("Ctrl+`", "Toggle | terminal", 0)],
+        )];
+        let sections = build_sections(&ids(&["General"]), &apps, &lists).unwrap();
+        let markdown = render_markdown("Sheet", &sections, Platform::Linux);
+        assert!(markdown.contains("| `` Ctrl+` `` | Toggle \\| terminal |\n"));
+    }
+
+    #[test]
+    fn escape_html_covers_markup_and_quotes() {
+        assert_eq!(
+            escape_html(r#"<b>"Tom" & 'Jerry'</b>"#),
+            "&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;"
+        );
+        assert_eq!(html_keys("Ctrl+<"), "<kbd>Ctrl</kbd>+<kbd>&lt;</kbd>");
+    }
+
+    #[test]
+    fn sections_group_by_application_sorted_by_name() {
+        let apps = vec![app("app-z", "zsh"), app("app-b", "Browser")];
+        let lists = vec![
+            list("Shell", "app-z", &[]),
+            list("Tabs", "app-b", &[]),
+            list("Orphan", "app-gone", &[]),
+            list("Pages", "app-b", &[]),
+        ];
+
+        let sections =
+            build_sections(&ids(&["Shell", "Tabs", "Orphan", "Pages"]), &apps, &lists).unwrap();
+        let grouped: Vec<(&str, Vec<&str>)> = sections
+            .iter()
+            .map(|s| {
+                let names = s.lists.iter().map(|l| l.name.as_str()).collect();
+                (s.application.as_str(), names)
+            })
+            .collect();
+        assert_eq!(
+            grouped,
+            vec![
+                ("app-gone", vec!["Orphan"]),
+                ("Browser", vec!["Tabs", "Pages"]),
+                ("zsh", vec!["Shell"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_list_ids_are_rejected() {
+        let lists = vec![list("General", "app-a", &[])];
+        let err = build_sections(&ids(&["General", "missing"]), &[], &lists)
+            .err()
+            .unwrap();
+        assert!(matches!(err, StorageError::Validation { message } if message.contains("missing")));
+    }
+
+    #[test]
+    fn shortcuts_render_in_order() {
+        let lists = vec![list(
+            "General",
+            "app-a",
+            &[("Ctrl+S", "Save", 2), ("Ctrl+O", "Open", 1)],
+        )];
+        let sections = build_sections(&ids(&["General"]), &[], &lists).unwrap();
+        let markdown = render_markdown("Sheet", &sections, Platform::Linux);
+        let open = markdown.find("Open").unwrap();
+        assert!(open < markdown.find("Save").unwrap());
+    }
+
+    #[test]
+    fn html_columns_are_clamped() {
+        assert!(render_html("Sheet", &[], 0, Platform::Linux).contains("column-count: 1;"));
+        assert!(render_html("Sheet", &[], 3, Platform::Linux).contains("column-count: 3;"));
+        assert!(render_html("Sheet", &[], 9, Platform::Linux).contains("column-count: 4;"));
+    }
+}
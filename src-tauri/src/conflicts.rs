@@ -0,0 +1,402 @@
+// Conflict detection for an application's shortcuts.
+//
+// Reports the same key combination used twice in one list, used in several
+// lists of the application with different meanings, or colliding with
+// Will-Shortcut's own global hotkeys and popup bindings. Combos are compared
+// in canonical form (see key_combo), with CmdOrCtrl resolved for this
+// platform and "A or B" entries checked per alternative.
+
+use crate::key_combo::{self, KeyCombo, Platform};
+use crate::storage::{self, Settings, ShortcutList, StorageError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictEntry {
+    pub list_id: String,
+    pub list_name: String,
+    pub shortcut_id: String,
+    pub key_combo: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Conflict {
+    // One list has the same combo more than once
+    DuplicateInList {
+        key_combo: String,
+        shortcuts: Vec<ConflictEntry>,
+    },
+    // Several lists of the application give the combo different descriptions
+    AcrossLists {
+        key_combo: String,
+        shortcuts: Vec<ConflictEntry>,
+    },
+    // A global hotkey swallows this combo (or the first step of a sequence)
+    // before the application sees it
+    GlobalHotkey {
+        key_combo: String,
+        hotkey: String,
+        shortcuts: Vec<ConflictEntry>,
+    },
+    // Same combo as one of the popup's own keyboard_shortcuts bindings
+    AppBinding {
+        key_combo: String,
+        binding: String,
+        shortcuts: Vec<ConflictEntry>,
+    },
+}
+
+// A shortcut's canonical combos; one per "or" alternative
+struct Keyed<'a> {
+    keys: Vec<String>,
+    first_steps: Vec<String>,
+    entry: ConflictEntry,
+    list: &'a ShortcutList,
+}
+
+pub fn find_conflicts(app_id: &str) -> Result<Vec<Conflict>, StorageError> {
+    let lists = storage::load_lists_for_application(app_id)?;
+    let settings = storage::load_settings()?;
+    Ok(conflicts_in(&lists, &settings, Platform::current()))
+}
+
+pub fn conflicts_in(
+    lists: &[ShortcutList],
+    settings: &Settings,
+    platform: Platform,
+) -> Vec<Conflict> {
+    let keyed: Vec<Keyed> = lists
+        .iter()
+        .flat_map(|list| list.shortcuts.iter().map(move |s| (list, s)))
+        .map(|(list, shortcut)| {
+            let (keys, first_steps) = canonical_keys(&shortcut.key_combo, platform);
+            Keyed {
+                keys,
+                first_steps,
+                entry: ConflictEntry {
+                    list_id: list.id.clone(),
+                    list_name: list.name.clone(),
+                    shortcut_id: shortcut.id.clone(),
+                    key_combo: shortcut.key_combo.clone(),
+                    description: shortcut.description.clone(),
+                },
+                list,
+            }
+        })
+        .collect();
+
+    let mut all_keys: Vec<&String> = keyed.iter().flat_map(|k| &k.keys).collect();
+    all_keys.sort();
+    all_keys.dedup();
+
+    let mut conflicts = Vec::new();
+    for key in all_keys {
+        let users: Vec<&Keyed> = keyed.iter().filter(|k| k.keys.contains(key)).collect();
+
+        for list in lists {
+            let in_list: Vec<ConflictEntry> = users
+                .iter()
+                .filter(|k| k.list.id == list.id)
+                .map(|k| k.entry.clone())
+                .collect();
+            if in_list.len() > 1 {
+                conflicts.push(Conflict::DuplicateInList {
+                    key_combo: key.clone(),
+                    shortcuts: in_list,
+                });
+            }
+        }
+
+        let mut list_ids: Vec<&str> = users.iter().map(|k| k.list.id.as_str()).collect();
+        list_ids.sort();
+        list_ids.dedup();
+        let first_description = users.first().map(|k| k.entry.description.to_lowercase());
+        let descriptions_differ = users
+            .iter()
+            .any(|k| Some(k.entry.description.to_lowercase()) != first_description);
+        if list_ids.len() > 1 && descriptions_differ {
+            conflicts.push(Conflict::AcrossLists {
+                key_combo: key.clone(),
+                shortcuts: users.iter().map(|k| k.entry.clone()).collect(),
+            });
+        }
+    }
+
+    for binding in settings.global_bindings() {
+        let (hotkeys, _) = canonical_keys(&binding.hotkey, platform);
+        for hotkey in &hotkeys {
+            let hit: Vec<ConflictEntry> = keyed
+                .iter()
+                .filter(|k| k.keys.contains(hotkey) || k.first_steps.contains(hotkey))
+                .map(|k| k.entry.clone())
+                .collect();
+            if !hit.is_empty() {
+                conflicts.push(Conflict::GlobalHotkey {
+                    key_combo: hotkey.clone(),
+                    hotkey: binding.hotkey.clone(),
+                    shortcuts: hit,
+                });
+            }
+        }
+    }
+
+    let bindings = &settings.keyboard_shortcuts;
+    for (binding, combo) in [
+        ("move_up", &bindings.move_up),
+        ("move_down", &bindings.move_down),
+        ("duplicate", &bindings.duplicate),
+        ("delete", &bindings.delete),
+        ("add_new", &bindings.add_new),
+        ("add_above", &bindings.add_above),
+        ("add_below", &bindings.add_below),
+    ] {
+        let (binding_keys, _) = canonical_keys(combo, platform);
+        for binding_key in &binding_keys {
+            let hit: Vec<ConflictEntry> = keyed
+                .iter()
+                .filter(|k| k.keys.contains(binding_key))
+                .map(|k| k.entry.clone())
+                .collect();
+            if !hit.is_empty() {
+                conflicts.push(Conflict::AppBinding {
+                    key_combo: binding_key.clone(),
+                    binding: binding.to_string(),
+                    shortcuts: hit,
+                });
+            }
+        }
+    }
+
+    conflicts
+}
+
+// Canonical form of each alternative, plus the first step of each
+// multi-step alternative ("Ctrl+K, Ctrl+S" → "Ctrl+K")
+fn canonical_keys(key_combo: &str, platform: Platform) -> (Vec<String>, Vec<String>) {
+    if key_combo.trim().is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+    match KeyCombo::parse_alternatives(key_combo) {
+        Ok(alternatives) => {
+            let resolved: Vec<KeyCombo> =
+                alternatives.iter().map(|c| c.resolve(platform)).collect();
+            let keys = resolved.iter().map(KeyCombo::to_string).collect();
+            let first_steps = resolved
+                .iter()
+                .filter(|c| c.steps.len() > 1)
+                .map(|c| c.steps[0].to_string())
+                .collect();
+            (keys, first_steps)
+        }
+        Err(_) => (vec![key_combo::normalize(key_combo)], Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{default_settings, GlobalAction, GlobalHotkey, Shortcut};
+
+    fn list(id: &str, shortcuts: &[(&str, &str)]) -> ShortcutList {
+        ShortcutList {
+            id: id.to_string(),
+            name: id.to_string(),
+            application_id: "app-test".to_string(),
+            shortcuts: shortcuts
+                .iter()
+                .enumerate()
+                .map(|(order, (key_combo, description))| Shortcut {
+                    id: format!("{id}-{order}"),
+                    key_combo: key_combo.to_string(),
+                    description: description.to_string(),
+                    order: order as i32,
+                })
+                .collect(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: "2025-01-01T00:00:00Z".to_string(),
+            match_rules: Vec::new(),
+        }
+    }
+
+    // Settings whose hotkey and popup bindings stay out of the tests' way
+    fn quiet_settings() -> Settings {
+        let mut settings = default_settings();
+        settings.global_hotkey = "Ctrl+Shift+Alt+F12".to_string();
+        settings.keyboard_shortcuts.move_up = "Alt+F1".to_string();
+        settings.keyboard_shortcuts.move_down = "Alt+F2".to_string();
+        settings.keyboard_shortcuts.duplicate = "Alt+F3".to_string();
+        settings.keyboard_shortcuts.delete = "Alt+F4".to_string();
+        settings.keyboard_shortcuts.add_new = "Alt+F5".to_string();
+        settings.keyboard_shortcuts.add_above = "Alt+F6".to_string();
+        settings.keyboard_shortcuts.add_below = "Alt+F7".to_string();
+        settings
+    }
+
+    // (kind, canonical combo, shortcut ids) of each conflict
+    fn summary(conflicts: &[Conflict]) -> Vec<(&'static str, String, Vec<String>)> {
+        conflicts
+            .iter()
+            .map(|conflict| {
+                let (kind, key_combo, shortcuts) = match conflict {
+                    Conflict::DuplicateInList {
+                        key_combo,
+                        shortcuts,
+                    } => ("duplicate_in_list", key_combo, shortcuts),
+                    Conflict::AcrossLists {
+                        key_combo,
+                        shortcuts,
+                    } => ("across_lists", key_combo, shortcuts),
+                    Conflict::GlobalHotkey {
+                        key_combo,
+                        shortcuts,
+                        ..
+                    } => ("global_hotkey", key_combo, shortcuts),
+                    Conflict::AppBinding {
+                        key_combo,
+                        shortcuts,
+                        ..
+                    } => ("app_binding", key_combo, shortcuts),
+                };
+                let ids = shortcuts.iter().map(|s| s.shortcut_id.clone()).collect();
+                (kind, key_combo.clone(), ids)
+            })
+            .collect()
+    }
+
+    fn entry(
+        kind: &'static str,
+        key_combo: &str,
+        ids: &[&str],
+    ) -> (&'static str, String, Vec<String>) {
+        (
+            kind,
+            key_combo.to_string(),
+            ids.iter().map(|id| id.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn same_combo_twice_in_a_list() {
+        let lists = vec![list(
+            "main",
+            &[
+                ("Ctrl+S", "Save"),
+                ("ctrl+s", "Save all"),
+                ("Ctrl+O", "Open"),
+            ],
+        )];
+
+        let conflicts = conflicts_in(&lists, &quiet_settings(), Platform::Linux);
+        assert_eq!(
+            summary(&conflicts),
+            vec![entry("duplicate_in_list", "Ctrl+S", &["main-0", "main-1"])]
+        );
+    }
+
+    #[test]
+    fn across_lists_only_when_descriptions_differ() {
+        let lists = vec![
+            list("a", &[("Ctrl+S", "Save file"), ("Ctrl+P", "Print")]),
+            list(
+                "b",
+                &[("Shift+Ctrl+S", "save FILE"), ("Ctrl+P", "Quick open")],
+            ),
+        ];
+
+        let conflicts = conflicts_in(&lists, &quiet_settings(), Platform::Linux);
+        assert_eq!(
+            summary(&conflicts),
+            vec![entry("across_lists", "Ctrl+P", &["a-1", "b-1"])]
+        );
+
+        let same_meaning = vec![
+            list("a", &[("Ctrl+S", "Save file")]),
+            list("b", &[("Ctrl+S", "save FILE")]),
+        ];
+        assert!(conflicts_in(&same_meaning, &quiet_settings(), Platform::Linux).is_empty());
+    }
+
+    #[test]
+    fn global_hotkey_hits_the_first_step_of_a_chord() {
+        let mut settings = quiet_settings();
+        settings.global_hotkey = "Ctrl+K".to_string();
+        settings.global_actions.push(GlobalHotkey {
+            hotkey: "Ctrl+Alt+S".to_string(),
+            action: GlobalAction::OpenSettings,
+            peek: false,
+        });
+        let lists = vec![list(
+            "main",
+            &[
+                ("Ctrl+K, Ctrl+S", "Save all"),
+                ("Ctrl+S, Ctrl+K", "Other"),
+                ("Ctrl+Alt+S", "Settings"),
+            ],
+        )];
+
+        let conflicts = conflicts_in(&lists, &settings, Platform::Linux);
+        assert_eq!(
+            summary(&conflicts),
+            vec![
+                entry("global_hotkey", "Ctrl+K", &["main-0"]),
+                entry("global_hotkey", "Ctrl+Alt+S", &["main-2"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn popup_bindings_are_reported() {
+        let mut settings = quiet_settings();
+        settings.keyboard_shortcuts.duplicate = "Ctrl+D".to_string();
+        let lists = vec![list(
+            "main",
+            &[("Ctrl+D", "Bookmark"), ("Ctrl+K, Ctrl+D", "Chord")],
+        )];
+
+        let conflicts = conflicts_in(&lists, &settings, Platform::Linux);
+        assert_eq!(
+            summary(&conflicts),
+            vec![entry("app_binding", "Ctrl+D", &["main-0"])]
+        );
+        assert!(matches!(
+            &conflicts[0],
+            Conflict::AppBinding { binding, .. } if binding == "duplicate"
+        ));
+    }
+
+    #[test]
+    fn alternatives_are_checked_one_by_one() {
+        let lists = vec![list(
+            "main",
+            &[("F5 or Ctrl+R", "Reload"), ("Ctrl+R", "Replace")],
+        )];
+
+        let conflicts = conflicts_in(&lists, &quiet_settings(), Platform::Linux);
+        assert_eq!(
+            summary(&conflicts),
+            vec![entry("duplicate_in_list", "Ctrl+R", &["main-0", "main-1"])]
+        );
+    }
+
+    #[test]
+    fn cmd_or_ctrl_resolves_for_the_platform() {
+        let lists = vec![list("main", &[("CmdOrCtrl+S", "Save"), ("Ctrl+S", "Stop")])];
+
+        let on_linux = conflicts_in(&lists, &quiet_settings(), Platform::Linux);
+        assert_eq!(
+            summary(&on_linux),
+            vec![entry("duplicate_in_list", "Ctrl+S", &["main-0", "main-1"])]
+        );
+        assert!(conflicts_in(&lists, &quiet_settings(), Platform::Macos).is_empty());
+
+        let lists = vec![list(
+            "main",
+            &[("CmdOrCtrl+S", "Save"), ("Cmd+S", "Save as")],
+        )];
+        let on_macos = conflicts_in(&lists, &quiet_settings(), Platform::Macos);
+        assert_eq!(summary(&on_macos).len(), 1);
+        assert_eq!(summary(&on_macos)[0].2, vec!["main-0", "main-1"]);
+        assert!(conflicts_in(&lists, &quiet_settings(), Platform::Windows).is_empty());
+    }
+}
@@ -0,0 +1,530 @@
+// CSV export/import of shortcuts for bulk editing in a spreadsheet.
+//
+// Columns: key_combo, description, order, list, process_name. On import the
+// header row decides the column order. Rows are routed to lists by
+// (process_name, list) unless a target list is given, in which case every row
+// goes into that list. Each imported list's shortcuts are replaced by its rows.
+//
+// Every row is validated before anything is saved; if any row is invalid the
+// import is abandoned and the report lists each problem with its line number.
+
+use crate::key_combo;
+use crate::storage::{self, Application, Shortcut, ShortcutList, StorageError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const HEADER: [&str; 5] = ["key_combo", "description", "order", "list", "process_name"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvExportSummary {
+    pub list_count: usize,
+    pub row_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvRowError {
+    // 1-based line in the file (the header is line 1)
+    pub line: u64,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvListSummary {
+    pub application_id: String,
+    pub list_id: String,
+    pub list_name: String,
+    pub created: bool,
+    pub shortcut_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvImportReport {
+    pub row_count: usize,
+    // Empty when the import was abandoned because of errors
+    pub lists: Vec<CsvListSummary>,
+    pub errors: Vec<CsvRowError>,
+}
+
+// A validated row, already resolved to its application
+struct Row {
+    line: u64,
+    application_id: String,
+    // Set when importing into a target list, which may share its name with
+    // other lists of the application
+    list_id: Option<String>,
+    list_name: String,
+    key_combo: String,
+    description: String,
+    order: Option<i32>,
+}
+
+// Column positions found in the header row
+struct Columns {
+    key_combo: usize,
+    description: usize,
+    order: Option<usize>,
+    list: Option<usize>,
+    process_name: Option<usize>,
+}
+
+pub fn export_csv(path: &Path, list_ids: &[String]) -> Result<CsvExportSummary, StorageError> {
+    if list_ids.is_empty() {
+        return Err(StorageError::validation("No lists selected"));
+    }
+    let apps = storage::load_applications()?;
+    let lists = storage::load_lists()?;
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let mut row_count = 0;
+    writer.write_record(HEADER).map_err(csv_write_error)?;
+    for id in list_ids {
+        let list = lists
+            .iter()
+            .find(|l| &l.id == id)
+            .ok_or_else(|| StorageError::validation(format!("Unknown list id: {id}")))?;
+        let process_name = apps
+            .iter()
+            .find(|a| a.id == list.application_id)
+            .map_or("", |a| a.process_name.as_str());
+        let mut shortcuts: Vec<&Shortcut> = list.shortcuts.iter().collect();
+        shortcuts.sort_by_key(|s| s.order);
+        for shortcut in shortcuts {
+            writer
+                .write_record([
+                    shortcut.key_combo.as_str(),
+                    shortcut.description.as_str(),
+                    &shortcut.order.to_string(),
+                    list.name.as_str(),
+                    process_name,
+                ])
+                .map_err(csv_write_error)?;
+            row_count += 1;
+        }
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| StorageError::validation(e.to_string()))?;
+    storage::write_atomic(path, &bytes)?;
+    Ok(CsvExportSummary {
+        list_count: list_ids.len(),
+        row_count,
+    })
+}
+
+fn csv_write_error(e: csv::Error) -> StorageError {
+    StorageError::validation(format!("Failed to write CSV: {e}"))
+}
+
+// Import rows from `path`. With `target_list` (a list id) every row goes into
+// that list and the list/process_name columns are ignored.
+pub fn import_csv(path: &Path, target_list: Option<&str>) -> Result<CsvImportReport, StorageError> {
+    let contents = std::fs::read(path).map_err(|e| StorageError::io(path, e))?;
+    let apps = storage::load_applications()?;
+    let target = match target_list {
+        Some(id) => Some(
+            storage::load_lists()?
+                .into_iter()
+                .find(|l| l.id == id)
+                .ok_or_else(|| StorageError::validation(format!("Unknown list id: {id}")))?,
+        ),
+        None => None,
+    };
+
+    let (rows, mut errors) = read_rows(&contents, &apps, target.as_ref());
+    let row_count = rows.len() + errors.iter().filter(|e| e.line > 1).count();
+    check_duplicates(&rows, &mut errors);
+    if !errors.is_empty() {
+        errors.sort_by_key(|e| e.line);
+        return Ok(CsvImportReport {
+            row_count,
+            lists: Vec::new(),
+            errors,
+        });
+    }
+
+    let lists = apply_rows(&rows)?;
+    Ok(CsvImportReport {
+        row_count,
+        lists,
+        errors,
+    })
+}
+
+fn read_rows(
+    contents: &[u8],
+    apps: &[Application],
+    target: Option<&ShortcutList>,
+) -> (Vec<Row>, Vec<CsvRowError>) {
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(contents);
+
+    let columns = match reader.headers() {
+        Ok(headers) => match find_columns(headers, target.is_some()) {
+            Ok(columns) => columns,
+            Err(reason) => {
+                errors.push(CsvRowError { line: 1, reason });
+                return (rows, errors);
+            }
+        },
+        Err(e) => {
+            errors.push(CsvRowError {
+                line: 1,
+                reason: e.to_string(),
+            });
+            return (rows, errors);
+        }
+    };
+
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                let line = e.position().map_or(0, |p| p.line());
+                errors.push(CsvRowError {
+                    line,
+                    reason: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let line = record.position().map_or(0, |p| p.line());
+        // Spreadsheets often leave trailing blank rows
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        match parse_row(&record, &columns, apps, target) {
+            Ok(row) => rows.push(Row { line, ..row }),
+            Err(reason) => errors.push(CsvRowError { line, reason }),
+        }
+    }
+    (rows, errors)
+}
+
+fn find_columns(headers: &csv::StringRecord, has_target: bool) -> Result<Columns, String> {
+    let find = |name: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(name));
+    let required = |name: &str| find(name).ok_or_else(|| format!("Missing \"{name}\" column"));
+    let columns = Columns {
+        key_combo: required("key_combo")?,
+        description: required("description")?,
+        order: find("order"),
+        list: find("list"),
+        process_name: find("process_name"),
+    };
+    if !has_target && (columns.list.is_none() || columns.process_name.is_none()) {
+        return Err(
+            "\"list\" and \"process_name\" columns are required without a target list".to_string(),
+        );
+    }
+    Ok(columns)
+}
+
+fn parse_row(
+    record: &csv::StringRecord,
+    columns: &Columns,
+    apps: &[Application],
+    target: Option<&ShortcutList>,
+) -> Result<Row, String> {
+    let field = |index: Option<usize>| index.and_then(|i| record.get(i)).unwrap_or("");
+
+    let key_combo = field(Some(columns.key_combo));
+    if key_combo.is_empty() {
+        return Err("key_combo is empty".to_string());
+    }
+    let description = field(Some(columns.description));
+    if description.is_empty() {
+        return Err("description is empty".to_string());
+    }
+    let order = match field(columns.order) {
+        "" => None,
+        value => Some(
+            value
+                .parse::<i32>()
+                .map_err(|_| format!("order \"{value}\" is not a whole number"))?,
+        ),
+    };
+
+    let (application_id, list_id, list_name) = match target {
+        Some(list) => (
+            list.application_id.clone(),
+            Some(list.id.clone()),
+            list.name.clone(),
+        ),
+        None => {
+            let process_name = field(columns.process_name);
+            if process_name.is_empty() {
+                return Err("process_name is empty".to_string());
+            }
+            let app = apps
+                .iter()
+                .find(|a| same_process(&a.process_name, process_name))
+                .ok_or_else(|| format!("No application with process_name \"{process_name}\""))?;
+            let list_name = field(columns.list);
+            if list_name.is_empty() {
+                return Err("list is empty".to_string());
+            }
+            (app.id.clone(), None, list_name.to_string())
+        }
+    };
+
+    Ok(Row {
+        line: 0,
+        application_id,
+        list_id,
+        list_name,
+        key_combo: key_combo::normalize(key_combo),
+        description: description.to_string(),
+        order,
+    })
+}
+
+// "firefox" matches "firefox.exe" so sheets can be shared across platforms
+fn same_process(a: &str, b: &str) -> bool {
+    let trim = |name: &str| {
+        let lower = name.to_ascii_lowercase();
+        lower
+            .strip_suffix(".exe")
+            .map(str::to_string)
+            .unwrap_or(lower)
+    };
+    trim(a) == trim(b)
+}
+
+impl Row {
+    fn same_list(&self, other: &Row) -> bool {
+        self.application_id == other.application_id
+            && self.list_id == other.list_id
+            && self.list_name == other.list_name
+    }
+}
+
+// The same key_combo twice in one list is almost always a spreadsheet mistake
+fn check_duplicates(rows: &[Row], errors: &mut Vec<CsvRowError>) {
+    for (i, row) in rows.iter().enumerate() {
+        if let Some(first) = rows[..i]
+            .iter()
+            .find(|r| r.same_list(row) && key_combo::same_combo(&r.key_combo, &row.key_combo))
+        {
+            errors.push(CsvRowError {
+                line: row.line,
+                reason: format!(
+                    "{} is already in \"{}\" (line {})",
+                    row.key_combo, row.list_name, first.line
+                ),
+            });
+        }
+    }
+}
+
+// Replace the shortcuts of each (application, list name) with its rows,
+// creating lists that don't exist yet, or of the target list when rows carry
+// its id. Existing shortcut ids are kept for unchanged key combos.
+fn apply_rows(rows: &[Row]) -> Result<Vec<CsvListSummary>, StorageError> {
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let mut summaries = Vec::new();
+
+    let mut app_ids: Vec<&str> = Vec::new();
+    for row in rows {
+        if !app_ids.contains(&row.application_id.as_str()) {
+            app_ids.push(&row.application_id);
+        }
+    }
+
+    for app_id in app_ids {
+        let mut app_lists = storage::load_lists_for_application(app_id)?;
+        // First row of each list, in file order
+        let mut firsts: Vec<&Row> = Vec::new();
+        for row in rows.iter().filter(|r| r.application_id == app_id) {
+            if !firsts.iter().any(|first| first.same_list(row)) {
+                firsts.push(row);
+            }
+        }
+
+        for first in firsts {
+            let list_name = first.list_name.as_str();
+            let list_rows: Vec<&Row> = rows.iter().filter(|r| r.same_list(first)).collect();
+            let existing = match &first.list_id {
+                Some(id) => app_lists.iter().position(|l| &l.id == id),
+                None => app_lists.iter().position(|l| l.name == list_name),
+            };
+            if let (Some(id), None) = (&first.list_id, existing) {
+                return Err(StorageError::validation(format!("Unknown list id: {id}")));
+            }
+            let (index, created) = match existing {
+                Some(index) => (index, false),
+                None => {
+                    app_lists.push(ShortcutList {
+                        id: uuid::Uuid::new_v4().to_string(),
+                        name: list_name.to_string(),
+                        application_id: app_id.to_string(),
+                        shortcuts: Vec::new(),
+                        created_at: now.clone(),
+                        updated_at: now.clone(),
+                        match_rules: Vec::new(),
+                    });
+                    (app_lists.len() - 1, true)
+                }
+            };
+            let list = &mut app_lists[index];
+
+            // Rows without an order go after the highest explicit one, in file
+            // order, so they can't collide with it
+            let mut next_order = list_rows
+                .iter()
+                .filter_map(|r| r.order)
+                .max()
+                .map_or(0, |max| max + 1);
+            let mut shortcuts: Vec<Shortcut> = list_rows
+                .iter()
+                .map(|row| Shortcut {
+                    id: list
+                        .shortcuts
+                        .iter()
+                        .find(|s| key_combo::same_combo(&s.key_combo, &row.key_combo))
+                        .map_or_else(|| uuid::Uuid::new_v4().to_string(), |s| s.id.clone()),
+                    key_combo: row.key_combo.clone(),
+                    description: row.description.clone(),
+                    order: row.order.unwrap_or_else(|| {
+                        next_order += 1;
+                        next_order - 1
+                    }),
+                })
+                .collect();
+            shortcuts.sort_by_key(|s| s.order);
+            list.shortcuts = shortcuts;
+            list.updated_at = now.clone();
+
+            summaries.push(CsvListSummary {
+                application_id: app_id.to_string(),
+                list_id: list.id.clone(),
+                list_name: list.name.clone(),
+                created,
+                shortcut_count: list.shortcuts.len(),
+            });
+        }
+        storage::save_lists_for_application(app_id, &app_lists)?;
+    }
+    Ok(summaries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::tests::use_scratch_data_dir;
+
+    fn empty_list(id: &str, app_id: &str, name: &str) -> ShortcutList {
+        ShortcutList {
+            id: id.to_string(),
+            name: name.to_string(),
+            application_id: app_id.to_string(),
+            shortcuts: Vec::new(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: "2025-01-01T00:00:00Z".to_string(),
+            match_rules: Vec::new(),
+        }
+    }
+
+    // Import `csv` into the target list `list_id` of the first bundled application
+    fn import_into(test: &str, list_id: &str, csv: &str) -> Vec<ShortcutList> {
+        let dir = use_scratch_data_dir(test);
+        let app_id = storage::load_applications().unwrap()[0].id.clone();
+        let lists = vec![
+            empty_list("first", &app_id, "General"),
+            empty_list("second", &app_id, "General"),
+        ];
+        storage::save_lists_for_application(&app_id, &lists).unwrap();
+        let path = dir.join("import.csv");
+        std::fs::write(&path, csv).unwrap();
+
+        let report = import_csv(&path, Some(list_id)).unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        storage::load_lists_for_application(&app_id).unwrap()
+    }
+
+    fn shortcuts(list: &ShortcutList) -> Vec<(&str, i32)> {
+        list.shortcuts
+            .iter()
+            .map(|s| (s.key_combo.as_str(), s.order))
+            .collect()
+    }
+
+    #[test]
+    fn target_list_is_found_by_id_not_name() {
+        let lists = import_into(
+            "csv-target-id",
+            "second",
+            "key_combo,description\nCtrl+K,Command menu\n",
+        );
+        assert!(lists[0].shortcuts.is_empty());
+        assert_eq!(shortcuts(&lists[1]), vec![("Ctrl+K", 0)]);
+    }
+
+    #[test]
+    fn blank_orders_follow_the_highest_explicit_one() {
+        let lists = import_into(
+            "csv-orders",
+            "first",
+            "key_combo,description,order\nCtrl+A,a,2\nCtrl+B,b,\nCtrl+C,c,0\nCtrl+D,d,\n",
+        );
+        assert_eq!(
+            shortcuts(&lists[0]),
+            vec![("Ctrl+C", 0), ("Ctrl+A", 2), ("Ctrl+B", 3), ("Ctrl+D", 4)]
+        );
+    }
+
+    #[test]
+    fn key_combos_are_normalized() {
+        let lists = import_into(
+            "csv-normalize",
+            "first",
+            "key_combo,description\nshift+ctrl+p,Command palette\ncontrol + k  control+s,Save all\n",
+        );
+        assert_eq!(
+            shortcuts(&lists[0]),
+            vec![("Ctrl+Shift+P", 0), ("Ctrl+K, Ctrl+S", 1)]
+        );
+    }
+
+    #[test]
+    fn site_lists_round_trip() {
+        use crate::defaults::{WEB_APPS_APP_ID, WEB_APPS_PROCESS_NAME};
+
+        let dir = use_scratch_data_dir("csv-site-lists");
+        let mut gmail = empty_list("gmail", WEB_APPS_APP_ID, "Gmail");
+        gmail.shortcuts = vec![
+            Shortcut {
+                id: "s1".to_string(),
+                key_combo: "C".to_string(),
+                description: "Compose".to_string(),
+                order: 0,
+            },
+            Shortcut {
+                id: "s2".to_string(),
+                key_combo: "Shift+I".to_string(),
+                description: "Mark as read".to_string(),
+                order: 1,
+            },
+        ];
+        storage::save_lists_for_application(WEB_APPS_APP_ID, &vec![gmail]).unwrap();
+        let path = dir.join("sites.csv");
+        export_csv(&path, &["gmail".to_string()]).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        assert!(
+            csv.contains(&format!("C,Compose,0,Gmail,{WEB_APPS_PROCESS_NAME}")),
+            "{csv}"
+        );
+
+        use_scratch_data_dir("csv-site-lists-target");
+        let report = import_csv(&path, None).unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.lists[0].application_id, WEB_APPS_APP_ID);
+
+        let lists = storage::load_lists_for_application(WEB_APPS_APP_ID).unwrap();
+        assert_eq!(lists.len(), 1);
+        assert_eq!(lists[0].name, "Gmail");
+        assert_eq!(shortcuts(&lists[0]), vec![("C", 0), ("Shift+I", 1)]);
+    }
+}
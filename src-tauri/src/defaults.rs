@@ -1,8 +1,15 @@
-use crate::storage::{Application, Shortcut, ShortcutList};
+use crate::key_combo::{self, Platform};
+use crate::storage::{Application, MatchField, MatchKind, MatchRule, Shortcut, ShortcutList};
+use std::collections::HashSet;
 use uuid::Uuid;
 
-const VSCODE_APP_ID: &str = "9e121cd1-9808-47b9-99c9-072699fbeff3";
+pub const VSCODE_APP_ID: &str = "9e121cd1-9808-47b9-99c9-072699fbeff3";
 const CHROME_APP_ID: &str = "658cf89a-1955-43b9-95b1-6bbc1673aac5";
+// Holds lists for websites; offered in every application marked is_browser
+pub const WEB_APPS_APP_ID: &str = "app-web";
+// Web apps isn't a program, but CSV and bundle files route lists by
+// process_name, so it gets one that no real process can have
+pub const WEB_APPS_PROCESS_NAME: &str = "<web-apps>";
 
 // Small helpers to keep default data definitions concise
 fn make_shortcut(order: i32, key_combo: &str, description: &str) -> Shortcut {
@@ -22,9 +29,68 @@ fn make_list(app_id: &str, name: &str, shortcuts: Vec<Shortcut>) -> ShortcutList
 	        shortcuts,
 	        created_at: "2025-01-01T00:00:00Z".to_string(),
 	        updated_at: "2025-01-01T00:00:00Z".to_string(),
+	        match_rules: Vec::new(),
 	    }
 }
 
+// A Web apps list picked while the browser's window title matches `title_glob`
+fn make_site_list(name: &str, title_glob: &str, shortcuts: Vec<Shortcut>) -> ShortcutList {
+	    let mut list = make_list(WEB_APPS_APP_ID, name, shortcuts);
+	    list.match_rules.push(MatchRule {
+	        field: MatchField::WindowTitle,
+	        kind: MatchKind::Glob,
+	        pattern: title_glob.to_string(),
+	        priority: 0,
+	    });
+	    list
+}
+
+// macOS apps use Cmd where the Windows/Linux templates use Ctrl; the
+// CmdOrCtrl token lets one template render correctly on either.
+// Ctrl+Tab stays Ctrl on macOS too (Cmd+Tab is the app switcher).
+fn with_primary_modifier(shortcuts: Vec<Shortcut>) -> Vec<Shortcut> {
+	    shortcuts
+	        .into_iter()
+	        .map(|mut s| {
+	            if !s.key_combo.ends_with("+Tab") {
+	                s.key_combo = s.key_combo.replace("Ctrl+", "CmdOrCtrl+");
+	            }
+	            s
+	        })
+	        .collect()
+}
+
+// The Ctrl form a CmdOrCtrl template shortcut was seeded with before
+// with_primary_modifier existed
+fn legacy_key_combo(key_combo: &str) -> String {
+	    key_combo.replace("CmdOrCtrl+", "Ctrl+")
+}
+
+// Combos earlier seeds got wrong on macOS, as (application id, seeded combo,
+// description). merge_shortcuts corrects entries that still hold one.
+const SUPERSEDED_SEEDS: &[(&str, &str, &str)] = &[
+	    ("app-finder", "Alt+Left", "Back"),
+	    ("app-finder", "Alt+Right", "Forward"),
+	    ("app-finder", "Alt+Up", "Go up one folder"),
+	    ("app-iterm2", "CmdOrCtrl+Shift+T", "Open new tab"),
+	    ("app-iterm2", "CmdOrCtrl+Shift+N", "Open new window"),
+	    ("app-iterm2", "CmdOrCtrl+Shift+W", "Close tab"),
+	    ("app-mac-terminal", "CmdOrCtrl+Shift+T", "Open new tab"),
+	    ("app-mac-terminal", "CmdOrCtrl+Shift+N", "Open new window"),
+	    ("app-mac-terminal", "CmdOrCtrl+Shift+W", "Close tab"),
+];
+
+// Whether `shortcut` is still exactly a superseded seed in `application_id`,
+// in either its CmdOrCtrl or pre-CmdOrCtrl Ctrl form
+fn is_superseded_seed(application_id: &str, shortcut: &Shortcut) -> bool {
+	    SUPERSEDED_SEEDS.iter().any(|(app_id, key_combo, description)| {
+	        *app_id == application_id
+	            && *description == shortcut.description
+	            && (key_combo::same_combo(key_combo, &shortcut.key_combo)
+	                || key_combo::same_combo(&legacy_key_combo(key_combo), &shortcut.key_combo))
+	    })
+}
+
 // Category templates
 fn code_editor_general() -> Vec<Shortcut> {
 	    vec![
@@ -77,6 +143,62 @@ fn browser_navigation() -> Vec<Shortcut> {
 	    ]
 }
 
+// Websites (single-key shortcuts have to be enabled in Gmail's settings)
+fn gmail_general() -> Vec<Shortcut> {
+	    vec![
+	        make_shortcut(0, "C", "Compose"),
+	        make_shortcut(1, "/", "Search mail"),
+	        make_shortcut(2, "R", "Reply"),
+	        make_shortcut(3, "A", "Reply all"),
+	        make_shortcut(4, "F", "Forward"),
+	        make_shortcut(5, "E", "Archive"),
+	        make_shortcut(6, "J", "Older conversation"),
+	        make_shortcut(7, "K", "Newer conversation"),
+	        make_shortcut(8, "Shift+I", "Mark as read"),
+	        make_shortcut(9, "G, I", "Go to Inbox"),
+	    ]
+}
+
+fn github_general() -> Vec<Shortcut> {
+	    vec![
+	        make_shortcut(0, "Ctrl+K", "Open command palette"),
+	        make_shortcut(1, "S", "Focus search bar"),
+	        make_shortcut(2, "T", "Open file finder"),
+	        make_shortcut(3, ".", "Open in github.dev editor"),
+	        make_shortcut(4, "Y", "Expand URL to permalink"),
+	        make_shortcut(5, "G, C", "Go to Code tab"),
+	        make_shortcut(6, "G, I", "Go to Issues tab"),
+	        make_shortcut(7, "G, P", "Go to Pull requests tab"),
+	        make_shortcut(8, "Ctrl+Enter", "Submit comment"),
+	    ]
+}
+
+fn jira_general() -> Vec<Shortcut> {
+	    vec![
+	        make_shortcut(0, "C", "Create issue"),
+	        make_shortcut(1, "/", "Quick search"),
+	        make_shortcut(2, "E", "Edit issue"),
+	        make_shortcut(3, "M", "Comment on issue"),
+	        make_shortcut(4, "I", "Assign to me"),
+	        make_shortcut(5, "J", "Next issue"),
+	        make_shortcut(6, "K", "Previous issue"),
+	        make_shortcut(7, "G, D", "Go to dashboard"),
+	    ]
+}
+
+fn figma_web_general() -> Vec<Shortcut> {
+	    vec![
+	        make_shortcut(0, "Ctrl+/", "Quick actions"),
+	        make_shortcut(1, "V", "Move tool"),
+	        make_shortcut(2, "F", "Frame tool"),
+	        make_shortcut(3, "R", "Rectangle tool"),
+	        make_shortcut(4, "T", "Text tool"),
+	        make_shortcut(5, "Ctrl+D", "Duplicate selection"),
+	        make_shortcut(6, "Ctrl+G", "Group selection"),
+	        make_shortcut(7, "Shift+1", "Zoom to fit"),
+	    ]
+}
+
 fn terminal_general() -> Vec<Shortcut> {
 	    vec![
 	        make_shortcut(0, "Ctrl+Shift+T", "Open new tab"),
@@ -87,6 +209,18 @@ fn terminal_general() -> Vec<Shortcut> {
 	    ]
 }
 
+// macOS terminals open tabs and windows with Cmd+T / Cmd+N, not the Linux
+// Ctrl+Shift forms
+fn mac_terminal_general() -> Vec<Shortcut> {
+	    with_primary_modifier(vec![
+	        make_shortcut(0, "Ctrl+T", "Open new tab"),
+	        make_shortcut(1, "Ctrl+N", "Open new window"),
+	        make_shortcut(2, "Ctrl+W", "Close tab"),
+	        make_shortcut(3, "Ctrl++", "Zoom in"),
+	        make_shortcut(4, "Ctrl+-", "Zoom out"),
+	    ])
+}
+
 fn terminal_navigation() -> Vec<Shortcut> {
 	    vec![
 	        make_shortcut(0, "Ctrl+L", "Clear screen"),
@@ -117,6 +251,17 @@ fn file_manager_navigation() -> Vec<Shortcut> {
 	    ]
 }
 
+// Finder goes back, forward and up with Cmd+[ / Cmd+] / Cmd+Up
+fn finder_navigation() -> Vec<Shortcut> {
+	    with_primary_modifier(vec![
+	        make_shortcut(0, "Ctrl+[", "Back"),
+	        make_shortcut(1, "Ctrl+]", "Forward"),
+	        make_shortcut(2, "Ctrl+Up", "Go up one folder"),
+	        make_shortcut(3, "Ctrl+L", "Focus address bar"),
+	        make_shortcut(4, "Ctrl+Shift+1", "Toggle details view"),
+	    ])
+}
+
 fn word_processor_general() -> Vec<Shortcut> {
 	    vec![
 	        make_shortcut(0, "Ctrl+N", "Create new document"),
@@ -406,6 +551,8 @@ pub fn create_default_data() -> (Vec<Application>, Vec<ShortcutList>) {
 	        detection_name: "Visual Studio Code".to_string(),
 	        icon: None,
 	        last_used_list_id: None,
+	        match_rules: Vec::new(),
+	        is_browser: false,
 	    });
 	    lists.push(make_list(&vscode_id, "General", code_editor_general()));
 	    lists.push(make_list(&vscode_id, "Navigation", code_editor_navigation()));
@@ -419,6 +566,8 @@ pub fn create_default_data() -> (Vec<Application>, Vec<ShortcutList>) {
 	        detection_name: "Google Chrome".to_string(),
 	        icon: None,
 	        last_used_list_id: None,
+	        match_rules: Vec::new(),
+	        is_browser: true,
 	    });
 	    lists.push(make_list(&chrome_id, "General", browser_general()));
 	    lists.push(make_list(&chrome_id, "Navigation", browser_navigation()));
@@ -433,8 +582,8 @@ pub fn create_default_data() -> (Vec<Application>, Vec<ShortcutList>) {
 	    lists.push(make_list(&firefox_id, "Navigation", browser_navigation()));
 
 	    let safari_id = "app-safari".to_string();
-	    lists.push(make_list(&safari_id, "General", browser_general()));
-	    lists.push(make_list(&safari_id, "Navigation", browser_navigation()));
+	    lists.push(make_list(&safari_id, "General", with_primary_modifier(browser_general())));
+	    lists.push(make_list(&safari_id, "Navigation", with_primary_modifier(browser_navigation())));
 
 	    let brave_id = "app-brave".to_string();
 	    lists.push(make_list(&brave_id, "General", browser_general()));
@@ -444,6 +593,12 @@ pub fn create_default_data() -> (Vec<Application>, Vec<ShortcutList>) {
 	    lists.push(make_list(&opera_id, "General", browser_general()));
 	    lists.push(make_list(&opera_id, "Navigation", browser_navigation()));
 
+	    // Websites, picked by window title in any browser
+	    lists.push(make_site_list("Gmail", "* - Gmail*", gmail_general()));
+	    lists.push(make_site_list("GitHub", "*GitHub*", github_general()));
+	    lists.push(make_site_list("Jira", "* - Jira*", jira_general()));
+	    lists.push(make_site_list("Figma", "*Figma*", figma_web_general()));
+
 	    // Code editors and IDEs
 	    let vs_id = "app-visual-studio".to_string();
 	    // Use richer Visual Studio-specific lists; keep Debugging as list #3
@@ -475,8 +630,8 @@ pub fn create_default_data() -> (Vec<Application>, Vec<ShortcutList>) {
 	    lists.push(make_list(&android_studio_id, "Navigation", code_editor_navigation()));
 
 	    let xcode_id = "app-xcode".to_string();
-	    lists.push(make_list(&xcode_id, "General", code_editor_general()));
-	    lists.push(make_list(&xcode_id, "Navigation", code_editor_navigation()));
+	    lists.push(make_list(&xcode_id, "General", with_primary_modifier(code_editor_general())));
+	    lists.push(make_list(&xcode_id, "Navigation", with_primary_modifier(code_editor_navigation())));
 
 	    let sublime_id = "app-sublime-text".to_string();
 	    lists.push(make_list(&sublime_id, "General", code_editor_general()));
@@ -508,11 +663,11 @@ pub fn create_default_data() -> (Vec<Application>, Vec<ShortcutList>) {
 	    lists.push(make_list(&ps_id, "Navigation", terminal_navigation()));
 
 	    let iterm_id = "app-iterm2".to_string();
-	    lists.push(make_list(&iterm_id, "General", terminal_general()));
+	    lists.push(make_list(&iterm_id, "General", mac_terminal_general()));
 	    lists.push(make_list(&iterm_id, "Navigation", terminal_navigation()));
 
 	    let mac_term_id = "app-mac-terminal".to_string();
-	    lists.push(make_list(&mac_term_id, "General", terminal_general()));
+	    lists.push(make_list(&mac_term_id, "General", mac_terminal_general()));
 	    lists.push(make_list(&mac_term_id, "Navigation", terminal_navigation()));
 
 	    // File managers
@@ -521,8 +676,8 @@ pub fn create_default_data() -> (Vec<Application>, Vec<ShortcutList>) {
 	    lists.push(make_list(&explorer_id, "Navigation", file_manager_navigation()));
 
 	    let finder_id = "app-finder".to_string();
-	    lists.push(make_list(&finder_id, "General", file_manager_general()));
-	    lists.push(make_list(&finder_id, "Navigation", file_manager_navigation()));
+	    lists.push(make_list(&finder_id, "General", with_primary_modifier(file_manager_general())));
+	    lists.push(make_list(&finder_id, "Navigation", finder_navigation()));
 
 	    let nautilus_id = "app-nautilus".to_string();
 	    lists.push(make_list(&nautilus_id, "General", file_manager_general()));
@@ -643,3 +798,202 @@ pub fn create_default_data() -> (Vec<Application>, Vec<ShortcutList>) {
 
 	    (applications, lists)
 }
+
+// Append the template shortcuts `list` doesn't have yet after its last one,
+// without touching key combos the user already has. Returns whether the list
+// changed.
+//
+// macOS lists seeded before the CmdOrCtrl templates hold the Ctrl form, which
+// no longer matches the resolved Cmd form. Entries still holding the seeded
+// Ctrl combo and description are upgraded to CmdOrCtrl in place; edited ones
+// are kept as they are. Either way the template entry isn't added again.
+// Unedited entries listed in SUPERSEDED_SEEDS are corrected the same way.
+pub fn merge_shortcuts(list: &mut ShortcutList, template: Vec<Shortcut>, platform: Platform) -> bool {
+	    let mut changed = false;
+
+	    for shortcut in list.shortcuts.iter_mut() {
+	        let superseded = is_superseded_seed(&list.application_id, shortcut);
+	        let seeded = template.iter().find(|t| {
+	            t.description == shortcut.description
+	                && (superseded
+	                    || (t.key_combo.contains("CmdOrCtrl+")
+	                        && key_combo::same_combo(&legacy_key_combo(&t.key_combo), &shortcut.key_combo)))
+	        });
+	        if let Some(seeded) = seeded {
+	            shortcut.key_combo = seeded.key_combo.clone();
+	            changed = true;
+	        }
+	    }
+
+	    // Normalized so "ctrl+shift+p" and "Shift+Ctrl+P" count as the same and
+	    // CmdOrCtrl matches whatever this platform recorded
+	    let mut existing_keys: HashSet<String> = list
+	        .shortcuts
+	        .iter()
+	        .map(|s| key_combo::normalize_for(&s.key_combo, platform))
+	        .collect();
+	    let mut max_order = list.shortcuts.iter().map(|s| s.order).max().unwrap_or(-1);
+
+	    for mut shortcut in template {
+	        let legacy = key_combo::normalize_for(&legacy_key_combo(&shortcut.key_combo), platform);
+	        let resolved = key_combo::normalize_for(&shortcut.key_combo, platform);
+	        if existing_keys.contains(&legacy) || !existing_keys.insert(resolved) {
+	            continue;
+	        }
+
+	        max_order += 1;
+	        shortcut.id = Uuid::new_v4().to_string();
+	        shortcut.order = max_order;
+	        list.shortcuts.push(shortcut);
+	        changed = true;
+	    }
+
+	    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> Vec<Shortcut> {
+        with_primary_modifier(vec![
+            make_shortcut(0, "Ctrl+S", "Save file"),
+            make_shortcut(1, "Ctrl+P", "Quick Open file"),
+            make_shortcut(2, "Ctrl+Tab", "Next editor tab"),
+            make_shortcut(3, "Ctrl+W", "Close tab"),
+        ])
+    }
+
+    fn combos(list: &ShortcutList) -> Vec<(&str, &str, i32)> {
+        list.shortcuts
+            .iter()
+            .map(|s| (s.key_combo.as_str(), s.description.as_str(), s.order))
+            .collect()
+    }
+
+    #[test]
+    fn legacy_ctrl_entries_are_upgraded_not_duplicated_on_macos() {
+        let mut list = make_list(
+            "app-safari",
+            "General",
+            vec![
+                make_shortcut(0, "Ctrl+S", "Save file"),
+                make_shortcut(1, "Ctrl+P", "My quick open"),
+                make_shortcut(2, "Ctrl+Tab", "Next editor tab"),
+            ],
+        );
+
+        assert!(merge_shortcuts(&mut list, template(), Platform::Macos));
+        assert_eq!(
+            combos(&list),
+            vec![
+                ("CmdOrCtrl+S", "Save file", 0),
+                ("Ctrl+P", "My quick open", 1),
+                ("Ctrl+Tab", "Next editor tab", 2),
+                ("CmdOrCtrl+W", "Close tab", 3),
+            ]
+        );
+
+        assert!(!merge_shortcuts(&mut list, template(), Platform::Macos));
+        assert_eq!(list.shortcuts.len(), 4);
+    }
+
+    #[test]
+    fn existing_combos_are_kept_on_other_platforms() {
+        let mut list = make_list(
+            "app-vscode",
+            "General",
+            vec![
+                make_shortcut(3, "ctrl+s", "Save everything"),
+                make_shortcut(7, "CmdOrCtrl+W", "Close tab"),
+            ],
+        );
+
+        assert!(merge_shortcuts(&mut list, template(), Platform::Linux));
+        assert_eq!(
+            combos(&list),
+            vec![
+                ("ctrl+s", "Save everything", 3),
+                ("CmdOrCtrl+W", "Close tab", 7),
+                ("CmdOrCtrl+P", "Quick Open file", 8),
+                ("Ctrl+Tab", "Next editor tab", 9),
+            ]
+        );
+        assert!(!merge_shortcuts(&mut list, template(), Platform::Windows));
+    }
+
+    #[test]
+    fn superseded_mac_seeds_are_corrected_in_place() {
+        let mut finder = make_list(
+            "app-finder",
+            "Navigation",
+            vec![
+                make_shortcut(0, "Alt+Left", "Back"),
+                make_shortcut(1, "Alt+Right", "Go forward"),
+                make_shortcut(2, "Alt+Up", "Go up one folder"),
+            ],
+        );
+
+        assert!(merge_shortcuts(
+            &mut finder,
+            finder_navigation(),
+            Platform::Macos
+        ));
+        assert_eq!(
+            combos(&finder),
+            vec![
+                ("CmdOrCtrl+[", "Back", 0),
+                ("Alt+Right", "Go forward", 1),
+                ("CmdOrCtrl+Up", "Go up one folder", 2),
+                ("CmdOrCtrl+]", "Forward", 3),
+                ("CmdOrCtrl+L", "Focus address bar", 4),
+                ("CmdOrCtrl+Shift+1", "Toggle details view", 5),
+            ]
+        );
+
+        let mut iterm = make_list(
+            "app-iterm2",
+            "General",
+            vec![
+                make_shortcut(0, "Ctrl+Shift+T", "Open new tab"),
+                make_shortcut(1, "CmdOrCtrl+Shift+N", "Open new window"),
+            ],
+        );
+
+        assert!(merge_shortcuts(
+            &mut iterm,
+            mac_terminal_general(),
+            Platform::Macos
+        ));
+        assert_eq!(
+            combos(&iterm)[..3],
+            [
+                ("CmdOrCtrl+T", "Open new tab", 0),
+                ("CmdOrCtrl+N", "Open new window", 1),
+                ("CmdOrCtrl+W", "Close tab", 2),
+            ]
+        );
+        assert!(!merge_shortcuts(
+            &mut iterm,
+            mac_terminal_general(),
+            Platform::Macos
+        ));
+    }
+
+    #[test]
+    fn other_file_managers_keep_alt_arrows() {
+        let mut nautilus = make_list(
+            "app-nautilus",
+            "Navigation",
+            vec![make_shortcut(0, "Alt+Left", "Back")],
+        );
+
+        assert!(merge_shortcuts(
+            &mut nautilus,
+            file_manager_navigation(),
+            Platform::Linux
+        ));
+        assert_eq!(combos(&nautilus)[0], ("Alt+Left", "Back", 0));
+        assert_eq!(nautilus.shortcuts.len(), 5);
+    }
+}
@@ -0,0 +1,284 @@
+// Optional background thread that follows window focus so a pinned popup can
+// switch lists as the user moves between applications.
+//
+// None of the detection backends report focus changes by themselves, so the
+// watcher polls window_detection every POLL_INTERVAL. A new window has to stay
+// focused for DEBOUNCE before it counts, which skips windows that are only
+// passed through with Alt+Tab. Will-Shortcut's own windows are ignored so
+// clicking into the popup doesn't switch it to itself.
+//
+// An "active-app-changed" event is emitted whenever the matched application or
+// site list changes. Applications and lists are read without migrating or
+// quarantining anything, so the watcher never writes to the data directory.
+
+use crate::window_detection::{self, ActiveWindowInfo};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+const DEBOUNCE: Duration = Duration::from_millis(400);
+
+// Payload of "active-app-changed"
+#[derive(Debug, Clone, Serialize)]
+pub struct ActiveAppChanged {
+    // Same identifier as "active-app-detected"
+    pub identifier: String,
+    // Same list id as "site-list-detected"
+    pub site_list_id: Option<String>,
+    pub window: ActiveWindowInfo,
+}
+
+// Stop flag of the running watcher thread, if there is one
+#[derive(Default)]
+pub struct FocusWatcher {
+    running: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+// Start or stop the watcher to match the watch_focus setting
+pub fn set_enabled(app: &AppHandle, enabled: bool) {
+    let state = app.state::<FocusWatcher>();
+    let mut running = state.running.lock().unwrap_or_else(|e| e.into_inner());
+    match (enabled, running.as_ref()) {
+        (true, None) => {
+            let stop = Arc::new(AtomicBool::new(false));
+            let handle = app.clone();
+            let thread_stop = stop.clone();
+            let spawned = thread::Builder::new()
+                .name("focus-watcher".to_string())
+                .spawn(move || watch(handle, thread_stop));
+            match spawned {
+                Ok(_) => *running = Some(stop),
+                Err(e) => eprintln!("Failed to start focus watcher: {e}"),
+            }
+        }
+        (false, Some(stop)) => {
+            stop.store(true, Ordering::Relaxed);
+            *running = None;
+        }
+        _ => {}
+    }
+}
+
+fn watch(app: AppHandle, stop: Arc<AtomicBool>) {
+    let mut debounce = Debounce::default();
+    // What the last settled window resolved to
+    let mut last_emitted: Option<(String, Option<String>)> = None;
+
+    while !stop.load(Ordering::Relaxed) {
+        thread::sleep(POLL_INTERVAL);
+
+        let Ok(window) = window_detection::active_window() else {
+            continue;
+        };
+        if is_own_window(&window, std::process::id()) {
+            continue;
+        }
+        let Some(window) = debounce.poll(window, Instant::now()) else {
+            continue;
+        };
+
+        // Title changes (a new tab, an unsaved-changes marker) only matter
+        // when they change which application or site list matches
+        let Ok((apps, lists)) = crate::storage::read_applications_and_lists() else {
+            continue;
+        };
+        let context = crate::popup::context_for(&apps, &lists, window);
+        if last_emitted.as_ref() == Some(&context) {
+            continue;
+        }
+        let (identifier, site_list_id) = context.clone();
+        last_emitted = Some(context);
+        let _ = app.emit(
+            "active-app-changed",
+            ActiveAppChanged {
+                identifier,
+                site_list_id,
+                window: window.clone(),
+            },
+        );
+    }
+}
+
+// Follows the focused window across polls and reports it once it has stayed
+// focused for DEBOUNCE
+#[derive(Default)]
+struct Debounce {
+    // The window seen on the last poll and when it was first seen
+    current: Option<(ActiveWindowInfo, Instant)>,
+    // Window key (app name + title) that was last reported
+    settled_key: Option<(String, String)>,
+}
+
+impl Debounce {
+    // The focused window at `now`, if it just settled. A window is reported
+    // once; it's reported again only after another one has settled.
+    fn poll(&mut self, window: ActiveWindowInfo, now: Instant) -> Option<&ActiveWindowInfo> {
+        if self
+            .current
+            .as_ref()
+            .is_none_or(|(seen, _)| !same_window(seen, &window))
+        {
+            self.current = Some((window, now));
+            return None;
+        }
+        let (window, since) = self.current.as_ref()?;
+        let key = (window.app_name.clone(), window.title.clone());
+        if now.duration_since(*since) < DEBOUNCE || self.settled_key.as_ref() == Some(&key) {
+            return None;
+        }
+        self.settled_key = Some(key);
+        Some(window)
+    }
+}
+
+fn same_window(a: &ActiveWindowInfo, b: &ActiveWindowInfo) -> bool {
+    a.app_name == b.app_name && a.title == b.title && a.pid == b.pid
+}
+
+// The popup and settings windows belong to this process (`own_pid`); backends
+// without a pid are matched on the app name instead
+fn is_own_window(window: &ActiveWindowInfo, own_pid: u32) -> bool {
+    if let Some(pid) = window.pid {
+        return pid == own_pid;
+    }
+    let name = window.app_name.to_ascii_lowercase();
+    name.starts_with("will-shortcut") || name == "com.andrew.will-shortcut"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(app_name: &str, title: &str, pid: Option<u32>) -> ActiveWindowInfo {
+        ActiveWindowInfo {
+            app_name: app_name.to_string(),
+            title: title.to_string(),
+            pid,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn windows_are_compared_by_app_title_and_pid() {
+        let editor = window("code", "main.rs", Some(10));
+        assert!(same_window(&editor, &editor.clone()));
+        assert!(!same_window(&editor, &window("code", "lib.rs", Some(10))));
+        assert!(!same_window(&editor, &window("code", "main.rs", Some(11))));
+        assert!(!same_window(&editor, &window("code", "main.rs", None)));
+        assert!(!same_window(
+            &editor,
+            &window("firefox", "main.rs", Some(10))
+        ));
+
+        // Bounds and paths don't make it a different window
+        let mut moved = editor.clone();
+        moved.process_path = Some("/usr/bin/code".to_string());
+        assert!(same_window(&editor, &moved));
+    }
+
+    #[test]
+    fn own_windows_are_recognised_by_pid_or_name() {
+        assert!(is_own_window(&window("whatever", "", Some(42)), 42));
+        assert!(!is_own_window(&window("will-shortcut", "", Some(7)), 42));
+        assert!(is_own_window(&window("Will-Shortcut", "", None), 42));
+        assert!(is_own_window(&window("will-shortcut.exe", "", None), 42));
+        assert!(is_own_window(
+            &window("com.andrew.will-shortcut", "", None),
+            42
+        ));
+        assert!(!is_own_window(&window("firefox", "", None), 42));
+    }
+
+    // Poll `windows` POLL_INTERVAL apart, returning the titles that settled
+    fn settled(windows: &[ActiveWindowInfo]) -> Vec<Option<String>> {
+        let mut debounce = Debounce::default();
+        let start = Instant::now();
+        windows
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                debounce
+                    .poll(w.clone(), start + POLL_INTERVAL * i as u32)
+                    .map(|w| w.title.clone())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn a_window_settles_once_after_the_debounce() {
+        let editor = window("code", "main.rs", Some(10));
+        assert_eq!(
+            settled(&[
+                editor.clone(),
+                editor.clone(),
+                editor.clone(),
+                editor.clone()
+            ]),
+            // 0ms, 250ms: too soon; 500ms: settled; 750ms: already reported
+            vec![None, None, Some("main.rs".to_string()), None]
+        );
+    }
+
+    #[test]
+    fn windows_passed_through_do_not_settle() {
+        let editor = window("code", "main.rs", Some(10));
+        let browser = window("firefox", "Docs", Some(20));
+        let terminal = window("kitty", "~", Some(30));
+        assert_eq!(
+            settled(&[
+                editor.clone(),
+                browser.clone(),
+                terminal.clone(),
+                editor.clone(),
+                editor.clone(),
+                editor.clone(),
+            ]),
+            vec![None, None, None, None, None, Some("main.rs".to_string())]
+        );
+    }
+
+    #[test]
+    fn returning_to_a_window_settles_it_again() {
+        let editor = window("code", "main.rs", Some(10));
+        let browser = window("firefox", "Docs", Some(20));
+        let polls = [
+            editor.clone(),
+            editor.clone(),
+            editor.clone(),
+            browser.clone(),
+            browser.clone(),
+            browser.clone(),
+            editor.clone(),
+            editor.clone(),
+            editor.clone(),
+        ];
+        let titles: Vec<Option<String>> = settled(&polls)
+            .into_iter()
+            .filter(Option::is_some)
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                Some("main.rs".to_string()),
+                Some("Docs".to_string()),
+                Some("main.rs".to_string())
+            ]
+        );
+
+        // A quick detour that never settles doesn't report the same window twice
+        let polls = [
+            editor.clone(),
+            editor.clone(),
+            editor.clone(),
+            browser.clone(),
+            editor.clone(),
+            editor.clone(),
+            editor.clone(),
+        ];
+        assert_eq!(settled(&polls).iter().filter(|t| t.is_some()).count(), 1);
+    }
+}
@@ -0,0 +1,657 @@
+// Global hotkeys: parsing, registration and the actions they run.
+//
+// Settings store hotkeys in the app's key_combo notation ("Ctrl+Shift+Alt+K",
+// "Win+↑"), which the global shortcut plugin doesn't fully understand, so
+// they are converted to its accelerator syntax before registering.
+//
+// Every binding shares one handler that looks its action up in
+// RegisteredHotkeys, so changing the action of an already registered hotkey
+// doesn't need a new OS registration.
+//
+// Bindings in peek mode show the popup on press and hide it again on release.
+
+use crate::key_combo::{KeyCombo, Modifier};
+use crate::popup;
+use crate::storage::{GlobalAction, GlobalHotkey, Settings, StorageError};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+// The shortcuts currently registered with the OS and what each one does
+#[derive(Default)]
+pub struct RegisteredHotkeys {
+    bindings: Mutex<HashMap<Shortcut, GlobalHotkey>>,
+    // The peek hotkey currently held down, if it opened the popup
+    peeking: Mutex<Option<Shortcut>>,
+}
+
+impl RegisteredHotkeys {
+    fn snapshot(&self) -> HashMap<Shortcut, GlobalHotkey> {
+        self.bindings
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn replace(&self, registered: HashMap<Shortcut, GlobalHotkey>) {
+        *self.bindings.lock().unwrap_or_else(|e| e.into_inner()) = registered;
+    }
+
+    fn binding_for(&self, shortcut: &Shortcut) -> Option<GlobalHotkey> {
+        self.bindings
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(shortcut)
+            .cloned()
+    }
+
+    // Record that `shortcut` opened the popup for a peek; false if a peek is
+    // already running (key repeat sends more presses)
+    fn start_peek(&self, shortcut: Shortcut) -> bool {
+        let mut peeking = self.peeking.lock().unwrap_or_else(|e| e.into_inner());
+        if peeking.is_some() {
+            return false;
+        }
+        *peeking = Some(shortcut);
+        true
+    }
+
+    fn end_peek(&self, shortcut: &Shortcut) -> bool {
+        let mut peeking = self.peeking.lock().unwrap_or_else(|e| e.into_inner());
+        if peeking.as_ref() != Some(shortcut) {
+            return false;
+        }
+        *peeking = None;
+        true
+    }
+
+    // What a key event of a peek binding should do. A popup that's already
+    // open stays open after the peek.
+    fn peek_step(
+        &self,
+        shortcut: &Shortcut,
+        state: ShortcutState,
+        popup_visible: bool,
+    ) -> PeekStep {
+        match state {
+            ShortcutState::Pressed if !popup_visible && self.start_peek(*shortcut) => {
+                PeekStep::Open
+            }
+            ShortcutState::Released if self.end_peek(shortcut) => PeekStep::Hide,
+            _ => PeekStep::Nothing,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PeekStep {
+    // Run the binding's action to show the popup
+    Open,
+    Hide,
+    Nothing,
+}
+
+#[derive(Debug, thiserror::Error, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HotkeyError {
+    // The string isn't a usable hotkey (typo, multi-step chord, unknown key)
+    #[error("\"{hotkey}\" is not a valid hotkey: {message}")]
+    InvalidHotkey { hotkey: String, message: String },
+    // The OS refused the registration, almost always because another
+    // application already owns the combination
+    #[error("\"{hotkey}\" is already in use by another application")]
+    HotkeyTaken { hotkey: String, message: String },
+    #[error("Could not register \"{hotkey}\": {message}")]
+    HotkeyRegisterFailed { hotkey: String, message: String },
+    #[error("{error}")]
+    Storage { error: StorageError },
+}
+
+impl From<StorageError> for HotkeyError {
+    fn from(error: StorageError) -> Self {
+        HotkeyError::Storage { error }
+    }
+}
+
+impl HotkeyError {
+    fn invalid(hotkey: &str, message: impl Into<String>) -> Self {
+        HotkeyError::InvalidHotkey {
+            hotkey: hotkey.to_string(),
+            message: message.into(),
+        }
+    }
+
+    // Classify an error from the plugin's register call. The plugin only
+    // passes strings through, so this goes by global-hotkey's messages.
+    pub fn from_register(hotkey: &str, error: tauri_plugin_global_shortcut::Error) -> Self {
+        let message = error.to_string();
+        let lower = message.to_lowercase();
+        if lower.contains("already registered") || lower.contains("unable to register") {
+            HotkeyError::HotkeyTaken {
+                hotkey: hotkey.to_string(),
+                message,
+            }
+        } else {
+            HotkeyError::HotkeyRegisterFailed {
+                hotkey: hotkey.to_string(),
+                message,
+            }
+        }
+    }
+}
+
+// Convert a key_combo-style hotkey into the plugin's accelerator syntax
+pub fn to_accelerator(hotkey: &str) -> Result<String, HotkeyError> {
+    let combo = KeyCombo::parse(hotkey).map_err(|e| HotkeyError::invalid(hotkey, e.to_string()))?;
+    let [chord] = combo.steps.as_slice() else {
+        return Err(HotkeyError::invalid(
+            hotkey,
+            "global hotkeys must be a single key combination",
+        ));
+    };
+    let function_key = chord
+        .key
+        .strip_prefix('F')
+        .is_some_and(|n| n.parse::<u8>().is_ok());
+    if chord.modifiers.is_empty() && !function_key {
+        return Err(HotkeyError::invalid(
+            hotkey,
+            "add at least one modifier so normal typing isn't captured",
+        ));
+    }
+
+    let mut parts: Vec<&str> = chord
+        .modifiers
+        .iter()
+        .map(|m| match m {
+            Modifier::Primary => "CmdOrCtrl",
+            Modifier::Ctrl => "Ctrl",
+            Modifier::Shift => "Shift",
+            Modifier::Alt => "Alt",
+            Modifier::Win | Modifier::Cmd => "Super",
+        })
+        .collect();
+    let key = match chord.key.as_str() {
+        "↑" => "ArrowUp",
+        "↓" => "ArrowDown",
+        "←" => "ArrowLeft",
+        "→" => "ArrowRight",
+        "Esc" => "Escape",
+        "+" => return Err(HotkeyError::invalid(hotkey, "use \"=\" for the plus key")),
+        other => other,
+    };
+    parts.push(key);
+    Ok(parts.join("+"))
+}
+
+// Parse a hotkey from settings into the plugin's Shortcut type
+pub fn parse_hotkey(hotkey: &str) -> Result<Shortcut, HotkeyError> {
+    let accelerator = to_accelerator(hotkey)?;
+    Shortcut::from_str(&accelerator).map_err(|e| HotkeyError::invalid(hotkey, e.to_string()))
+}
+
+// Parse every binding, rejecting hotkeys assigned to more than one action
+pub fn parse_bindings(
+    bindings: &[GlobalHotkey],
+) -> Result<Vec<(Shortcut, &GlobalHotkey)>, HotkeyError> {
+    let mut parsed: Vec<(Shortcut, &GlobalHotkey)> = Vec::with_capacity(bindings.len());
+    for binding in bindings {
+        if let GlobalAction::OpenList { list_id } = &binding.action {
+            if list_id.is_empty() {
+                return Err(HotkeyError::invalid(
+                    &binding.hotkey,
+                    "no list chosen to open",
+                ));
+            }
+        }
+        let shortcut = parse_hotkey(&binding.hotkey)?;
+        if parsed.iter().any(|(existing, _)| *existing == shortcut) {
+            return Err(HotkeyError::invalid(
+                &binding.hotkey,
+                "assigned to more than one action",
+            ));
+        }
+        parsed.push((shortcut, binding));
+    }
+    Ok(parsed)
+}
+
+// Register whatever bindings can be registered at startup, logging the rest
+pub fn register_startup(app: &AppHandle, bindings: &[GlobalHotkey]) {
+    let mut registered = HashMap::new();
+    for binding in bindings {
+        let result = parse_hotkey(&binding.hotkey)
+            .and_then(|shortcut| register(app, shortcut, &binding.hotkey).map(|()| shortcut));
+        match result {
+            Ok(shortcut) => {
+                registered.insert(shortcut, binding.clone());
+            }
+            Err(e) => eprintln!("Failed to register global hotkey on startup: {e}"),
+        }
+    }
+    app.state::<RegisteredHotkeys>().replace(registered);
+}
+
+// Swap the registered hotkeys for `bindings` (see `swap`)
+pub fn refresh(app: &AppHandle, bindings: &[GlobalHotkey]) -> Result<(), HotkeyError> {
+    let state = app.state::<RegisteredHotkeys>();
+    let next = swap(app, &state.snapshot(), bindings)?;
+    state.replace(next);
+    Ok(())
+}
+
+// Registering and releasing shortcuts with the OS
+trait Registrar {
+    fn register(&self, shortcut: Shortcut, hotkey: &str) -> Result<(), HotkeyError>;
+    fn unregister(&self, shortcut: Shortcut) -> Result<(), String>;
+}
+
+impl Registrar for AppHandle {
+    fn register(&self, shortcut: Shortcut, hotkey: &str) -> Result<(), HotkeyError> {
+        register(self, shortcut, hotkey)
+    }
+
+    fn unregister(&self, shortcut: Shortcut) -> Result<(), String> {
+        self.global_shortcut()
+            .unregister(shortcut)
+            .map_err(|e| e.to_string())
+    }
+}
+
+// Move from the `current` registrations to `bindings`, returning the new set.
+// New shortcuts are registered before old ones are released; if any of them
+// fails, the ones registered so far are released again and the previous set
+// keeps working.
+fn swap(
+    registrar: &impl Registrar,
+    current: &HashMap<Shortcut, GlobalHotkey>,
+    bindings: &[GlobalHotkey],
+) -> Result<HashMap<Shortcut, GlobalHotkey>, HotkeyError> {
+    let parsed = parse_bindings(bindings)?;
+
+    let mut added = Vec::new();
+    for (shortcut, binding) in &parsed {
+        if current.contains_key(shortcut) {
+            continue;
+        }
+        if let Err(e) = registrar.register(*shortcut, &binding.hotkey) {
+            for shortcut in added {
+                let _ = registrar.unregister(shortcut);
+            }
+            return Err(e);
+        }
+        added.push(*shortcut);
+    }
+
+    let next: HashMap<Shortcut, GlobalHotkey> = parsed
+        .into_iter()
+        .map(|(shortcut, binding)| (shortcut, binding.clone()))
+        .collect();
+    for shortcut in current.keys().filter(|s| !next.contains_key(s)) {
+        if let Err(e) = registrar.unregister(*shortcut) {
+            eprintln!("Failed to unregister global hotkey {shortcut}: {e}");
+        }
+    }
+    Ok(next)
+}
+
+// Register the hotkeys of `settings`, then persist them with `save`. Nothing
+// is saved if a hotkey can't be registered, and the previously registered
+// hotkeys are restored if saving fails, so disk and OS always agree.
+pub fn save_settings(
+    app: &AppHandle,
+    settings: &Settings,
+    save: impl FnOnce(&Settings) -> Result<(), StorageError>,
+) -> Result<(), HotkeyError> {
+    let previous: Vec<GlobalHotkey> = app
+        .state::<RegisteredHotkeys>()
+        .snapshot()
+        .into_values()
+        .collect();
+    refresh(app, &settings.global_bindings())?;
+    if let Err(e) = save(settings) {
+        if let Err(restore_error) = refresh(app, &previous) {
+            eprintln!("Failed to restore global hotkeys: {restore_error}");
+        }
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+fn register(app: &AppHandle, shortcut: Shortcut, hotkey: &str) -> Result<(), HotkeyError> {
+    app.global_shortcut()
+        .on_shortcut(shortcut, handle_shortcut)
+        .map_err(|e| HotkeyError::from_register(hotkey, e))
+}
+
+fn handle_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    let state = app.state::<RegisteredHotkeys>();
+    let Some(binding) = state.binding_for(shortcut) else {
+        return;
+    };
+
+    if binding.peek && binding.action.supports_peek() {
+        match state.peek_step(shortcut, event.state, popup::is_visible(app)) {
+            PeekStep::Open => run_action(app, &binding.action),
+            PeekStep::Hide => popup::hide(app),
+            PeekStep::Nothing => {}
+        }
+        return;
+    }
+
+    // Only trigger on key press, not release
+    if event.state == ShortcutState::Pressed {
+        run_action(app, &binding.action);
+    }
+}
+
+fn run_action(app: &AppHandle, action: &GlobalAction) {
+    match action {
+        GlobalAction::TogglePopup => popup::toggle(app),
+        GlobalAction::OpenList { list_id } => popup::show_with(app, "open-list", list_id.clone()),
+        GlobalAction::OpenSettings => popup::open_settings(app),
+        // A hidden popup just opens on the usual list; only cycle once it's up
+        GlobalAction::NextList => {
+            if popup::is_visible(app) {
+                popup::show_with(app, "next-list", ());
+            } else {
+                popup::show(app);
+            }
+        }
+        GlobalAction::OpenSearch => popup::show_with(app, "open-search", ()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashSet;
+
+    // Records registrations instead of making them; hotkeys in `taken` fail
+    // the way a combination owned by another application does
+    #[derive(Default)]
+    struct FakeRegistrar {
+        registered: RefCell<HashSet<Shortcut>>,
+        taken: Vec<&'static str>,
+    }
+
+    impl Registrar for FakeRegistrar {
+        fn register(&self, shortcut: Shortcut, hotkey: &str) -> Result<(), HotkeyError> {
+            if self.taken.contains(&hotkey) {
+                return Err(HotkeyError::HotkeyTaken {
+                    hotkey: hotkey.to_string(),
+                    message: "already registered".to_string(),
+                });
+            }
+            assert!(self.registered.borrow_mut().insert(shortcut));
+            Ok(())
+        }
+
+        fn unregister(&self, shortcut: Shortcut) -> Result<(), String> {
+            assert!(self.registered.borrow_mut().remove(&shortcut));
+            Ok(())
+        }
+    }
+
+    impl FakeRegistrar {
+        fn hotkeys(&self) -> Vec<Shortcut> {
+            let mut hotkeys: Vec<Shortcut> = self.registered.borrow().iter().copied().collect();
+            hotkeys.sort_by_key(|s| s.id());
+            hotkeys
+        }
+    }
+
+    fn binding(hotkey: &str, action: GlobalAction) -> GlobalHotkey {
+        GlobalHotkey {
+            hotkey: hotkey.to_string(),
+            action,
+            peek: false,
+        }
+    }
+
+    fn shortcuts(hotkeys: &[&str]) -> Vec<Shortcut> {
+        let mut shortcuts: Vec<Shortcut> =
+            hotkeys.iter().map(|h| parse_hotkey(h).unwrap()).collect();
+        shortcuts.sort_by_key(|s| s.id());
+        shortcuts
+    }
+
+    // Swap from nothing to `bindings`, as at startup
+    fn start(
+        registrar: &FakeRegistrar,
+        bindings: &[GlobalHotkey],
+    ) -> HashMap<Shortcut, GlobalHotkey> {
+        swap(registrar, &HashMap::new(), bindings).unwrap()
+    }
+
+    #[test]
+    fn swap_registers_new_and_releases_removed_hotkeys() {
+        let registrar = FakeRegistrar::default();
+        let current = start(
+            &registrar,
+            &[
+                binding("Ctrl+Alt+K", GlobalAction::TogglePopup),
+                binding("Ctrl+Alt+S", GlobalAction::OpenSettings),
+            ],
+        );
+
+        let next = swap(
+            &registrar,
+            &current,
+            &[
+                // Same hotkey, new action: no new registration needed
+                binding("Ctrl+Alt+K", GlobalAction::OpenSearch),
+                binding("Ctrl+Alt+N", GlobalAction::NextList),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            registrar.hotkeys(),
+            shortcuts(&["Ctrl+Alt+K", "Ctrl+Alt+N"])
+        );
+        assert_eq!(
+            next[&parse_hotkey("Ctrl+Alt+K").unwrap()].action,
+            GlobalAction::OpenSearch
+        );
+    }
+
+    #[test]
+    fn duplicate_hotkeys_are_rejected_before_registering() {
+        let registrar = FakeRegistrar::default();
+        let current = start(
+            &registrar,
+            &[binding("Ctrl+Alt+K", GlobalAction::TogglePopup)],
+        );
+
+        let result = swap(
+            &registrar,
+            &current,
+            &[
+                binding("Ctrl+Alt+N", GlobalAction::NextList),
+                binding("Ctrl+Alt+S", GlobalAction::OpenSettings),
+                // Same combination written differently
+                binding("Alt+Ctrl+s", GlobalAction::OpenSearch),
+            ],
+        );
+        assert!(matches!(result, Err(HotkeyError::InvalidHotkey { .. })));
+        assert_eq!(registrar.hotkeys(), shortcuts(&["Ctrl+Alt+K"]));
+    }
+
+    #[test]
+    fn an_empty_binding_list_releases_everything() {
+        let registrar = FakeRegistrar::default();
+        assert!(start(&registrar, &[]).is_empty());
+
+        let current = start(
+            &registrar,
+            &[binding("Ctrl+Alt+K", GlobalAction::TogglePopup)],
+        );
+        let next = swap(&registrar, &current, &[]).unwrap();
+        assert!(next.is_empty());
+        assert!(registrar.hotkeys().is_empty());
+    }
+
+    #[test]
+    fn failed_registration_keeps_the_previous_set() {
+        let mut registrar = FakeRegistrar::default();
+        let current = start(
+            &registrar,
+            &[
+                binding("Ctrl+Alt+K", GlobalAction::TogglePopup),
+                binding("Ctrl+Alt+S", GlobalAction::OpenSettings),
+            ],
+        );
+        registrar.taken.push("Ctrl+Alt+F");
+
+        let result = swap(
+            &registrar,
+            &current,
+            &[
+                binding("Ctrl+Alt+K", GlobalAction::TogglePopup),
+                binding("Ctrl+Alt+N", GlobalAction::NextList),
+                binding("Ctrl+Alt+F", GlobalAction::OpenSearch),
+            ],
+        );
+        assert!(matches!(result, Err(HotkeyError::HotkeyTaken { .. })));
+        // Ctrl+Alt+N was registered and released again; Ctrl+Alt+S survives
+        assert_eq!(
+            registrar.hotkeys(),
+            shortcuts(&["Ctrl+Alt+K", "Ctrl+Alt+S"])
+        );
+    }
+
+    #[test]
+    fn converts_hotkeys_to_accelerators() {
+        let cases = [
+            ("Ctrl+Shift+K", "Ctrl+Shift+K"),
+            ("CmdOrCtrl+Alt+P", "CmdOrCtrl+Alt+P"),
+            ("Win+↑", "Super+ArrowUp"),
+            ("Cmd+↓", "Super+ArrowDown"),
+            ("Ctrl+Alt+←", "Ctrl+Alt+ArrowLeft"),
+            ("Alt+→", "Alt+ArrowRight"),
+            ("Shift+Esc", "Shift+Escape"),
+            ("Ctrl+=", "Ctrl+="),
+            ("F12", "F12"),
+        ];
+        for (hotkey, expected) in cases {
+            assert_eq!(to_accelerator(hotkey).unwrap(), expected, "{hotkey}");
+        }
+    }
+
+    #[test]
+    fn rejects_hotkeys_that_cannot_be_registered() {
+        for hotkey in [
+            // The plus key: accelerators use "+" as the separator
+            "Ctrl++",
+            // No modifier would capture normal typing
+            "K",
+            "Esc",
+            "↑",
+            // Global shortcuts are a single combination
+            "Ctrl+K, Ctrl+S",
+            "Ctrl+K Ctrl+S",
+            // Not a key combination at all
+            "",
+            "Ctrl+",
+        ] {
+            assert!(
+                matches!(
+                    to_accelerator(hotkey),
+                    Err(HotkeyError::InvalidHotkey { .. })
+                ),
+                "{hotkey:?} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn repeated_peek_presses_open_the_popup_once() {
+        let state = RegisteredHotkeys::default();
+        let peek = parse_hotkey("Ctrl+Alt+K").unwrap();
+        assert_eq!(
+            state.peek_step(&peek, ShortcutState::Pressed, false),
+            PeekStep::Open
+        );
+        // Key repeat, before and after the popup shows up
+        assert_eq!(
+            state.peek_step(&peek, ShortcutState::Pressed, false),
+            PeekStep::Nothing
+        );
+        assert_eq!(
+            state.peek_step(&peek, ShortcutState::Pressed, true),
+            PeekStep::Nothing
+        );
+        assert_eq!(
+            state.peek_step(&peek, ShortcutState::Released, true),
+            PeekStep::Hide
+        );
+        // The next press peeks again
+        assert_eq!(
+            state.peek_step(&peek, ShortcutState::Pressed, false),
+            PeekStep::Open
+        );
+    }
+
+    #[test]
+    fn release_without_a_press_does_nothing() {
+        let state = RegisteredHotkeys::default();
+        let peek = parse_hotkey("Ctrl+Alt+K").unwrap();
+        let other = parse_hotkey("Ctrl+Alt+J").unwrap();
+        assert_eq!(
+            state.peek_step(&peek, ShortcutState::Released, true),
+            PeekStep::Nothing
+        );
+
+        // Only the hotkey that started the peek ends it
+        assert_eq!(
+            state.peek_step(&peek, ShortcutState::Pressed, false),
+            PeekStep::Open
+        );
+        assert_eq!(
+            state.peek_step(&other, ShortcutState::Released, true),
+            PeekStep::Nothing
+        );
+        assert_eq!(
+            state.peek_step(&other, ShortcutState::Pressed, true),
+            PeekStep::Nothing
+        );
+        assert_eq!(
+            state.peek_step(&peek, ShortcutState::Released, true),
+            PeekStep::Hide
+        );
+    }
+
+    #[test]
+    fn popup_opened_normally_stays_open_after_a_peek() {
+        let state = RegisteredHotkeys::default();
+        let peek = parse_hotkey("Ctrl+Alt+K").unwrap();
+        // The popup was toggled open by another hotkey or the tray
+        assert_eq!(
+            state.peek_step(&peek, ShortcutState::Pressed, true),
+            PeekStep::Nothing
+        );
+        assert_eq!(
+            state.peek_step(&peek, ShortcutState::Released, true),
+            PeekStep::Nothing
+        );
+        // Once it's closed again, peeking works as usual
+        assert_eq!(
+            state.peek_step(&peek, ShortcutState::Pressed, false),
+            PeekStep::Open
+        );
+        // Toggling the popup closed mid-peek doesn't leave the peek running;
+        // hiding an already hidden popup is harmless
+        assert_eq!(
+            state.peek_step(&peek, ShortcutState::Released, false),
+            PeekStep::Hide
+        );
+        assert_eq!(
+            state.peek_step(&peek, ShortcutState::Pressed, false),
+            PeekStep::Open
+        );
+    }
+}
@@ -0,0 +1,575 @@
+// Import Emacs key bindings from an init.el into a list for the Emacs
+// application.
+//
+// Recognises `global-set-key`, `local-set-key`, `define-key`, `keymap-set`
+// and `keymap-global-set` forms anywhere in the file (including inside
+// `with-eval-after-load` and hooks), plus `use-package` `:bind` lists. Keys may
+// be given as `(kbd "C-x C-f")`, a plain string, or a vector such as `[f5]`.
+// Bindings that need Hyper or Super have no equivalent on most keyboards and
+// are skipped.
+
+use super::{
+    humanize_identifier, parse_error_at, save_imported_list, ImportSummary, ImportedShortcut,
+    KeyStroke,
+};
+use crate::storage::StorageError;
+use std::path::Path;
+
+pub const EMACS_APP_ID: &str = "app-emacs";
+pub const LIST_NAME: &str = "init.el Bindings";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Sexp {
+    Atom(String),
+    Str(String),
+    // Items plus the byte offset just past the closing paren
+    List(Vec<Sexp>, usize),
+    Vector(Vec<Sexp>),
+    Quoted(Box<Sexp>),
+}
+
+pub fn import_init_el(path: &Path) -> Result<ImportSummary, StorageError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| StorageError::io(path, e))?;
+    let (shortcuts, skipped) = parse_init_el(&contents)
+        .map_err(|(offset, message)| parse_error_at(path, &contents, offset, message))?;
+    save_imported_list(EMACS_APP_ID, LIST_NAME, shortcuts, skipped)
+}
+
+// Returns the shortcuts plus the number of bindings that couldn't be
+// converted (e.g. `[remap ...]` or `H-x`). Errors carry a byte offset.
+pub fn parse_init_el(contents: &str) -> Result<(Vec<ImportedShortcut>, usize), (usize, String)> {
+    let forms = Reader::new(contents).read_all()?;
+    let mut shortcuts = Vec::new();
+    let mut skipped = 0;
+    for form in &forms {
+        collect_bindings(form, contents, &mut shortcuts, &mut skipped);
+    }
+    Ok((shortcuts, skipped))
+}
+
+fn collect_bindings(
+    form: &Sexp,
+    source: &str,
+    shortcuts: &mut Vec<ImportedShortcut>,
+    skipped: &mut usize,
+) {
+    let Sexp::List(items, end) = form else {
+        return;
+    };
+
+    if let Some((keymap, key, command)) = binding_parts(items) {
+        let description = trailing_comment(source, *end);
+        push_binding(keymap, key, command, description, shortcuts, skipped);
+        return;
+    }
+
+    if let Some(Sexp::Atom(head)) = items.first() {
+        if head == "use-package" {
+            for (keymap, pair) in use_package_bindings(items) {
+                let Sexp::List(parts, end) = pair else {
+                    continue;
+                };
+                if let [key, Sexp::Atom(dot), command] = parts.as_slice() {
+                    if dot == "." {
+                        let description = trailing_comment(source, *end);
+                        push_binding(keymap, key, command, description, shortcuts, skipped);
+                    }
+                }
+            }
+        }
+    }
+
+    for item in items {
+        collect_bindings(item, source, shortcuts, skipped);
+    }
+}
+
+fn push_binding(
+    keymap: Option<&str>,
+    key: &Sexp,
+    command: &Sexp,
+    comment: Option<String>,
+    shortcuts: &mut Vec<ImportedShortcut>,
+    skipped: &mut usize,
+) {
+    let Some(key_combo) = convert_key_arg(key) else {
+        *skipped += 1;
+        return;
+    };
+    let description = comment.unwrap_or_else(|| {
+        let mut description = describe_command(command);
+        if let Some(mode) = keymap.and_then(|k| k.strip_suffix("-map")) {
+            if mode != "global" {
+                description = format!("{description} ({mode})");
+            }
+        }
+        description
+    });
+    shortcuts.push(ImportedShortcut {
+        key_combo,
+        description,
+    });
+}
+
+// The `("C-c a" . command)` pairs of a use-package form's `:bind` and `:bind*`
+// arguments, each with the keymap a preceding `:map` names. The argument is a
+// single pair or a list of pairs and `:map` switches.
+fn use_package_bindings(items: &[Sexp]) -> Vec<(Option<&str>, &Sexp)> {
+    let mut pairs = Vec::new();
+    let mut args = items.iter();
+    while let Some(item) = args.next() {
+        if !matches!(item, Sexp::Atom(k) if k == ":bind" || k == ":bind*") {
+            continue;
+        }
+        let Some(arg @ Sexp::List(bind, _)) = args.next() else {
+            continue;
+        };
+        if matches!(bind.get(1), Some(Sexp::Atom(dot)) if dot == ".") {
+            pairs.push((None, arg));
+            continue;
+        }
+        let mut keymap = None;
+        let mut bind = bind.iter();
+        while let Some(entry) = bind.next() {
+            match entry {
+                Sexp::Atom(k) if k == ":map" => keymap = bind.next().and_then(keymap_name),
+                Sexp::List(..) => pairs.push((keymap, entry)),
+                _ => {}
+            }
+        }
+    }
+    pairs
+}
+
+// (keymap name, key argument, command) for a binding form
+fn binding_parts(items: &[Sexp]) -> Option<(Option<&str>, &Sexp, &Sexp)> {
+    let Some(Sexp::Atom(head)) = items.first() else {
+        return None;
+    };
+    match (head.as_str(), items.len()) {
+        ("global-set-key" | "keymap-global-set" | "local-set-key" | "keymap-local-set", 3) => {
+            Some((None, &items[1], &items[2]))
+        }
+        ("define-key" | "keymap-set", 4) => Some((keymap_name(&items[1]), &items[2], &items[3])),
+        _ => None,
+    }
+}
+
+fn keymap_name(sexp: &Sexp) -> Option<&str> {
+    match sexp {
+        Sexp::Atom(name) => Some(name),
+        Sexp::Quoted(inner) => keymap_name(inner),
+        _ => None,
+    }
+}
+
+fn describe_command(command: &Sexp) -> String {
+    match command {
+        Sexp::Quoted(inner) => describe_command(inner),
+        Sexp::Atom(name) => humanize_identifier(name.trim_start_matches("#'")),
+        Sexp::List(items, _) => match items.first() {
+            Some(Sexp::Atom(head)) if head == "lambda" => "Custom command".to_string(),
+            Some(Sexp::Atom(head)) if head == "function" => {
+                items.get(1).map(describe_command).unwrap_or_default()
+            }
+            _ => "Custom command".to_string(),
+        },
+        Sexp::Str(text) => format!("Insert \"{text}\""),
+        Sexp::Vector(_) => "Keyboard macro".to_string(),
+    }
+}
+
+// `;; comment` on the same line after `end`
+fn trailing_comment(source: &str, end: usize) -> Option<String> {
+    let rest = source.get(end..)?;
+    let line = rest.split('\n').next().unwrap_or("");
+    let comment = line.trim_start().strip_prefix(';')?;
+    let comment = comment.trim_start_matches(';').trim();
+    if comment.is_empty() {
+        None
+    } else {
+        Some(comment.to_string())
+    }
+}
+
+fn convert_key_arg(key: &Sexp) -> Option<String> {
+    match key {
+        // (kbd "C-x C-f")
+        Sexp::List(items, _) => match (items.first(), items.get(1)) {
+            (Some(Sexp::Atom(head)), Some(Sexp::Str(keys))) if head == "kbd" => convert_kbd(keys),
+            _ => None,
+        },
+        // keymap-set strings use kbd syntax; legacy strings may use "\C-c"
+        Sexp::Str(keys) => {
+            if keys.contains("\\C-") || keys.contains("\\M-") {
+                convert_legacy_string(keys)
+            } else {
+                convert_kbd(keys)
+            }
+        }
+        // [f5], [C-f5], [remap kill-buffer]
+        Sexp::Vector(items) => {
+            let mut strokes = Vec::new();
+            for item in items {
+                match item {
+                    Sexp::Atom(name) if name != "remap" => strokes.push(convert_chord(name)?),
+                    _ => return None,
+                }
+            }
+            (!strokes.is_empty()).then(|| strokes.join(", "))
+        }
+        _ => None,
+    }
+}
+
+// "C-x C-f" → "Ctrl+X, Ctrl+F"
+pub fn convert_kbd(keys: &str) -> Option<String> {
+    let strokes: Option<Vec<String>> = keys.split_whitespace().map(convert_chord).collect();
+    strokes.filter(|s| !s.is_empty()).map(|s| s.join(", "))
+}
+
+// One chord such as "C-M-s", "M-<up>", "<f5>", "RET" or "A"
+fn convert_chord(chord: &str) -> Option<String> {
+    let mut stroke = KeyStroke::default();
+    let mut rest = chord;
+    loop {
+        let modifier = match rest.get(..2) {
+            Some("C-") => "ctrl",
+            Some("M-") => "alt",
+            Some("S-") => "shift",
+            // Hyper and Super aren't Alt or Win; aliasing them would collide
+            // with real M- and Win bindings
+            Some("H-") | Some("s-") if rest.len() > 2 => return None,
+            Some("A-") => "alt",
+            _ => break,
+        };
+        // "C--" is Ctrl+minus, so stop before consuming the final "-"
+        if rest.len() == 2 {
+            break;
+        }
+        stroke.add_modifier(modifier);
+        rest = &rest[2..];
+    }
+
+    let key = rest.trim_start_matches('<').trim_end_matches('>');
+    if key.is_empty() {
+        return None;
+    }
+    let key = match key {
+        "RET" | "return" => "Enter",
+        "SPC" => "Space",
+        "TAB" | "tab" => "Tab",
+        "ESC" | "escape" => "Esc",
+        "DEL" | "backspace" => "Backspace",
+        "deletechar" | "delete" => "Delete",
+        "prior" => "PageUp",
+        "next" => "PageDown",
+        other => other,
+    };
+    // Emacs is case-sensitive: "C-c A" means Shift+A
+    if key.chars().count() == 1 && key.chars().all(|c| c.is_ascii_uppercase()) {
+        stroke.add_modifier("shift");
+    }
+    stroke.set_key(key);
+    Some(stroke.to_combo_string())
+}
+
+// Old-style strings: "\C-cl" → "Ctrl+C, L"
+fn convert_legacy_string(keys: &str) -> Option<String> {
+    let mut strokes = Vec::new();
+    let mut rest = keys;
+    while !rest.is_empty() {
+        let mut chord = String::new();
+        while let Some(prefix) = ["\\C-", "\\M-", "\\S-"]
+            .iter()
+            .find(|p| rest.starts_with(**p))
+        {
+            chord.push_str(&prefix[1..]);
+            rest = &rest[3..];
+        }
+        let c = rest.chars().next()?;
+        chord.push(c);
+        rest = &rest[c.len_utf8()..];
+        strokes.push(convert_chord(&chord)?);
+    }
+    (!strokes.is_empty()).then(|| strokes.join(", "))
+}
+
+// Minimal Emacs Lisp reader: lists, vectors, strings, atoms, quotes and
+// `;` comments. Enough to find binding forms; everything else is skipped.
+struct Reader<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(source: &'a str) -> Self {
+        Reader { source, pos: 0 }
+    }
+
+    fn read_all(mut self) -> Result<Vec<Sexp>, (usize, String)> {
+        let mut forms = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.pos >= self.source.len() {
+                return Ok(forms);
+            }
+            forms.push(self.read()?);
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == ';' {
+                while let Some(c) = self.bump() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            } else if c.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn read(&mut self) -> Result<Sexp, (usize, String)> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek() {
+            None => Err((start, "unexpected end of file".to_string())),
+            Some('(') => {
+                self.bump();
+                let items = self.read_until(')')?;
+                Ok(Sexp::List(items, self.pos))
+            }
+            Some('[') => {
+                self.bump();
+                Ok(Sexp::Vector(self.read_until(']')?))
+            }
+            Some(')') | Some(']') => Err((start, "unexpected closing bracket".to_string())),
+            Some('"') => {
+                self.bump();
+                self.read_string(start)
+            }
+            Some('\'') | Some('`') | Some(',') => {
+                self.bump();
+                if self.peek() == Some('@') {
+                    self.bump();
+                }
+                Ok(Sexp::Quoted(Box::new(self.read()?)))
+            }
+            Some('#') if self.source[self.pos..].starts_with("#'") => {
+                self.pos += 2;
+                Ok(Sexp::Quoted(Box::new(self.read()?)))
+            }
+            Some('?') => Ok(Sexp::Atom(self.read_char_literal())),
+            Some(_) => Ok(Sexp::Atom(self.read_atom())),
+        }
+    }
+
+    fn read_until(&mut self, close: char) -> Result<Vec<Sexp>, (usize, String)> {
+        let open_pos = self.pos - 1;
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None => return Err((open_pos, format!("missing closing '{close}'"))),
+                Some(c) if c == close => {
+                    self.bump();
+                    return Ok(items);
+                }
+                _ => items.push(self.read()?),
+            }
+        }
+    }
+
+    // Keeps backslash escapes other than \" and \\ as written, so legacy key
+    // strings like "\C-c" survive.
+    fn read_string(&mut self, start: usize) -> Result<Sexp, (usize, String)> {
+        let mut text = String::new();
+        loop {
+            match self.bump() {
+                None => return Err((start, "unterminated string".to_string())),
+                Some('"') => return Ok(Sexp::Str(text)),
+                Some('\\') => match self.bump() {
+                    Some('"') => text.push('"'),
+                    Some('\\') => text.push('\\'),
+                    Some(c) => {
+                        text.push('\\');
+                        text.push(c);
+                    }
+                    None => return Err((start, "unterminated string".to_string())),
+                },
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    // `?a`, `?(`, `?\;`, `?\C-x`: the character after `?` (or after its
+    // backslash) belongs to the literal even when it's a delimiter
+    fn read_char_literal(&mut self) -> String {
+        let mut atom = String::new();
+        atom.extend(self.bump());
+        match self.bump() {
+            Some('\\') => {
+                atom.push('\\');
+                atom.extend(self.bump());
+            }
+            Some(c) => atom.push(c),
+            None => return atom,
+        }
+        atom + &self.read_atom()
+    }
+
+    fn read_atom(&mut self) -> String {
+        let mut atom = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || "()[]\";".contains(c) {
+                break;
+            }
+            self.bump();
+            // `?\C-x` character syntax and escaped symbol characters
+            if c == '\\' {
+                if let Some(next) = self.bump() {
+                    atom.push(c);
+                    atom.push(next);
+                }
+                continue;
+            }
+            atom.push(c);
+        }
+        atom
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(init_el: &str) -> (Vec<(String, String)>, usize) {
+        let (shortcuts, skipped) = parse_init_el(init_el).unwrap();
+        let shortcuts = shortcuts
+            .into_iter()
+            .map(|s| (s.key_combo, s.description))
+            .collect();
+        (shortcuts, skipped)
+    }
+
+    fn pair(keys: &str, description: &str) -> (String, String) {
+        (keys.to_string(), description.to_string())
+    }
+
+    #[test]
+    fn reads_character_literals_and_escaped_strings() {
+        let forms = Reader::new(r#"(insert ?( ?) ?\; ?\C-x ?a) "say \"hi\" \\ \C-c""#)
+            .read_all()
+            .unwrap();
+        let Sexp::List(items, _) = &forms[0] else {
+            panic!("expected a list, got {forms:?}");
+        };
+        let atoms: Vec<String> = items[1..]
+            .iter()
+            .map(|item| match item {
+                Sexp::Atom(atom) => atom.clone(),
+                other => format!("{other:?}"),
+            })
+            .collect();
+        assert_eq!(atoms, ["?(", "?)", "?\\;", "?\\C-x", "?a"]);
+        assert_eq!(forms[1], Sexp::Str(r#"say "hi" \ \C-c"#.to_string()));
+    }
+
+    #[test]
+    fn character_literals_inside_bindings_do_not_abort_the_import() {
+        let init_el = r#"
+(define-key map (kbd "C-c (") (lambda () (interactive) (insert ?()))
+(global-set-key (kbd "C-c )") (lambda () (interactive) (insert ?\))))
+(global-set-key (kbd "C-c ;") (lambda () (interactive) (insert ?\;))) ; semicolon
+"#;
+        assert_eq!(
+            parsed(init_el).0,
+            vec![
+                pair("Ctrl+C, (", "Custom command"),
+                pair("Ctrl+C, )", "Custom command"),
+                pair("Ctrl+C, ;", "semicolon"),
+            ]
+        );
+    }
+
+    #[test]
+    fn binding_forms() {
+        let init_el = r#"
+(global-set-key (kbd "C-x C-s") 'save-buffer)
+(global-set-key "\C-cl" #'org-store-link)
+(keymap-global-set "M-<up>" 'move-line-up)
+(with-eval-after-load 'python
+  (define-key python-mode-map (kbd "C-c C-r") 'python-shell-send-region))
+(use-package org
+  :bind (("C-c a" . org-agenda)
+         ("C-c c" . org-capture) ; capture a note
+         :map org-mode-map
+         ("C-c C-x" . org-cut-subtree)))
+(use-package magit :bind ("C-x g" . magit-status))
+"#;
+        assert_eq!(
+            parsed(init_el),
+            (
+                vec![
+                    pair("Ctrl+X, Ctrl+S", "Save buffer"),
+                    pair("Ctrl+C, L", "Org store link"),
+                    pair("Alt+↑", "Move line up"),
+                    pair("Ctrl+C, Ctrl+R", "Python shell send region (python-mode)"),
+                    pair("Ctrl+C, A", "Org agenda"),
+                    pair("Ctrl+C, C", "capture a note"),
+                    pair("Ctrl+C, Ctrl+X", "Org cut subtree (org-mode)"),
+                    pair("Ctrl+X, G", "Magit status"),
+                ],
+                0
+            )
+        );
+    }
+
+    #[test]
+    fn kbd_chords() {
+        assert_eq!(convert_kbd("C-x C-s").as_deref(), Some("Ctrl+X, Ctrl+S"));
+        assert_eq!(convert_kbd("C-M-s").as_deref(), Some("Ctrl+Alt+S"));
+        assert_eq!(convert_kbd("C-c A").as_deref(), Some("Ctrl+C, Shift+A"));
+        assert_eq!(convert_kbd("<f5>").as_deref(), Some("F5"));
+        assert_eq!(convert_kbd("C--").as_deref(), Some("Ctrl+-"));
+        assert_eq!(convert_kbd("C-c RET").as_deref(), Some("Ctrl+C, Enter"));
+    }
+
+    #[test]
+    fn vectors_remap_and_hyper_super() {
+        let init_el = r#"
+(global-set-key [f5] 'revert-buffer)
+(global-set-key [C-f5] 'compile)
+(global-set-key [remap kill-buffer] 'kill-this-buffer)
+(global-set-key (kbd "H-x") 'execute-extended-command)
+(global-set-key (kbd "s-s") 'save-buffer)
+(global-set-key (kbd "M-x") 'execute-extended-command)
+"#;
+        assert_eq!(
+            parsed(init_el),
+            (
+                vec![
+                    pair("F5", "Revert buffer"),
+                    pair("Ctrl+F5", "Compile"),
+                    pair("Alt+X", "Execute extended command"),
+                ],
+                3
+            )
+        );
+    }
+}
@@ -0,0 +1,414 @@
+// Import a JetBrains keymap `.xml` (IntelliJ IDEA, PyCharm, WebStorm, Rider)
+// into a list named after the keymap.
+//
+// Custom keymaps only store the differences from their `parent` keymap, so
+// parents found as sibling `.xml` files (JetBrains keeps them together in its
+// `keymaps/` folder) are merged in first. Built-in parents such as `$default`
+// aren't files and end the chain.
+
+use super::{
+    humanize_identifier, join_sequence, parse_error_at, save_imported_list, ImportSummary,
+    ImportedShortcut, KeyStroke,
+};
+use crate::storage::StorageError;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::path::Path;
+
+pub const JETBRAINS_APP_IDS: [&str; 4] = [
+    "app-intellij-idea",
+    "app-pycharm",
+    "app-webstorm",
+    "app-rider",
+];
+
+// Readable names for common action ids; anything else is humanized
+const ACTION_DESCRIPTIONS: &[(&str, &str)] = &[
+    ("SearchEverywhere", "Search Everywhere"),
+    ("GotoAction", "Find Action"),
+    ("GotoClass", "Go to Class"),
+    ("GotoFile", "Go to File"),
+    ("GotoSymbol", "Go to Symbol"),
+    ("GotoDeclaration", "Go to Declaration"),
+    ("GotoImplementation", "Go to Implementation(s)"),
+    ("GotoLine", "Go to Line"),
+    ("FindUsages", "Find Usages"),
+    ("FindInPath", "Find in Files"),
+    ("ReplaceInPath", "Replace in Files"),
+    ("RenameElement", "Rename"),
+    ("Refactorings.QuickListPopupAction", "Refactor This"),
+    ("ReformatCode", "Reformat Code"),
+    ("OptimizeImports", "Optimize Imports"),
+    ("ShowIntentionActions", "Show Intention Actions"),
+    ("EditorDuplicate", "Duplicate Line/Block"),
+    ("EditorDeleteLine", "Delete Line"),
+    ("CommentByLineComment", "Comment/Uncomment Line"),
+    ("CommentByBlockComment", "Comment/Uncomment Block"),
+    ("EditorSelectWord", "Extend Selection"),
+    ("EditorUnSelectWord", "Shrink Selection"),
+    ("MoveLineUp", "Move Line Up"),
+    ("MoveLineDown", "Move Line Down"),
+    ("MoveStatementUp", "Move Statement Up"),
+    ("MoveStatementDown", "Move Statement Down"),
+    ("Generate", "Generate Code"),
+    ("ChangeSignature", "Change Signature"),
+    ("Back", "Navigate Back"),
+    ("Forward", "Navigate Forward"),
+    ("RecentFiles", "Recent Files"),
+    ("JumpToLastChange", "Go to Last Edit Location"),
+    ("GotoNextError", "Next Error"),
+    ("GotoPreviousError", "Previous Error"),
+    ("CallHierarchy", "Show Call Hierarchy"),
+    ("ShowSettings", "Open Settings"),
+    ("ActivateProjectToolWindow", "Toggle Project View"),
+    ("HideAllWindows", "Hide All Tool Windows"),
+    ("CodeCompletion", "Basic Code Completion"),
+    ("SmartTypeCompletion", "Smart Code Completion"),
+    ("EditorCompleteStatement", "Complete Statement"),
+    ("Run", "Run"),
+    ("Debug", "Debug"),
+    ("ToggleLineBreakpoint", "Toggle Breakpoint"),
+    ("StepOver", "Step Over"),
+    ("StepInto", "Step Into"),
+    ("Resume", "Resume Program"),
+    ("$Undo", "Undo"),
+    ("$Redo", "Redo"),
+    ("$Copy", "Copy"),
+    ("$Cut", "Cut"),
+    ("$Paste", "Paste"),
+    ("$SelectAll", "Select All"),
+];
+
+#[derive(Debug, Clone)]
+struct Keymap {
+    name: String,
+    parent: Option<String>,
+    // Action id → key combos, in document order. An empty list unbinds an
+    // action inherited from the parent.
+    actions: Vec<(String, Vec<String>)>,
+}
+
+pub fn import_keymap(path: &Path, app_id: &str) -> Result<ImportSummary, StorageError> {
+    if !JETBRAINS_APP_IDS.contains(&app_id) {
+        return Err(StorageError::validation(format!(
+            "{app_id} is not a JetBrains application"
+        )));
+    }
+
+    let keymap = read_keymap(path)?;
+    let chain = resolve_parents(path, keymap);
+    let (shortcuts, skipped) = merge_chain(&chain);
+    save_imported_list(app_id, &chain[0].name, shortcuts, skipped)
+}
+
+fn read_keymap(path: &Path) -> Result<Keymap, StorageError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| StorageError::io(path, e))?;
+    parse_keymap(&contents)
+        .map_err(|(offset, message)| parse_error_at(path, &contents, offset, message))
+}
+
+// Returns [keymap, parent, grandparent, ...] using sibling .xml files
+fn resolve_parents(path: &Path, keymap: Keymap) -> Vec<Keymap> {
+    let mut siblings: HashMap<String, Keymap> = HashMap::new();
+    if let Some(dir) = path.parent() {
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let sibling = entry.path();
+                if sibling == path || sibling.extension().is_none_or(|ext| ext != "xml") {
+                    continue;
+                }
+                // Unreadable siblings are ignored; they may not be keymaps at all
+                if let Ok(parsed) = read_keymap(&sibling) {
+                    siblings.insert(parsed.name.clone(), parsed);
+                }
+            }
+        }
+    }
+
+    let mut chain = vec![keymap];
+    while let Some(parent_name) = chain.last().and_then(|k| k.parent.clone()) {
+        // remove() also guards against parent cycles
+        match siblings.remove(&parent_name) {
+            Some(parent) => chain.push(parent),
+            None => break,
+        }
+    }
+    chain
+}
+
+// Apply the chain from the oldest ancestor down, letting each keymap replace
+// the bindings of the actions it lists. Returns the shortcuts and the number
+// of actions left unbound.
+fn merge_chain(chain: &[Keymap]) -> (Vec<ImportedShortcut>, usize) {
+    let mut merged: Vec<(String, Vec<String>)> = Vec::new();
+    for keymap in chain.iter().rev() {
+        for (id, combos) in &keymap.actions {
+            match merged.iter_mut().find(|(existing, _)| existing == id) {
+                Some(entry) => entry.1 = combos.clone(),
+                None => merged.push((id.clone(), combos.clone())),
+            }
+        }
+    }
+
+    let skipped = merged
+        .iter()
+        .filter(|(_, combos)| combos.is_empty())
+        .count();
+    let shortcuts = merged
+        .iter()
+        .flat_map(|(id, combos)| {
+            let description = describe_action(id);
+            combos.iter().map(move |combo| ImportedShortcut {
+                key_combo: combo.clone(),
+                description: description.clone(),
+            })
+        })
+        .collect();
+    (shortcuts, skipped)
+}
+
+fn describe_action(id: &str) -> String {
+    ACTION_DESCRIPTIONS
+        .iter()
+        .find(|(action, _)| *action == id)
+        .map(|(_, description)| description.to_string())
+        .unwrap_or_else(|| humanize_identifier(id.trim_start_matches('$')))
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>, String> {
+    match element.try_get_attribute(name).map_err(|e| e.to_string())? {
+        Some(attr) => Ok(Some(
+            attr.unescape_value()
+                .map_err(|e| e.to_string())?
+                .into_owned(),
+        )),
+        None => Ok(None),
+    }
+}
+
+// Parse keymap XML. Errors carry the byte offset they occurred at.
+fn parse_keymap(xml: &str) -> Result<Keymap, (usize, String)> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut keymap: Option<Keymap> = None;
+    let mut current_action: Option<(String, Vec<String>)> = None;
+
+    loop {
+        let offset = reader.buffer_position() as usize;
+        let event = reader
+            .read_event()
+            .map_err(|e| (reader.error_position() as usize, e.to_string()))?;
+        let at = |message: String| (offset, message);
+
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let is_empty = matches!(event, Event::Empty(_));
+                match e.name().as_ref() {
+                    b"keymap" => {
+                        keymap = Some(Keymap {
+                            name: attribute(e, "name")
+                                .map_err(at)?
+                                .unwrap_or_else(|| "Imported Keymap".to_string()),
+                            parent: attribute(e, "parent").map_err(at)?,
+                            actions: Vec::new(),
+                        });
+                    }
+                    b"action" => {
+                        let id = attribute(e, "id")
+                            .map_err(at)?
+                            .ok_or_else(|| at("<action> without an id".to_string()))?;
+                        if is_empty {
+                            if let Some(keymap) = keymap.as_mut() {
+                                keymap.actions.push((id, Vec::new()));
+                            }
+                        } else {
+                            current_action = Some((id, Vec::new()));
+                        }
+                    }
+                    b"keyboard-shortcut" => {
+                        let first =
+                            attribute(e, "first-keystroke")
+                                .map_err(at)?
+                                .ok_or_else(|| {
+                                    at("<keyboard-shortcut> without first-keystroke".to_string())
+                                })?;
+                        let mut strokes = vec![convert_keystroke(&first)];
+                        if let Some(second) = attribute(e, "second-keystroke").map_err(at)? {
+                            strokes.push(convert_keystroke(&second));
+                        }
+                        if let Some((_, combos)) = current_action.as_mut() {
+                            combos.push(join_sequence(&strokes));
+                        }
+                    }
+                    // Mouse and other shortcut kinds aren't keyboard shortcuts
+                    _ => {}
+                }
+            }
+            Event::End(ref e) if e.name().as_ref() == b"action" => {
+                if let (Some(action), Some(keymap)) = (current_action.take(), keymap.as_mut()) {
+                    keymap.actions.push(action);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    keymap.ok_or_else(|| (0, "No <keymap> element found".to_string()))
+}
+
+// Java KeyStroke text ("control shift BACK_SPACE") → KeyStroke
+fn convert_keystroke(text: &str) -> KeyStroke {
+    let mut stroke = KeyStroke::default();
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    if let Some((key, modifiers)) = tokens.split_last() {
+        for modifier in modifiers {
+            match *modifier {
+                // JetBrains "meta" is the macOS Command key
+                "meta" => stroke.add_modifier("cmd"),
+                "altGraph" => stroke.add_modifier("alt"),
+                other => stroke.add_modifier(other),
+            };
+        }
+        stroke.set_key(java_key_name(key));
+    }
+    stroke
+}
+
+fn java_key_name(key: &str) -> &str {
+    match key {
+        "BACK_SPACE" => "Backspace",
+        "ESCAPE" => "Esc",
+        "PAGE_UP" => "PageUp",
+        "PAGE_DOWN" => "PageDown",
+        "OPEN_BRACKET" => "[",
+        "CLOSE_BRACKET" => "]",
+        "SEMICOLON" => ";",
+        "COMMA" => ",",
+        "PERIOD" => ".",
+        "SLASH" | "DIVIDE" => "/",
+        "BACK_SLASH" => "\\",
+        "MINUS" | "SUBTRACT" => "-",
+        "EQUALS" => "=",
+        "ADD" | "PLUS" => "+",
+        "MULTIPLY" => "*",
+        "BACK_QUOTE" => "`",
+        "QUOTE" => "'",
+        other => other.strip_prefix("NUMPAD").unwrap_or(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::tests::scratch_dir;
+
+    fn combos(shortcuts: &[ImportedShortcut]) -> Vec<(&str, &str)> {
+        shortcuts
+            .iter()
+            .map(|s| (s.key_combo.as_str(), s.description.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_chords_and_skips_mouse_shortcuts() {
+        let keymap = parse_keymap(
+            r#"<keymap version="1" name="Mine" parent="$default">
+                 <action id="GotoFile">
+                   <keyboard-shortcut first-keystroke="control shift N" />
+                   <keyboard-shortcut first-keystroke="control K" second-keystroke="control F" />
+                 </action>
+                 <action id="GotoDeclaration">
+                   <mouse-shortcut keystroke="control button1" />
+                 </action>
+                 <action id="EditorDuplicate" />
+               </keymap>"#,
+        )
+        .unwrap();
+        assert_eq!(keymap.name, "Mine");
+        assert_eq!(keymap.parent.as_deref(), Some("$default"));
+        assert_eq!(
+            keymap.actions,
+            vec![
+                (
+                    "GotoFile".to_string(),
+                    vec!["Ctrl+Shift+N".to_string(), "Ctrl+K, Ctrl+F".to_string()]
+                ),
+                ("GotoDeclaration".to_string(), Vec::new()),
+                ("EditorDuplicate".to_string(), Vec::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors_carry_an_offset() {
+        let xml = r#"<keymap name="Mine"><action><keyboard-shortcut /></action></keymap>"#;
+        let (offset, message) = parse_keymap(xml).unwrap_err();
+        assert_eq!(offset, xml.find("<action>").unwrap());
+        assert!(message.contains("without an id"), "{message}");
+        assert!(parse_keymap("<keymaps />").is_err());
+    }
+
+    #[test]
+    fn converts_keystrokes() {
+        let cases = [
+            ("control shift A", "Ctrl+Shift+A"),
+            ("meta BACK_SPACE", "Cmd+Backspace"),
+            ("alt OPEN_BRACKET", "Alt+["),
+            ("altGraph CLOSE_BRACKET", "Alt+]"),
+            ("shift ESCAPE", "Shift+Esc"),
+            ("control NUMPAD5", "Ctrl+5"),
+            ("F12", "F12"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(
+                convert_keystroke(text).to_combo_string(),
+                expected,
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn child_keymaps_override_and_unbind_parent_actions() {
+        let dir = scratch_dir("jetbrains-parents");
+        std::fs::write(
+            dir.join("base.xml"),
+            r#"<keymap name="Base" parent="$default">
+                 <action id="GotoFile"><keyboard-shortcut first-keystroke="control shift N" /></action>
+                 <action id="EditorDeleteLine"><keyboard-shortcut first-keystroke="control Y" /></action>
+                 <action id="ReformatCode"><keyboard-shortcut first-keystroke="control alt L" /></action>
+               </keymap>"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("notes.xml"), "not a keymap").unwrap();
+        let child_path = dir.join("child.xml");
+        std::fs::write(
+            &child_path,
+            r#"<keymap name="Child" parent="Base">
+                 <action id="EditorDeleteLine" />
+                 <action id="ReformatCode"><keyboard-shortcut first-keystroke="control shift F" /></action>
+                 <action id="ToggleZenMode"><keyboard-shortcut first-keystroke="alt P" /></action>
+               </keymap>"#,
+        )
+        .unwrap();
+
+        let chain = resolve_parents(&child_path, read_keymap(&child_path).unwrap());
+        let names: Vec<&str> = chain.iter().map(|k| k.name.as_str()).collect();
+        assert_eq!(names, vec!["Child", "Base"]);
+
+        let (shortcuts, skipped) = merge_chain(&chain);
+        assert_eq!(
+            combos(&shortcuts),
+            vec![
+                ("Ctrl+Shift+N", "Go to File"),
+                ("Ctrl+Shift+F", "Reformat Code"),
+                ("Alt+P", "Toggle zen mode"),
+            ]
+        );
+        assert_eq!(skipped, 1);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,199 @@
+// Importers that turn other tools' keybinding files into ShortcutLists.
+//
+// Each importer parses its own format into `ImportedShortcut`s and then calls
+// `save_imported_list`, which creates the target list or replaces the
+// shortcuts of an existing list with the same application + name.
+
+pub mod emacs;
+pub mod jetbrains;
+pub mod shell;
+pub mod tmux;
+pub mod vim;
+pub mod vscode;
+
+use crate::key_combo::{self, Modifier};
+use crate::storage::{self, Shortcut, ShortcutList, StorageError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// A shortcut parsed from a foreign config, before ids/order are assigned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedShortcut {
+    pub key_combo: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub application_id: String,
+    pub list_id: String,
+    pub list_name: String,
+    // false when an existing list was updated
+    pub created: bool,
+    pub shortcut_count: usize,
+    // Entries that were recognised but not imported (e.g. unbind entries)
+    pub skipped: usize,
+}
+
+// Create `list_name` for `app_id`, or replace the shortcuts of the existing
+// list with that name (the same app + name matching initialize_defaults uses).
+pub fn save_imported_list(
+    app_id: &str,
+    list_name: &str,
+    imported: Vec<ImportedShortcut>,
+    skipped: usize,
+) -> Result<ImportSummary, StorageError> {
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let shortcuts: Vec<Shortcut> = imported
+        .into_iter()
+        .enumerate()
+        .map(|(i, s)| Shortcut {
+            id: uuid::Uuid::new_v4().to_string(),
+            key_combo: key_combo::normalize(&s.key_combo),
+            description: s.description,
+            order: i as i32,
+        })
+        .collect();
+    let shortcut_count = shortcuts.len();
+
+    let mut app_lists = storage::load_lists_for_application(app_id)?;
+    let (list_id, created) = match app_lists.iter_mut().find(|l| l.name == list_name) {
+        Some(existing) => {
+            existing.shortcuts = shortcuts;
+            existing.updated_at = now;
+            (existing.id.clone(), false)
+        }
+        None => {
+            let list = ShortcutList {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: list_name.to_string(),
+                application_id: app_id.to_string(),
+                shortcuts,
+                created_at: now.clone(),
+                updated_at: now,
+                match_rules: Vec::new(),
+            };
+            let id = list.id.clone();
+            app_lists.push(list);
+            (id, true)
+        }
+    };
+    storage::save_lists_for_application(app_id, &app_lists)?;
+
+    Ok(ImportSummary {
+        application_id: app_id.to_string(),
+        list_id,
+        list_name: list_name.to_string(),
+        created,
+        shortcut_count,
+        skipped,
+    })
+}
+
+// Parse error for a non-JSON config, with the line/column of byte `offset`
+pub fn parse_error_at(path: &Path, contents: &str, offset: usize, message: String) -> StorageError {
+    let before = &contents[..offset.min(contents.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    StorageError::Parse {
+        path: path.display().to_string(),
+        line,
+        column,
+        message,
+    }
+}
+
+// One keystroke in the app's key_combo notation, e.g. "Ctrl+Shift+P"
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyStroke {
+    modifiers: Vec<Modifier>,
+    key: String,
+}
+
+impl KeyStroke {
+    // Add a modifier by any of its common spellings. Returns false if `name`
+    // isn't a modifier.
+    pub fn add_modifier(&mut self, name: &str) -> bool {
+        let Some(modifier) = Modifier::from_name(name) else {
+            return false;
+        };
+        if !self.modifiers.contains(&modifier) {
+            self.modifiers.push(modifier);
+            self.modifiers.sort();
+        }
+        true
+    }
+
+    pub fn set_key(&mut self, raw: &str) {
+        self.key = key_label(raw);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modifiers.is_empty() && self.key.is_empty()
+    }
+
+    pub fn to_combo_string(&self) -> String {
+        let mut parts: Vec<&str> = self.modifiers.iter().map(|m| m.label()).collect();
+        if !self.key.is_empty() {
+            parts.push(&self.key);
+        }
+        parts.join("+")
+    }
+}
+
+// Join a multi-step sequence the way the bundled defaults do ("Ctrl+K, Ctrl+C")
+pub fn join_sequence(strokes: &[KeyStroke]) -> String {
+    strokes
+        .iter()
+        .map(KeyStroke::to_combo_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// Map a key name from another tool onto the label the app displays
+pub fn key_label(raw: &str) -> String {
+    key_combo::canonical_key(raw)
+}
+
+// "editor.action.commentLine" → "Comment line"
+pub fn humanize_identifier(id: &str) -> String {
+    let last = id.rsplit(['.', ':']).next().unwrap_or(id);
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in last.chars() {
+        if c == '_' || c == '-' || c == ' ' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    let sentence = words
+        .iter()
+        .map(|w| {
+            // Keep acronyms (e.g. "HTML") as they are
+            if w.len() > 1 && w.chars().all(|c| c.is_uppercase()) {
+                w.clone()
+            } else {
+                w.to_lowercase()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut chars = sentence.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => id.to_string(),
+    }
+}
@@ -0,0 +1,643 @@
+// Import shell line-editing bindings from an .inputrc (readline), bash `bind`
+// lines or zsh `bindkey` output / .zshrc `bindkey` lines into a "Shell line
+// editing" list for one of the terminal applications.
+//
+// Both formats describe keys as the bytes the terminal sends ("\C-a", "^[b",
+// "\e[1;5C"), so they are decoded to raw characters first and then turned
+// into keystrokes by `decode_sequence`.
+
+use super::{
+    humanize_identifier, join_sequence, save_imported_list, ImportSummary, ImportedShortcut,
+    KeyStroke,
+};
+use crate::storage::StorageError;
+use std::path::Path;
+
+pub const LIST_NAME: &str = "Shell line editing";
+
+// Applications that share the terminal default templates
+pub const TERMINAL_APP_IDS: [&str; 5] = [
+    "app-windows-terminal",
+    "app-iterm2",
+    "app-mac-terminal",
+    "app-cmd",
+    "app-powershell",
+];
+
+const ESC: char = '\u{1b}';
+const DEL: char = '\u{7f}';
+
+// Widgets that every keymap binds by default and aren't worth listing
+const IGNORED_WIDGETS: [&str; 4] = [
+    "self-insert",
+    "self-insert-unmeta",
+    "undefined-key",
+    "digit-argument",
+];
+
+pub(super) fn ensure_terminal_app(app_id: &str) -> Result<(), StorageError> {
+    if TERMINAL_APP_IDS.contains(&app_id) {
+        Ok(())
+    } else {
+        Err(StorageError::validation(format!(
+            "{app_id} is not a terminal application"
+        )))
+    }
+}
+
+pub fn import_shell_bindings(path: &Path, app_id: &str) -> Result<ImportSummary, StorageError> {
+    ensure_terminal_app(app_id)?;
+    let contents = std::fs::read_to_string(path).map_err(|e| StorageError::io(path, e))?;
+    let (shortcuts, skipped) = parse_shell_bindings(&contents);
+    save_imported_list(app_id, LIST_NAME, shortcuts, skipped)
+}
+
+// Accepts inputrc lines (`"\C-a": beginning-of-line`, `Control-u: ...`), bash
+// `bind '"\C-a": ...'` lines and zsh lines (`bindkey "^A" beginning-of-line`
+// or bare `"^A" ...` as printed by `bindkey`) in the same file. Anything else,
+// such as the rest of a .bashrc/.zshrc, is ignored. Returns the shortcuts plus
+// the number of bindings whose keys couldn't be decoded.
+pub fn parse_shell_bindings(contents: &str) -> (Vec<ImportedShortcut>, usize) {
+    let mut shortcuts = Vec::new();
+    let mut skipped = 0;
+    for line in contents.lines() {
+        let line = line.trim();
+        // `$if`/`set` are readline directives; `#` starts a comment in both
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with('$')
+            || line.starts_with("set ")
+        {
+            continue;
+        }
+        let parsed = if line.starts_with("bindkey ") {
+            parse_bindkey_line(line)
+        } else if line.starts_with("bind ") {
+            parse_bind_line(line)
+        } else if let Some(zsh) = parse_bindkey_output_line(line) {
+            Some(zsh)
+        } else {
+            parse_inputrc_line(line)
+        };
+        let Some((keys, description)) = parsed else {
+            continue;
+        };
+        match keys {
+            Some(key_combo) => shortcuts.push(ImportedShortcut {
+                key_combo,
+                description,
+            }),
+            None => skipped += 1,
+        }
+    }
+    (shortcuts, skipped)
+}
+
+// `bindkey [-M keymap] [-s] "^X^E" edit-command-line`
+fn parse_bindkey_line(line: &str) -> Option<(Option<String>, String)> {
+    let words = shell_words(line);
+    let mut args = words.iter().skip(1).peekable();
+    let mut macro_binding = false;
+    while let Some(arg) = args.peek() {
+        match arg.as_str() {
+            "-M" => {
+                args.next();
+                args.next();
+            }
+            "-s" => {
+                macro_binding = true;
+                args.next();
+            }
+            flag if flag.starts_with('-') && flag.len() > 1 => {
+                args.next();
+            }
+            _ => break,
+        }
+    }
+    let keys = args.next()?;
+    let action = args.next()?;
+    let action = if macro_binding {
+        format!("\"{action}\"")
+    } else {
+        action.clone()
+    };
+    described(decode_sequence(&decode_zsh(keys)), &action)
+}
+
+// bash `bind [-m keymap] [-x] '"\C-x": kill-line'`. With -x the binding runs a
+// shell command. Other options (-f file, -q, -p, ...) don't bind anything.
+fn parse_bind_line(line: &str) -> Option<(Option<String>, String)> {
+    let words = shell_words(line);
+    let mut args = words.iter().skip(1);
+    let mut shell_command = false;
+    let spec = loop {
+        match args.next()?.as_str() {
+            "-m" => {
+                args.next();
+            }
+            "-x" => shell_command = true,
+            flag if flag.starts_with('-') => return None,
+            spec => break spec,
+        }
+    };
+    if !shell_command {
+        return parse_inputrc_line(spec);
+    }
+    let (keys, command) = split_quoted_keys(spec)?;
+    let command = command.trim_matches(|c| c == '"' || c == '\'');
+    Some((keys, format!("Run `{command}`")))
+}
+
+// `"^A" beginning-of-line`, as printed by running `bindkey` with no arguments.
+// Ranges such as `"^@"-"^?" self-insert` are skipped.
+fn parse_bindkey_output_line(line: &str) -> Option<(Option<String>, String)> {
+    let rest = line.strip_prefix('"')?;
+    let end = closing_quote(rest, '"')?;
+    let after = rest[end + 1..].trim_start();
+    if after.starts_with('-') || after.starts_with(':') || after.is_empty() {
+        return None;
+    }
+    described(decode_sequence(&decode_zsh(&rest[..end])), after)
+}
+
+// `"\C-x\C-r": re-read-init-file`, `Control-u: unix-line-discard`,
+// `"\ep": "macro text"`. Lines whose key is neither quoted nor a readline key
+// name (`export PATH=/a:/b`, `zstyle ':completion:*' ...`) aren't bindings.
+fn parse_inputrc_line(line: &str) -> Option<(Option<String>, String)> {
+    let (keys, action) = match split_quoted_keys(line) {
+        Some(quoted) => quoted,
+        None => {
+            let (key, action) = line.split_once(':')?;
+            (Some(convert_key_name(key.trim())?), action)
+        }
+    };
+    let action = action.trim();
+    if action.is_empty() {
+        return None;
+    }
+    described(keys, action)
+}
+
+// `"\C-x\C-r": action` → the decoded keys and the text after the colon
+fn split_quoted_keys(line: &str) -> Option<(Option<String>, &str)> {
+    let rest = line.strip_prefix('"')?;
+    let end = closing_quote(rest, '"')?;
+    let action = rest[end + 1..].trim_start().strip_prefix(':')?;
+    Some((
+        decode_sequence(&decode_escapes(&rest[..end], false)),
+        action.trim(),
+    ))
+}
+
+// The keys and description of a binding, or None for widgets not worth listing
+fn described(keys: Option<String>, action: &str) -> Option<(Option<String>, String)> {
+    if IGNORED_WIDGETS.contains(&action) {
+        return None;
+    }
+    Some((keys, describe_action(action)))
+}
+
+// Readline's `Control-u` / `Meta-Rubout` / `C-M-x` spelling. The key has to be
+// a single character or one of readline's key names.
+fn convert_key_name(name: &str) -> Option<String> {
+    let mut stroke = KeyStroke::default();
+    let mut rest = name;
+    loop {
+        let lower = rest.to_ascii_lowercase();
+        let (modifier, len) = if lower.starts_with("control-") {
+            ("ctrl", 8)
+        } else if lower.starts_with("meta-") {
+            ("alt", 5)
+        } else if lower.starts_with("c-") && rest.len() > 2 {
+            ("ctrl", 2)
+        } else if lower.starts_with("m-") && rest.len() > 2 {
+            ("alt", 2)
+        } else {
+            break;
+        };
+        stroke.add_modifier(modifier);
+        rest = &rest[len..];
+    }
+    let key = match rest.to_ascii_lowercase().as_str() {
+        "rubout" | "del" => "Backspace",
+        "newline" | "ret" | "return" => "Enter",
+        "lfd" => "Enter",
+        "spc" | "space" => "Space",
+        "esc" | "escape" => "Esc",
+        "tab" => "Tab",
+        _ if rest.chars().count() == 1 && !rest.starts_with(char::is_whitespace) => rest,
+        _ => return None,
+    };
+    if key.chars().count() == 1 && key.chars().all(|c| c.is_ascii_uppercase()) {
+        stroke.add_modifier("shift");
+    }
+    stroke.set_key(key);
+    Some(stroke.to_combo_string())
+}
+
+fn describe_action(action: &str) -> String {
+    let action = action.trim();
+    if let Some(text) = action
+        .strip_prefix('"')
+        .and_then(|a| a.strip_suffix('"'))
+        .or_else(|| action.strip_prefix('\'').and_then(|a| a.strip_suffix('\'')))
+    {
+        let text = text
+            .trim_end_matches("\\n")
+            .trim_end_matches("^M")
+            .trim_end_matches("\\r");
+        return format!("Type \"{text}\"");
+    }
+    humanize_identifier(action)
+}
+
+fn closing_quote(s: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return Some(i);
+        }
+    }
+    None
+}
+
+// Split a shell command line into words, honouring quotes. Backslash escapes
+// are kept so key strings can be decoded afterwards.
+fn shell_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some('"') if c == '\\' => {
+                current.push(c);
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            Some(_) => current.push(c),
+            None if c == '\'' || c == '"' => {
+                quote = Some(c);
+                in_word = true;
+            }
+            None if c == '#' && !in_word => break,
+            None if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_word = true;
+                if c == '\\' {
+                    if let Some(next) = chars.next() {
+                        current.push(next);
+                    }
+                }
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    words
+}
+
+// zsh caret notation ("^X^E", "^[b", "^?") plus backslash escapes
+fn decode_zsh(keys: &str) -> Vec<char> {
+    decode_escapes(keys, true)
+}
+
+// Decode readline/zsh escapes into the raw characters a terminal sends
+fn decode_escapes(keys: &str, caret: bool) -> Vec<char> {
+    let mut out = Vec::new();
+    let chars: Vec<char> = keys.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if caret && c == '^' && i + 1 < chars.len() {
+            out.push(control_char(chars[i + 1]));
+            i += 2;
+            continue;
+        }
+        if c != '\\' || i + 1 >= chars.len() {
+            out.push(c);
+            i += 1;
+            continue;
+        }
+        let next = chars[i + 1];
+        let has_prefix_arg = chars.get(i + 2) == Some(&'-') && i + 3 < chars.len();
+        match next {
+            'C' if has_prefix_arg => {
+                out.push(control_char(chars[i + 3]));
+                i += 4;
+            }
+            'M' if has_prefix_arg => {
+                out.push(ESC);
+                // "\M-\C-x"
+                if chars[i + 3] == '\\' && chars.get(i + 4) == Some(&'C') && i + 6 < chars.len() {
+                    out.push(control_char(chars[i + 6]));
+                    i += 7;
+                } else {
+                    out.push(chars[i + 3]);
+                    i += 4;
+                }
+            }
+            'e' | 'E' => {
+                out.push(ESC);
+                i += 2;
+            }
+            't' => {
+                out.push('\t');
+                i += 2;
+            }
+            'n' => {
+                out.push('\n');
+                i += 2;
+            }
+            'r' => {
+                out.push('\r');
+                i += 2;
+            }
+            'd' => {
+                out.push(DEL);
+                i += 2;
+            }
+            'x' => {
+                let hex: String = chars[i + 2..]
+                    .iter()
+                    .take(2)
+                    .take_while(|c| c.is_ascii_hexdigit())
+                    .collect();
+                match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    Some(decoded) => out.push(decoded),
+                    None => out.push('x'),
+                }
+                i += 2 + hex.len();
+            }
+            '0'..='7' => {
+                let octal: String = chars[i + 1..]
+                    .iter()
+                    .take(3)
+                    .take_while(|c| c.is_digit(8))
+                    .collect();
+                if let Some(decoded) = u32::from_str_radix(&octal, 8).ok().and_then(char::from_u32)
+                {
+                    out.push(decoded);
+                }
+                i += 1 + octal.len();
+            }
+            other => {
+                out.push(other);
+                i += 2;
+            }
+        }
+    }
+    out
+}
+
+fn control_char(c: char) -> char {
+    if c == '?' {
+        return DEL;
+    }
+    let upper = c.to_ascii_uppercase();
+    if ('@'..='_').contains(&upper) {
+        char::from_u32(upper as u32 - '@' as u32).unwrap_or(c)
+    } else {
+        c
+    }
+}
+
+// Turn the raw characters of a binding into "Ctrl+X, Ctrl+E" notation.
+// Returns None for sequences we can't name (e.g. unknown CSI codes).
+fn decode_sequence(raw: &[char]) -> Option<String> {
+    let mut strokes = Vec::new();
+    let mut i = 0;
+    while i < raw.len() {
+        let c = raw[i];
+        if c == ESC {
+            match raw.get(i + 1) {
+                None => {
+                    strokes.push(named_stroke("Esc"));
+                    i += 1;
+                }
+                Some('[') | Some('O') if i + 2 < raw.len() => {
+                    let (stroke, len) = decode_csi(&raw[i + 1..])?;
+                    strokes.push(stroke);
+                    i += 1 + len;
+                }
+                // ESC prefix is how terminals send Meta/Alt
+                Some(&next) => {
+                    let mut stroke = char_stroke(next)?;
+                    stroke.add_modifier("alt");
+                    strokes.push(stroke);
+                    i += 2;
+                }
+            }
+        } else {
+            strokes.push(char_stroke(c)?);
+            i += 1;
+        }
+    }
+    (!strokes.is_empty()).then(|| join_sequence(&strokes))
+}
+
+fn named_stroke(key: &str) -> KeyStroke {
+    let mut stroke = KeyStroke::default();
+    stroke.set_key(key);
+    stroke
+}
+
+fn char_stroke(c: char) -> Option<KeyStroke> {
+    let mut stroke = KeyStroke::default();
+    match c {
+        '\t' => stroke.set_key("Tab"),
+        '\r' | '\n' => stroke.set_key("Enter"),
+        ' ' => stroke.set_key("Space"),
+        ESC => stroke.set_key("Esc"),
+        DEL => stroke.set_key("Backspace"),
+        '\u{0}' => {
+            stroke.add_modifier("ctrl");
+            stroke.set_key("Space");
+        }
+        c if (c as u32) < 0x20 => {
+            stroke.add_modifier("ctrl");
+            let base = char::from_u32(c as u32 + '@' as u32)?;
+            stroke.set_key(&base.to_string());
+        }
+        c if c.is_ascii_uppercase() => {
+            stroke.add_modifier("shift");
+            stroke.set_key(&c.to_string());
+        }
+        c => stroke.set_key(&c.to_string()),
+    }
+    Some(stroke)
+}
+
+// `[A`, `[1;5C`, `[3~`, `OP` (after the ESC). Returns the stroke and the
+// number of characters consumed.
+fn decode_csi(seq: &[char]) -> Option<(KeyStroke, usize)> {
+    let introducer = seq[0];
+    let mut end = 1;
+    while end < seq.len() && (seq[end].is_ascii_digit() || seq[end] == ';') {
+        end += 1;
+    }
+    let final_char = *seq.get(end)?;
+    let params: String = seq[1..end].iter().collect();
+    let mut params = params.split(';').map(|p| p.parse::<u32>().ok());
+    let first = params.next().flatten();
+    let modifier = params.next().flatten();
+
+    let key = match (introducer, final_char) {
+        (_, 'A') => "Up",
+        (_, 'B') => "Down",
+        (_, 'C') => "Right",
+        (_, 'D') => "Left",
+        (_, 'H') => "Home",
+        (_, 'F') => "End",
+        ('[', 'Z') => "BackTab",
+        ('O', 'P') => "F1",
+        ('O', 'Q') => "F2",
+        ('O', 'R') => "F3",
+        ('O', 'S') => "F4",
+        ('[', '~') => match first? {
+            1 | 7 => "Home",
+            2 => "Insert",
+            3 => "Delete",
+            4 | 8 => "End",
+            5 => "PageUp",
+            6 => "PageDown",
+            15 => "F5",
+            17 => "F6",
+            18 => "F7",
+            19 => "F8",
+            20 => "F9",
+            21 => "F10",
+            23 => "F11",
+            24 => "F12",
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let mut stroke = KeyStroke::default();
+    if key == "BackTab" {
+        stroke.add_modifier("shift");
+        stroke.set_key("Tab");
+    } else {
+        stroke.set_key(key);
+    }
+    // xterm modifier parameter: 1 + (shift=1 | alt=2 | ctrl=4)
+    if let Some(bits) = modifier.map(|m| m.saturating_sub(1)) {
+        if bits & 1 != 0 {
+            stroke.add_modifier("shift");
+        }
+        if bits & 2 != 0 {
+            stroke.add_modifier("alt");
+        }
+        if bits & 4 != 0 {
+            stroke.add_modifier("ctrl");
+        }
+    }
+    Some((stroke, end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(contents: &str) -> Vec<(String, String)> {
+        parse_shell_bindings(contents)
+            .0
+            .into_iter()
+            .map(|s| (s.key_combo, s.description))
+            .collect()
+    }
+
+    fn pair(keys: &str, description: &str) -> (String, String) {
+        (keys.to_string(), description.to_string())
+    }
+
+    #[test]
+    fn zsh_bindkey_escape_sequences() {
+        let zshrc = r#"
+bindkey '^[b' backward-word
+bindkey "\e[1;5C" forward-word
+bindkey -M viins '^X^E' edit-command-line
+bindkey -s '^Xg' 'git status\n'
+"^A" beginning-of-line
+"^@"-"^?" self-insert
+"#;
+        assert_eq!(
+            parsed(zshrc),
+            vec![
+                pair("Alt+B", "Backward word"),
+                pair("Ctrl+→", "Forward word"),
+                pair("Ctrl+X, Ctrl+E", "Edit command line"),
+                pair("Ctrl+X, G", "Type \"git status\""),
+                pair("Ctrl+A", "Beginning of line"),
+            ]
+        );
+    }
+
+    #[test]
+    fn bash_bind_lines() {
+        let bashrc = r#"
+bind '"\C-x\C-e": edit-and-execute-command'
+bind -m emacs '"\ep": history-search-backward'
+bind -x '"\C-t": fzf-file-widget'
+bind -f ~/.inputrc
+"#;
+        assert_eq!(
+            parsed(bashrc),
+            vec![
+                pair("Ctrl+X, Ctrl+E", "Edit and execute command"),
+                pair("Alt+P", "History search backward"),
+                pair("Ctrl+T", "Run `fzf-file-widget`"),
+            ]
+        );
+    }
+
+    #[test]
+    fn inputrc_key_names_and_sequences() {
+        let inputrc = r#"
+$if mode=emacs
+set completion-ignore-case on
+Control-a: beginning-of-line
+Meta-Rubout: backward-kill-word
+C-M-x: "echo hi"
+"\C-x\C-r": re-read-init-file
+$endif
+"#;
+        assert_eq!(
+            parsed(inputrc),
+            vec![
+                pair("Ctrl+A", "Beginning of line"),
+                pair("Alt+Backspace", "Backward kill word"),
+                pair("Ctrl+Alt+X", "Type \"echo hi\""),
+                pair("Ctrl+X, Ctrl+R", "Re read init file"),
+            ]
+        );
+    }
+
+    #[test]
+    fn other_shell_lines_are_not_bindings() {
+        let zshrc = r#"
+zstyle ':completion:*' menu select
+zstyle ':completion:*:descriptions' format '%B%d%b'
+export PATH=/usr/local/bin:/usr/bin
+PATH=$HOME/bin:$PATH
+alias x='a:b'
+if [[ -n $TMUX ]]; then echo "tmux: yes"; fi
+"#;
+        assert_eq!(parse_shell_bindings(zshrc), (Vec::new(), 0));
+    }
+}
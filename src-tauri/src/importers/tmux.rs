@@ -0,0 +1,364 @@
+// Import tmux key bindings from a .tmux.conf into a "tmux" list for one of the
+// terminal applications.
+//
+// Bindings in the prefix table are recorded as a two-step sequence starting
+// with the configured prefix (`set -g prefix C-a`, default `C-b`), so
+// `bind | split-window -h` becomes "Ctrl+A, |". Root-table bindings (`-n`)
+// have no prefix; copy-mode bindings are tagged "(copy mode)".
+
+use super::shell::ensure_terminal_app;
+use super::{humanize_identifier, save_imported_list, ImportSummary, ImportedShortcut, KeyStroke};
+use crate::storage::StorageError;
+use std::path::Path;
+
+pub const LIST_NAME: &str = "tmux";
+
+const DEFAULT_PREFIX: &str = "C-b";
+
+// Flags whose following word is a value rather than a command argument
+const VALUE_FLAGS: [&str; 10] = ["-t", "-c", "-s", "-F", "-x", "-y", "-e", "-n", "-l", "-p"];
+
+// The words of a set-option command after its flags. -t (the target session
+// or window) takes a value, also at the end of a cluster such as "-gt".
+fn option_args(words: &[String]) -> impl Iterator<Item = &String> {
+    let mut words = words.iter().peekable();
+    while let Some(flag) = words.next_if(|w| w.len() > 1 && w.starts_with('-')) {
+        if flag.ends_with('t') {
+            words.next();
+        }
+    }
+    words
+}
+
+struct Binding {
+    table: String,
+    key: String,
+    note: Option<String>,
+    comment: Option<String>,
+    // Command words, with ";" separating chained commands
+    command: Vec<String>,
+}
+
+pub fn import_tmux_config(path: &Path, app_id: &str) -> Result<ImportSummary, StorageError> {
+    ensure_terminal_app(app_id)?;
+    let contents = std::fs::read_to_string(path).map_err(|e| StorageError::io(path, e))?;
+    let (shortcuts, skipped) = parse_tmux_config(&contents);
+    save_imported_list(app_id, LIST_NAME, shortcuts, skipped)
+}
+
+// Returns the shortcuts plus the number of bindings that couldn't be
+// converted (unknown key names or custom key tables).
+pub fn parse_tmux_config(contents: &str) -> (Vec<ImportedShortcut>, usize) {
+    let mut prefix = DEFAULT_PREFIX.to_string();
+    let mut bindings = Vec::new();
+
+    for (words, comment) in logical_lines(contents) {
+        let Some(command) = words.first() else {
+            continue;
+        };
+        match command.as_str() {
+            "set" | "set-option" => {
+                let mut args = option_args(&words[1..]);
+                if args.next().map(String::as_str) == Some("prefix") {
+                    if let Some(value) = args.next() {
+                        prefix = value.clone();
+                    }
+                }
+            }
+            "bind" | "bind-key" => {
+                if let Some(binding) = parse_bind(&words[1..], comment) {
+                    bindings.push(binding);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // The prefix applies to every prefix-table binding, wherever it was set
+    let prefix_combo = convert_key(&prefix).filter(|_| prefix != "None");
+    let mut shortcuts = Vec::new();
+    let mut skipped = 0;
+    for binding in bindings {
+        let Some(key) = convert_key(&binding.key) else {
+            skipped += 1;
+            continue;
+        };
+        let (key_combo, suffix) = match binding.table.as_str() {
+            "prefix" => match &prefix_combo {
+                Some(prefix) => (format!("{prefix}, {key}"), ""),
+                None => (key, ""),
+            },
+            "root" => (key, ""),
+            "copy-mode" | "copy-mode-vi" => (key, " (copy mode)"),
+            _ => {
+                skipped += 1;
+                continue;
+            }
+        };
+        let description = binding
+            .note
+            .or(binding.comment)
+            .unwrap_or_else(|| describe_command(&binding.command));
+        shortcuts.push(ImportedShortcut {
+            key_combo,
+            description: format!("{description}{suffix}"),
+        });
+    }
+    (shortcuts, skipped)
+}
+
+// `bind-key [-nr] [-N note] [-T table] key command [arguments]`
+fn parse_bind(args: &[String], comment: Option<String>) -> Option<Binding> {
+    let mut table = "prefix".to_string();
+    let mut note = None;
+    let mut i = 0;
+    while i < args.len() && args[i].starts_with('-') && args[i].len() > 1 {
+        let flags = &args[i][1..];
+        for (pos, flag) in flags.char_indices() {
+            match flag {
+                'n' => table = "root".to_string(),
+                'T' | 'N' => {
+                    // The value may be attached ("-Tcopy-mode") or the next word
+                    let attached = &flags[pos + 1..];
+                    let value = if attached.is_empty() {
+                        i += 1;
+                        args.get(i)?.clone()
+                    } else {
+                        attached.to_string()
+                    };
+                    if flag == 'T' {
+                        table = value;
+                    } else {
+                        note = Some(value);
+                    }
+                    break;
+                }
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+    let key = args.get(i)?.clone();
+    Some(Binding {
+        table,
+        key,
+        note,
+        comment,
+        command: args[i + 1..].to_vec(),
+    })
+}
+
+// "split-window -h -c '#{pane_current_path}'" → "Split window horizontally"
+fn describe_command(words: &[String]) -> String {
+    let described: Vec<String> = words
+        .split(|w| w == ";")
+        .filter(|cmd| !cmd.is_empty())
+        .map(|cmd| {
+            let mut parts = vec![humanize_identifier(&cmd[0])];
+            let mut args = cmd[1..].iter();
+            while let Some(arg) = args.next() {
+                let word = match arg.as_str() {
+                    "-h" => "horizontally",
+                    "-v" => "vertically",
+                    "-L" => "left",
+                    "-R" => "right",
+                    "-U" => "up",
+                    "-D" => "down",
+                    flag if VALUE_FLAGS.contains(&flag) => {
+                        args.next();
+                        continue;
+                    }
+                    flag if flag.starts_with('-') => continue,
+                    other if other.contains("#{") => continue,
+                    other => other,
+                };
+                parts.push(word.to_string());
+            }
+            parts.join(" ")
+        })
+        .collect();
+    if described.is_empty() {
+        "Unbound".to_string()
+    } else {
+        described.join(", then ")
+    }
+}
+
+// "C-a", "M-Left", "S-F5", "^a", "BSpace", "|", "R"
+fn convert_key(key: &str) -> Option<String> {
+    let mut stroke = KeyStroke::default();
+    let mut rest = key;
+    if let Some(after) = rest.strip_prefix('^').filter(|r| !r.is_empty()) {
+        stroke.add_modifier("ctrl");
+        rest = after;
+    }
+    while rest.len() > 2 {
+        // get() rather than slicing: byte 2 may fall inside a character ("€")
+        let modifier = match rest.get(..2) {
+            Some("C-") => "ctrl",
+            Some("M-") => "alt",
+            Some("S-") => "shift",
+            _ => break,
+        };
+        stroke.add_modifier(modifier);
+        rest = &rest[2..];
+    }
+    let name = match rest {
+        "" => return None,
+        "BSpace" => "Backspace",
+        "BTab" => {
+            stroke.add_modifier("shift");
+            "Tab"
+        }
+        "DC" => "Delete",
+        "IC" => "Insert",
+        "NPage" | "PgDn" => "PageDown",
+        "PPage" | "PgUp" => "PageUp",
+        "Escape" => "Esc",
+        other => other,
+    };
+    // Multi-character names we don't recognise (e.g. "MouseDown1Pane")
+    if name.chars().count() > 1 && super::key_label(name) == name && !is_known_name(name) {
+        return None;
+    }
+    if name.chars().count() == 1 && name.chars().all(|c| c.is_ascii_uppercase()) {
+        stroke.add_modifier("shift");
+    }
+    stroke.set_key(name);
+    Some(stroke.to_combo_string())
+}
+
+fn is_known_name(name: &str) -> bool {
+    let function_key =
+        name.len() > 1 && name.starts_with('F') && name[1..].chars().all(|c| c.is_ascii_digit());
+    function_key
+        || matches!(
+            name,
+            "Backspace"
+                | "Delete"
+                | "Insert"
+                | "Home"
+                | "End"
+                | "Enter"
+                | "Space"
+                | "Tab"
+                | "PageUp"
+                | "PageDown"
+        )
+}
+
+// Split the config into commands (joining `\` continuations and dropping
+// comments), returning each command's words plus any same-line comment.
+fn logical_lines(contents: &str) -> Vec<(Vec<String>, Option<String>)> {
+    let mut result = Vec::new();
+    let mut pending = String::new();
+    for line in contents.lines() {
+        if let Some(continued) = line.strip_suffix('\\').filter(|l| !l.ends_with('\\')) {
+            pending.push_str(continued);
+            pending.push(' ');
+            continue;
+        }
+        pending.push_str(line);
+        let (words, comment) = split_words(&pending);
+        if !words.is_empty() {
+            result.push((words, comment));
+        }
+        pending.clear();
+    }
+    result
+}
+
+// tmux-style word splitting: quotes, backslash escapes, `\;` / trailing `;`
+// as command separators and `#` comments outside quotes.
+fn split_words(line: &str) -> (Vec<String>, Option<String>) {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some('"') if c == '\\' => {
+                if let Some((_, next)) = chars.next() {
+                    current.push(next);
+                }
+            }
+            Some(_) => current.push(c),
+            None => match c {
+                '\'' | '"' => {
+                    quote = Some(c);
+                    in_word = true;
+                }
+                '#' if !in_word => {
+                    let comment = line[i + 1..].trim_start_matches('#').trim();
+                    let comment = (!comment.is_empty()).then(|| comment.to_string());
+                    return (words, comment);
+                }
+                '\\' => {
+                    if let Some((_, next)) = chars.next() {
+                        // A lone `\;` separates chained commands
+                        if next == ';' && !in_word {
+                            words.push(";".to_string());
+                            continue;
+                        }
+                        current.push(next);
+                        in_word = true;
+                    }
+                }
+                c if c.is_whitespace() => {
+                    if in_word {
+                        words.push(std::mem::take(&mut current));
+                        in_word = false;
+                    }
+                }
+                c => {
+                    current.push(c);
+                    in_word = true;
+                }
+            },
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    (words, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combos(config: &str) -> Vec<String> {
+        parse_tmux_config(config)
+            .0
+            .into_iter()
+            .map(|s| s.key_combo)
+            .collect()
+    }
+
+    #[test]
+    fn multibyte_keys_do_not_panic() {
+        assert_eq!(convert_key("€").as_deref(), Some("€"));
+        assert_eq!(convert_key("C-é").as_deref(), Some("Ctrl+É"));
+        assert_eq!(combos("bind € new-window"), vec!["Ctrl+B, €"]);
+    }
+
+    #[test]
+    fn set_prefix_skips_flag_values() {
+        for config in [
+            "set -g prefix C-a",
+            "set-option -t sess prefix C-a",
+            "set -gt sess prefix C-a",
+            "set -g -t sess prefix C-a",
+        ] {
+            let config = format!("{config}\nbind c new-window");
+            assert_eq!(combos(&config), vec!["Ctrl+A, C"], "{config}");
+        }
+        // Another option targeted at a session named "prefix"
+        assert_eq!(
+            combos("set -t prefix status off\nbind c new-window"),
+            vec!["Ctrl+B, C"]
+        );
+    }
+}
@@ -0,0 +1,791 @@
+// Import Vim/Neovim mappings into mode-grouped lists for the Vim application.
+//
+// Supports `[nvxsoilct]map`/`[...]noremap` lines in a vimrc and single- or
+// multi-line `vim.keymap.set(...)` / `vim.api.nvim_set_keymap(...)` calls in
+// an init.lua. Descriptions come from (in order) a trailing comment, a `desc`
+// option, a comment on the line above, or the mapping's right-hand side.
+// `<leader>` becomes the key set by `mapleader` earlier in the file.
+
+use super::{save_imported_list, ImportSummary, ImportedShortcut, KeyStroke};
+use crate::storage::StorageError;
+use std::path::Path;
+
+pub const VIM_APP_ID: &str = "app-vim";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Mode {
+    Normal,
+    Visual,
+    Select,
+    OperatorPending,
+    Insert,
+    CommandLine,
+    Terminal,
+}
+
+impl Mode {
+    pub fn list_name(self) -> &'static str {
+        match self {
+            Mode::Normal => "Normal mode",
+            Mode::Visual => "Visual mode",
+            Mode::Select => "Select mode",
+            Mode::OperatorPending => "Operator-pending mode",
+            Mode::Insert => "Insert mode",
+            Mode::CommandLine => "Command-line mode",
+            Mode::Terminal => "Terminal mode",
+        }
+    }
+
+    // Mode letter as used in `nmap` or `vim.keymap.set('n', ...)`. An empty
+    // prefix (`map`/`noremap`) applies to normal, visual and operator-pending
+    // mode; it's filed under Normal.
+    fn from_letter(letter: &str) -> Option<Mode> {
+        match letter {
+            "" | "n" => Some(Mode::Normal),
+            "v" | "x" => Some(Mode::Visual),
+            "s" => Some(Mode::Select),
+            "o" => Some(Mode::OperatorPending),
+            "i" | "l" => Some(Mode::Insert),
+            "c" => Some(Mode::CommandLine),
+            "t" => Some(Mode::Terminal),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping {
+    pub mode: Mode,
+    pub shortcut: ImportedShortcut,
+}
+
+// Keys `<leader>` and `<localleader>` stand for, once the file assigns them
+#[derive(Debug, Default, Clone)]
+pub struct Leaders {
+    pub leader: Option<String>,
+    pub local_leader: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct ParsedMappings {
+    pub mappings: Vec<Mapping>,
+    // Mappings that can't be typed directly (e.g. <Plug> targets), per mode
+    pub skipped: Vec<Mode>,
+}
+
+// Import a vimrc or init.lua (chosen by file extension)
+pub fn import_mappings(path: &Path) -> Result<Vec<ImportSummary>, StorageError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| StorageError::io(path, e))?;
+    let parsed = if path.extension().is_some_and(|ext| ext == "lua") {
+        parse_init_lua(&contents)
+    } else {
+        parse_vimrc(&contents)
+    };
+    if parsed.mappings.is_empty() {
+        return Err(StorageError::validation(format!(
+            "No key mappings found in {}",
+            path.display()
+        )));
+    }
+
+    let mut modes: Vec<Mode> = parsed.mappings.iter().map(|m| m.mode).collect();
+    modes.sort();
+    modes.dedup();
+
+    modes
+        .into_iter()
+        .map(|mode| {
+            let shortcuts = parsed
+                .mappings
+                .iter()
+                .filter(|m| m.mode == mode)
+                .map(|m| m.shortcut.clone())
+                .collect();
+            let skipped = parsed.skipped.iter().filter(|m| **m == mode).count();
+            save_imported_list(VIM_APP_ID, mode.list_name(), shortcuts, skipped)
+        })
+        .collect()
+}
+
+pub fn parse_vimrc(contents: &str) -> ParsedMappings {
+    let mut parsed = ParsedMappings::default();
+    let mut leaders = Leaders::default();
+    let mut comment_above: Option<String> = None;
+
+    for raw_line in contents.lines() {
+        let line = raw_line.trim();
+        if let Some(comment) = line.strip_prefix('"') {
+            comment_above = Some(comment.trim().to_string());
+            continue;
+        }
+
+        if let Some((name, value)) = parse_let_leader(line) {
+            leaders.set(name, &value);
+        } else if let Some((mode, lhs, rhs, trailing)) = parse_map_command(line) {
+            let description = trailing
+                .or(comment_above.take())
+                .filter(|d| !d.is_empty())
+                .unwrap_or_else(|| describe_rhs(&rhs));
+            push_mapping(&mut parsed, &leaders, mode, &lhs, &description);
+        }
+        comment_above = None;
+    }
+    parsed
+}
+
+fn push_mapping(
+    parsed: &mut ParsedMappings,
+    leaders: &Leaders,
+    mode: Mode,
+    lhs: &str,
+    description: &str,
+) {
+    match convert_lhs(lhs, leaders) {
+        Some(key_combo) => parsed.mappings.push(Mapping {
+            mode,
+            shortcut: ImportedShortcut {
+                key_combo,
+                description: description.to_string(),
+            },
+        }),
+        None => parsed.skipped.push(mode),
+    }
+}
+
+impl Leaders {
+    // Record `mapleader`/`maplocalleader` (with or without a `g:`/`vim.g.`
+    // prefix). The value is written in key notation: " ", ",", "\<Space>".
+    fn set(&mut self, name: &str, value: &str) {
+        let value = value.replace("\\<", "<").replace("\\\\", "\\");
+        let key = convert_lhs(&value, &Leaders::default());
+        match name {
+            "mapleader" => self.leader = key,
+            "maplocalleader" => self.local_leader = key,
+            _ => {}
+        }
+    }
+}
+
+// `let mapleader = ","` → ("mapleader", ",")
+fn parse_let_leader(line: &str) -> Option<(&str, String)> {
+    let (name, value) = line.strip_prefix("let ")?.split_once('=')?;
+    let name = name.trim();
+    let name = name.strip_prefix("g:").unwrap_or(name);
+    if name != "mapleader" && name != "maplocalleader" {
+        return None;
+    }
+    let value = value.trim();
+    let quote = value.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+    let inner = value[1..].split(quote).next()?;
+    Some((name, inner.to_string()))
+}
+
+// Split a `nnoremap <silent> lhs rhs " comment` line into its parts
+fn parse_map_command(line: &str) -> Option<(Mode, String, String, Option<String>)> {
+    let (command, rest) = line.split_once(char::is_whitespace)?;
+    let command = command.trim_end_matches('!');
+    let prefix = command
+        .strip_suffix("noremap")
+        .or_else(|| command.strip_suffix("map"))?;
+    let mode = Mode::from_letter(prefix)?;
+
+    let mut rest = rest.trim_start();
+    while let Some(option) = MAP_OPTIONS
+        .iter()
+        .find(|o| rest.to_ascii_lowercase().starts_with(*o))
+    {
+        rest = rest[option.len()..].trim_start();
+    }
+
+    let (lhs, rhs) = rest.split_once(char::is_whitespace)?;
+    let (rhs, trailing) = split_trailing_comment(rhs.trim());
+    Some((mode, lhs.to_string(), rhs, trailing))
+}
+
+const MAP_OPTIONS: [&str; 7] = [
+    "<silent>",
+    "<buffer>",
+    "<expr>",
+    "<nowait>",
+    "<unique>",
+    "<script>",
+    "<special>",
+];
+
+// Vimscript treats `"` after a mapping as part of the RHS, but dotfiles
+// commonly use `| " comment` or a trailing `" comment` as documentation.
+fn split_trailing_comment(rhs: &str) -> (String, Option<String>) {
+    if let Some((before, comment)) = rhs.split_once("| \"") {
+        return (before.trim().to_string(), Some(comment.trim().to_string()));
+    }
+    if let Some(index) = rhs.rfind(" \"") {
+        let comment = &rhs[index + 2..];
+        if !comment.contains('"') {
+            return (
+                rhs[..index].trim().to_string(),
+                Some(comment.trim().to_string()),
+            );
+        }
+    }
+    (rhs.to_string(), None)
+}
+
+// Use the right-hand side as a description: ":w<CR>" → ":w"
+fn describe_rhs(rhs: &str) -> String {
+    let mut text = rhs.trim().to_string();
+    for prefix in ["<cmd>", "<Cmd>", "<CMD>"] {
+        if let Some(rest) = text.strip_prefix(prefix) {
+            text = format!(":{rest}");
+        }
+    }
+    for suffix in ["<CR>", "<cr>", "<Cr>"] {
+        if let Some(rest) = text.strip_suffix(suffix) {
+            text = rest.to_string();
+        }
+    }
+    // "<Plug>(coc-definition)" → "coc-definition"
+    if let Some(plug) = text.strip_prefix("<Plug>") {
+        text = plug
+            .trim_start_matches('(')
+            .trim_end_matches(')')
+            .to_string();
+    }
+    text.replace(":<C-u>", ":").replace(":<c-u>", ":")
+}
+
+// Convert Vim key notation ("<leader>ff", "<C-s>", "gD") into the app's
+// key_combo format ("Leader, F, F", "Ctrl+S", "G, Shift+D"). `<leader>` is
+// replaced by the assigned leader key when there is one. Returns None for
+// mappings that aren't typed by the user, such as <Plug> targets.
+pub fn convert_lhs(lhs: &str, leaders: &Leaders) -> Option<String> {
+    let mut strokes: Vec<String> = Vec::new();
+    let chars: Vec<char> = lhs.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] == '<' {
+            if let Some(len) = chars[i..].iter().position(|c| *c == '>') {
+                let name: String = chars[i + 1..i + len].iter().collect();
+                if !name.is_empty() {
+                    strokes.push(convert_special(&name, leaders)?);
+                    i += len + 1;
+                    continue;
+                }
+            }
+        }
+        strokes.push(convert_char(chars[i]));
+        i += 1;
+    }
+
+    if strokes.is_empty() {
+        None
+    } else {
+        Some(strokes.join(", "))
+    }
+}
+
+fn convert_char(c: char) -> String {
+    if c.is_ascii_uppercase() {
+        format!("Shift+{c}")
+    } else if c == ' ' {
+        "Space".to_string()
+    } else {
+        c.to_uppercase().to_string()
+    }
+}
+
+// "<C-S-x>", "<leader>", "<CR>" → app notation
+fn convert_special(name: &str, leaders: &Leaders) -> Option<String> {
+    let lower = name.to_ascii_lowercase();
+    match lower.as_str() {
+        "plug" | "sid" | "nop" => return None,
+        "leader" => {
+            return Some(
+                leaders
+                    .leader
+                    .clone()
+                    .unwrap_or_else(|| "Leader".to_string()),
+            )
+        }
+        "localleader" => {
+            return Some(
+                leaders
+                    .local_leader
+                    .clone()
+                    .unwrap_or_else(|| "LocalLeader".to_string()),
+            )
+        }
+        "bar" => return Some("|".to_string()),
+        "lt" => return Some("<".to_string()),
+        "bslash" => return Some("\\".to_string()),
+        _ => {}
+    }
+    if lower.starts_with("plug>") {
+        return None;
+    }
+
+    let mut stroke = KeyStroke::default();
+    let mut parts: Vec<&str> = name.split('-').collect();
+    // "<C-->" is Ctrl+minus
+    if name.ends_with("--") {
+        parts.retain(|p| !p.is_empty());
+        parts.push("-");
+    }
+    let key = parts.pop()?;
+    for modifier in parts {
+        match modifier.to_ascii_lowercase().as_str() {
+            "c" => stroke.add_modifier("ctrl"),
+            "s" => stroke.add_modifier("shift"),
+            "m" | "a" => stroke.add_modifier("alt"),
+            "d" => stroke.add_modifier("cmd"),
+            _ => return Some(format!("<{name}>")),
+        };
+    }
+    stroke.set_key(match key.to_ascii_lowercase().as_str() {
+        "cr" | "return" => "enter",
+        "bs" => "backspace",
+        "kplus" => "+",
+        "kminus" => "-",
+        _ => key,
+    });
+    Some(stroke.to_combo_string())
+}
+
+// ---------------------------------------------------------------------------
+// init.lua
+// ---------------------------------------------------------------------------
+
+// A Lua argument we understand: a string literal, a table, or anything else
+#[derive(Debug, Clone, PartialEq)]
+enum LuaArg {
+    Str(String),
+    Table(String),
+    Other(String),
+}
+
+pub fn parse_init_lua(contents: &str) -> ParsedMappings {
+    let mut parsed = ParsedMappings::default();
+    let mut leaders = Leaders::default();
+    let mut comment_above: Option<String> = None;
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i].trim();
+        i += 1;
+        if let Some(comment) = line.strip_prefix("--") {
+            comment_above = Some(comment.trim().to_string());
+            continue;
+        }
+        if let Some((name, value)) = parse_lua_leader(line) {
+            leaders.set(name, &value);
+            comment_above = None;
+            continue;
+        }
+
+        let Some(start) = ["vim.keymap.set(", "vim.api.nvim_set_keymap("]
+            .iter()
+            .find_map(|call| line.find(call).map(|pos| pos + call.len()))
+        else {
+            comment_above = None;
+            continue;
+        };
+
+        // Gather lines until the call's parentheses balance
+        let mut call = line[start..].to_string();
+        while !parens_balanced(&call) && i < lines.len() {
+            call.push('\n');
+            call.push_str(lines[i]);
+            i += 1;
+        }
+        let (args, trailing) = split_lua_args(&call);
+
+        let modes: Vec<Mode> = match args.first() {
+            Some(LuaArg::Str(m)) => Mode::from_letter(m).into_iter().collect(),
+            Some(LuaArg::Table(t)) => lua_strings(t)
+                .iter()
+                .filter_map(|m| Mode::from_letter(m))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let Some(LuaArg::Str(lhs)) = args.get(1) else {
+            comment_above = None;
+            continue;
+        };
+        let desc = args.get(3).and_then(|opts| match opts {
+            LuaArg::Table(t) => lua_field(t, "desc"),
+            _ => None,
+        });
+        let rhs = match args.get(2) {
+            Some(LuaArg::Str(rhs)) => describe_rhs(rhs),
+            Some(LuaArg::Other(other)) | Some(LuaArg::Table(other)) => describe_lua_rhs(other),
+            None => String::new(),
+        };
+
+        let description = trailing
+            .or(desc)
+            .or(comment_above.take())
+            .filter(|d| !d.is_empty())
+            .unwrap_or(rhs);
+        for mode in modes {
+            push_mapping(&mut parsed, &leaders, mode, lhs, &description);
+        }
+        comment_above = None;
+    }
+    parsed
+}
+
+// `vim.g.mapleader = " "` → ("mapleader", " ")
+fn parse_lua_leader(line: &str) -> Option<(&str, String)> {
+    let (name, value) = line.strip_prefix("vim.g.")?.split_once('=')?;
+    let name = name.trim();
+    if name != "mapleader" && name != "maplocalleader" {
+        return None;
+    }
+    let value = split_top_level(value).into_iter().next()?;
+    Some((name, lua_string_literal(&value)?))
+}
+
+// A function or expression RHS: keep short references such as
+// `builtin.find_files`, but don't turn a whole function body into a description
+fn describe_lua_rhs(rhs: &str) -> String {
+    let is_function = rhs.starts_with("function") && !rhs[8..].starts_with(is_identifier_char);
+    if is_function || rhs.contains('\n') || rhs.chars().count() > 60 {
+        "Lua function".to_string()
+    } else {
+        rhs.to_string()
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+// Walks Lua source outside string literals and `--` comments, calling `f`
+// with each significant character and its byte index (including the first
+// `-` of a comment). Stops when `f` returns false.
+fn scan_lua(text: &str, mut f: impl FnMut(usize, char) -> bool) {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut in_comment = false;
+    for (index, c) in text.char_indices() {
+        if in_comment {
+            in_comment = c != '\n';
+            continue;
+        }
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' => quote = Some(c),
+            '-' if text[index..].starts_with("--") => in_comment = true,
+            _ => {}
+        }
+        if !f(index, c) {
+            return;
+        }
+    }
+}
+
+// Byte index just past the `)` that closes a call, given the text after its `(`
+fn call_end(text: &str) -> Option<usize> {
+    let mut depth = 0i32;
+    let mut end = None;
+    scan_lua(text, |index, c| {
+        match c {
+            '(' | '{' => depth += 1,
+            '}' => depth -= 1,
+            ')' if depth == 0 => {
+                end = Some(index + 1);
+                return false;
+            }
+            ')' => depth -= 1,
+            _ => {}
+        }
+        true
+    });
+    end
+}
+
+fn parens_balanced(text: &str) -> bool {
+    call_end(text).is_some()
+}
+
+// Split on commas that aren't nested in a table, call, block or string
+fn split_top_level(text: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    let mut previous = ' ';
+    scan_lua(text, |index, c| {
+        let word_start = !is_identifier_char(previous);
+        previous = c;
+        match c {
+            '(' | '{' => depth += 1,
+            ')' | '}' => depth -= 1,
+            // `function ... end` bodies (and the blocks inside them) can
+            // hold top-level commas: `local a, b = ...`
+            c if word_start && c.is_ascii_alphabetic() => {
+                let word: String = text[index..]
+                    .chars()
+                    .take_while(|c| is_identifier_char(*c))
+                    .collect();
+                match word.as_str() {
+                    "function" | "if" | "do" | "repeat" => depth += 1,
+                    "end" | "until" => depth -= 1,
+                    _ => {}
+                }
+            }
+            ',' if depth == 0 => {
+                parts.push(text[start..index].to_string());
+                start = index + 1;
+            }
+            _ => {}
+        }
+        true
+    });
+    parts.push(text[start..].to_string());
+    parts
+        .into_iter()
+        .map(|p| strip_lua_comments(&p).trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+fn strip_lua_comments(text: &str) -> String {
+    let mut out = String::new();
+    let mut last = 0;
+    let mut comment_starts = Vec::new();
+    scan_lua(text, |index, c| {
+        if c == '-' && text[index..].starts_with("--") {
+            comment_starts.push(index);
+        }
+        true
+    });
+    // scan_lua skips comment bodies, so each start is a real comment
+    for start in comment_starts {
+        if start < last {
+            continue;
+        }
+        out.push_str(&text[last..start]);
+        last = text[start..].find('\n').map_or(text.len(), |n| start + n);
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+// Split the text after `vim.keymap.set(` into its arguments. Also returns a
+// `-- comment` following the closing parenthesis.
+fn split_lua_args(call: &str) -> (Vec<LuaArg>, Option<String>) {
+    let end = call_end(call).unwrap_or(call.len() + 1);
+    let inner = &call[..end - 1];
+    let args = split_top_level(inner)
+        .iter()
+        .map(|a| classify_lua_arg(a))
+        .collect();
+    let trailing = call
+        .get(end..)
+        .and_then(|rest| rest.trim().strip_prefix("--"))
+        .map(|c| c.trim().to_string());
+    (args, trailing)
+}
+
+fn classify_lua_arg(arg: &str) -> LuaArg {
+    if let Some(s) = lua_string_literal(arg) {
+        LuaArg::Str(s)
+    } else if arg.starts_with('{') {
+        LuaArg::Table(arg.to_string())
+    } else {
+        LuaArg::Other(arg.to_string())
+    }
+}
+
+fn lua_string_literal(arg: &str) -> Option<String> {
+    let quote = arg.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+    let inner = arg.strip_prefix(quote)?.strip_suffix(quote)?;
+    Some(
+        inner
+            .replace(&format!("\\{quote}"), &quote.to_string())
+            .replace("\\\\", "\\"),
+    )
+}
+
+// Every string literal in a table such as `{ 'n', 'v' }`
+fn lua_strings(table: &str) -> Vec<String> {
+    split_top_level(table_inner(table))
+        .iter()
+        .filter_map(|item| lua_string_literal(item))
+        .collect()
+}
+
+// The string value of `key = "..."` in an options table
+fn lua_field(table: &str, key: &str) -> Option<String> {
+    split_top_level(table_inner(table)).iter().find_map(|item| {
+        let (name, value) = item.split_once('=')?;
+        if name.trim() == key {
+            lua_string_literal(value.trim())
+        } else {
+            None
+        }
+    })
+}
+
+fn table_inner(table: &str) -> &str {
+    let table = table.trim();
+    table
+        .strip_prefix('{')
+        .and_then(|t| t.strip_suffix('}'))
+        .unwrap_or(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combos(parsed: &ParsedMappings) -> Vec<(Mode, &str, &str)> {
+        parsed
+            .mappings
+            .iter()
+            .map(|m| {
+                (
+                    m.mode,
+                    m.shortcut.key_combo.as_str(),
+                    m.shortcut.description.as_str(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn leader_uses_the_assigned_mapleader() {
+        let parsed = parse_vimrc(
+            "nnoremap <leader>w :w<CR>\n\
+             let mapleader = \",\"\n\
+             let g:maplocalleader = \"\\<Space>\"\n\
+             nnoremap <leader>q :q<CR>\n\
+             nnoremap <localleader>t :TestNearest<CR>\n",
+        );
+        assert_eq!(
+            combos(&parsed),
+            vec![
+                (Mode::Normal, "Leader, W", ":w"),
+                (Mode::Normal, ",, Q", ":q"),
+                (Mode::Normal, "Space, T", ":TestNearest"),
+            ]
+        );
+
+        let parsed = parse_init_lua(
+            "vim.g.mapleader = ' '\n\
+             vim.keymap.set('n', '<leader>ff', '<cmd>Telescope find_files<cr>')\n",
+        );
+        assert_eq!(
+            combos(&parsed),
+            vec![(Mode::Normal, "Space, F, F", ":Telescope find_files")]
+        );
+    }
+
+    #[test]
+    fn multi_key_sequences() {
+        let leaders = Leaders::default();
+        assert_eq!(
+            convert_lhs("<C-w>h", &leaders).as_deref(),
+            Some("Ctrl+W, H")
+        );
+        assert_eq!(convert_lhs("gD", &leaders).as_deref(), Some("G, Shift+D"));
+        assert_eq!(
+            convert_lhs("<C-S-x><CR>", &leaders).as_deref(),
+            Some("Ctrl+Shift+X, Enter")
+        );
+        assert_eq!(convert_lhs("<C-->", &leaders).as_deref(), Some("Ctrl+-"));
+    }
+
+    #[test]
+    fn map_commands_choose_the_mode() {
+        let parsed = parse_vimrc(
+            "noremap H ^\n\
+             nnoremap <C-w>h <C-w>h\n\
+             vnoremap < <gv\n\
+             xmap ga <Plug>(EasyAlign)\n\
+             inoremap jk <Esc>\n\
+             cnoremap <C-a> <Home>\n\
+             tnoremap <Esc> <C-\\><C-n>\n\
+             nmap! x y\n\
+             set number\n",
+        );
+        let modes: Vec<(Mode, &str)> = parsed
+            .mappings
+            .iter()
+            .map(|m| (m.mode, m.shortcut.key_combo.as_str()))
+            .collect();
+        assert_eq!(
+            modes,
+            vec![
+                (Mode::Normal, "Shift+H"),
+                (Mode::Normal, "Ctrl+W, H"),
+                (Mode::Visual, "<"),
+                (Mode::Visual, "G, A"),
+                (Mode::Insert, "J, K"),
+                (Mode::CommandLine, "Ctrl+A"),
+                (Mode::Terminal, "Esc"),
+                (Mode::Normal, "X"),
+            ]
+        );
+    }
+
+    #[test]
+    fn plug_targets_are_skipped_and_options_stripped() {
+        let parsed = parse_vimrc(
+            "\" Go to definition\n\
+             nmap <silent> gd <Plug>(coc-definition)\n\
+             nmap <silent><buffer> <Plug>(my-thing) :echo<CR>\n\
+             nnoremap <nowait> <expr> <leader>n v:count ? 'n' : 'N'\n",
+        );
+        assert_eq!(
+            combos(&parsed),
+            vec![
+                (Mode::Normal, "G, D", "Go to definition"),
+                (Mode::Normal, "Leader, N", "v:count ? 'n' : 'N'"),
+            ]
+        );
+        assert_eq!(parsed.skipped, vec![Mode::Normal]);
+
+        let parsed = parse_vimrc("nmap gr <Plug>(coc-references)\n");
+        assert_eq!(
+            combos(&parsed),
+            vec![(Mode::Normal, "G, R", "coc-references")]
+        );
+    }
+
+    #[test]
+    fn lua_descriptions() {
+        let parsed = parse_init_lua(
+            "vim.keymap.set('n', '<leader>f', function()\n\
+               local ok, builtin = pcall(require, 'telescope.builtin')\n\
+               if ok then builtin.find_files({ hidden = true }) end\n\
+             end, { desc = 'Find files', silent = true })\n\
+             vim.keymap.set({ 'n', 'v' }, '<leader>y', function() vim.cmd('normal! \"+y') end)\n\
+             vim.keymap.set('n', '<leader>g', builtin.live_grep)\n\
+             -- Save\n\
+             vim.api.nvim_set_keymap('n', '<C-s>', ':w<CR>', { noremap = true })\n\
+             vim.keymap.set('n', '<leader>x', '<cmd>bd<cr>', { desc = 'Close' }) -- Close buffer\n",
+        );
+        assert_eq!(
+            combos(&parsed),
+            vec![
+                (Mode::Normal, "Leader, F", "Find files"),
+                (Mode::Normal, "Leader, Y", "Lua function"),
+                (Mode::Visual, "Leader, Y", "Lua function"),
+                (Mode::Normal, "Leader, G", "builtin.live_grep"),
+                (Mode::Normal, "Ctrl+S", "Save"),
+                (Mode::Normal, "Leader, X", "Close buffer"),
+            ]
+        );
+    }
+}
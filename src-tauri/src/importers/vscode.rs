@@ -0,0 +1,269 @@
+// Import a VS Code `keybindings.json` (JSONC: comments and trailing commas
+// allowed) into a "My Keybindings" list for the VS Code application.
+
+use super::{humanize_identifier, save_imported_list, ImportSummary, ImportedShortcut, KeyStroke};
+use crate::defaults::VSCODE_APP_ID;
+use crate::storage::StorageError;
+use serde::Deserialize;
+use std::path::Path;
+
+pub const LIST_NAME: &str = "My Keybindings";
+
+#[derive(Debug, Deserialize)]
+struct Keybinding {
+    key: String,
+    command: String,
+    #[serde(default)]
+    when: Option<String>,
+}
+
+pub fn import_keybindings(path: &Path) -> Result<ImportSummary, StorageError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| StorageError::io(path, e))?;
+    let (shortcuts, skipped) =
+        parse_keybindings(&contents).map_err(|e| StorageError::parse(path, e))?;
+    save_imported_list(VSCODE_APP_ID, LIST_NAME, shortcuts, skipped)
+}
+
+// Parse keybindings.json contents. Returns the shortcuts plus the number of
+// entries skipped. An unbind entry (`"command": "-editor.action..."`) is
+// skipped and also removes the matching binding made earlier in the file.
+pub fn parse_keybindings(
+    contents: &str,
+) -> Result<(Vec<ImportedShortcut>, usize), serde_json::Error> {
+    let bindings: Vec<Keybinding> = serde_json::from_str(&strip_jsonc(contents))?;
+    // Each shortcut with the command that produced it, for unbind entries
+    let mut shortcuts: Vec<(String, ImportedShortcut)> = Vec::new();
+    let mut skipped = 0;
+
+    for binding in bindings {
+        let key_combo = convert_key(&binding.key);
+        if let Some(command) = binding.command.strip_prefix('-') {
+            shortcuts.retain(|(bound, shortcut)| {
+                bound != command || !(key_combo.is_empty() || shortcut.key_combo == key_combo)
+            });
+            skipped += 1;
+            continue;
+        }
+        if binding.command.is_empty() || key_combo.is_empty() {
+            skipped += 1;
+            continue;
+        }
+        // Many editor commands end in "Action" (moveLinesUpAction), which
+        // adds nothing to the description
+        let mut description = humanize_identifier(&binding.command);
+        if let Some(trimmed) = description.strip_suffix(" action") {
+            description = trimmed.to_string();
+        }
+        if let Some(when) = binding.when.filter(|w| !w.trim().is_empty()) {
+            description = format!("{description} (when {when})");
+        }
+        shortcuts.push((
+            binding.command,
+            ImportedShortcut {
+                key_combo,
+                description,
+            },
+        ));
+    }
+    let shortcuts = shortcuts
+        .into_iter()
+        .map(|(_, shortcut)| shortcut)
+        .collect();
+    Ok((shortcuts, skipped))
+}
+
+// "ctrl+k ctrl+s" → "Ctrl+K, Ctrl+S"
+fn convert_key(key: &str) -> String {
+    let strokes: Vec<KeyStroke> = key
+        .split_whitespace()
+        .map(|chord| {
+            let mut stroke = KeyStroke::default();
+            // A trailing "+" is the plus key itself, e.g. "ctrl++"
+            let (mods, last) = match chord.strip_suffix("++") {
+                Some(rest) => (rest, "+"),
+                None => match chord.rsplit_once('+') {
+                    Some((rest, last)) => (rest, last),
+                    None => ("", chord),
+                },
+            };
+            for part in mods.split('+').filter(|p| !p.is_empty()) {
+                if !stroke.add_modifier(part) {
+                    stroke.set_key(part);
+                }
+            }
+            if !stroke.add_modifier(last) {
+                stroke.set_key(last);
+            }
+            stroke
+        })
+        .filter(|s| !s.is_empty())
+        .collect();
+    super::join_sequence(&strokes)
+}
+
+// Turn JSONC into plain JSON: comments are blanked out (newlines kept so
+// parse errors still report the right line) and trailing commas removed.
+fn strip_jsonc(input: &str) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+    let mut in_string = false;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if in_string {
+            out.push(c);
+            if c == '\\' && i + 1 < chars.len() {
+                out.push(chars[i + 1]);
+                i += 1;
+            } else if c == '"' {
+                in_string = false;
+            }
+            i += 1;
+            continue;
+        }
+
+        match (c, chars.get(i + 1)) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+                i += 1;
+            }
+            ('/', Some('/')) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    out.push(' ');
+                    i += 1;
+                }
+            }
+            ('/', Some('*')) => {
+                out.push_str("  ");
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    out.push(if chars[i] == '\n' { '\n' } else { ' ' });
+                    i += 1;
+                }
+                out.push_str("  ");
+                i += 2;
+            }
+            (',', _) => {
+                // Drop the comma if the next significant character closes a
+                // container
+                let next = next_significant(&chars, i + 1);
+                out.push(if matches!(next, Some(']') | Some('}')) {
+                    ' '
+                } else {
+                    ','
+                });
+                i += 1;
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+// The next character at or after `i` that isn't whitespace or a comment
+fn next_significant(chars: &[char], mut i: usize) -> Option<char> {
+    while i < chars.len() {
+        match (chars[i], chars.get(i + 1)) {
+            (c, _) if c.is_whitespace() => i += 1,
+            ('/', Some('/')) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            ('/', Some('*')) => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            (c, _) => return Some(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(contents: &str) -> (Vec<(String, String)>, usize) {
+        let (shortcuts, skipped) = parse_keybindings(contents).unwrap();
+        let pairs = shortcuts
+            .into_iter()
+            .map(|s| (s.key_combo, s.description))
+            .collect();
+        (pairs, skipped)
+    }
+
+    #[test]
+    fn strips_comments_and_trailing_commas() {
+        let json = strip_jsonc(
+            "// Place your key bindings in this file\n\
+             [\n\
+               /* block\n comment */ { \"key\": \"ctrl+a\", \"command\": \"a\", },\n\
+               { \"key\": \"ctrl+b\", \"command\": \"b\" }, // last\n\
+             ]\n",
+        );
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!([
+                { "key": "ctrl+a", "command": "a" },
+                { "key": "ctrl+b", "command": "b" }
+            ])
+        );
+        // Blanked-out comments keep line numbers intact
+        assert_eq!(json.lines().count(), 6);
+    }
+
+    #[test]
+    fn comment_markers_inside_strings_are_kept() {
+        let json = strip_jsonc(
+            r#"[{ "key": "ctrl+o", "command": "http://example.com/*x*/", "when": "a, }" }]"#,
+        );
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value[0]["command"], "http://example.com/*x*/");
+        assert_eq!(value[0]["when"], "a, }");
+    }
+
+    #[test]
+    fn unbind_entries_remove_the_earlier_binding() {
+        let (shortcuts, skipped) = parse(
+            r#"[
+                { "key": "ctrl+d", "command": "editor.action.copyLinesDownAction" },
+                { "key": "ctrl+shift+d", "command": "editor.action.copyLinesDownAction" },
+                { "key": "ctrl+e", "command": "workbench.action.quickOpen" },
+                { "key": "ctrl+d", "command": "-editor.action.copyLinesDownAction" },
+                { "key": "", "command": "-workbench.action.quickOpen" },
+                { "key": "ctrl+u", "command": "-cursorUndo" },
+            ]"#,
+        );
+        assert_eq!(
+            shortcuts,
+            vec![("Ctrl+Shift+D".to_string(), "Copy lines down".to_string())]
+        );
+        assert_eq!(skipped, 3);
+    }
+
+    #[test]
+    fn converts_keys() {
+        let cases = [
+            ("ctrl+k ctrl+s", "Ctrl+K, Ctrl+S"),
+            ("ctrl++", "Ctrl++"),
+            ("ctrl+shift+=", "Ctrl+Shift+="),
+            ("cmd+shift+p", "Shift+Cmd+P"),
+            ("alt+up", "Alt+↑"),
+            ("escape", "Esc"),
+            ("ctrl+k  v", "Ctrl+K, V"),
+        ];
+        for (key, expected) in cases {
+            assert_eq!(convert_key(key), expected, "{key}");
+        }
+    }
+}
@@ -0,0 +1,617 @@
+// Parsing and canonical formatting of key combinations.
+//
+// `Shortcut.key_combo` is free text, so "ctrl+shift+p", "Shift+Ctrl+P" and
+// "⌃⇧P" can all mean the same thing. `KeyCombo` parses any of those spellings
+// and formats them the way the key capture input writes them: modifiers in
+// Ctrl, Shift, Alt, Win, Cmd order, named keys like "Esc"/"Space", arrows as
+// ↑↓←→ and multi-step chords joined with ", " ("Ctrl+K, Ctrl+S").
+//
+// Comparisons (seeding de-duplication, imports, conflict detection) should go
+// through `normalize` so equivalent spellings match.
+//
+// Lists shared between platforms can use the "CmdOrCtrl" token, which
+// `render` shows as Cmd on macOS and Ctrl elsewhere.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Modifier {
+    // Cmd on macOS, Ctrl everywhere else
+    Primary,
+    Ctrl,
+    Shift,
+    Alt,
+    Win,
+    Cmd,
+}
+
+impl Modifier {
+    // Any common spelling or macOS symbol of a modifier
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "cmdorctrl" | "commandorcontrol" | "mod" | "primary" => Some(Modifier::Primary),
+            "ctrl" | "control" | "ctl" | "⌃" => Some(Modifier::Ctrl),
+            "shift" | "⇧" => Some(Modifier::Shift),
+            "alt" | "option" | "opt" | "⌥" => Some(Modifier::Alt),
+            "win" | "windows" | "super" | "meta" => Some(Modifier::Win),
+            "cmd" | "command" | "⌘" => Some(Modifier::Cmd),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Modifier::Primary => "CmdOrCtrl",
+            Modifier::Ctrl => "Ctrl",
+            Modifier::Shift => "Shift",
+            Modifier::Alt => "Alt",
+            Modifier::Win => "Win",
+            Modifier::Cmd => "Cmd",
+        }
+    }
+
+    // Label as shown to a user of `platform`
+    fn platform_label(self, platform: Platform) -> &'static str {
+        match (self, platform) {
+            (Modifier::Primary, Platform::Macos) => "Cmd",
+            (Modifier::Primary, _) => "Ctrl",
+            (Modifier::Alt, Platform::Macos) => "Option",
+            (Modifier::Win, Platform::Macos) => "Cmd",
+            (Modifier::Win, Platform::Linux) => "Super",
+            (Modifier::Cmd, Platform::Windows) => "Win",
+            (Modifier::Cmd, Platform::Linux) => "Super",
+            (modifier, _) => modifier.label(),
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Modifier::Ctrl => "⌃",
+            Modifier::Alt => "⌥",
+            Modifier::Shift => "⇧",
+            Modifier::Primary | Modifier::Win | Modifier::Cmd => "⌘",
+        }
+    }
+
+    // Apple's conventional order: ⌃⌥⇧⌘
+    fn mac_rank(self) -> u8 {
+        match self {
+            Modifier::Ctrl => 0,
+            Modifier::Alt => 1,
+            Modifier::Shift => 2,
+            Modifier::Primary | Modifier::Win | Modifier::Cmd => 3,
+        }
+    }
+
+    fn from_symbol(c: char) -> Option<Self> {
+        match c {
+            '⌃' => Some(Modifier::Ctrl),
+            '⇧' => Some(Modifier::Shift),
+            '⌥' => Some(Modifier::Alt),
+            '⌘' => Some(Modifier::Cmd),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Platform {
+    Windows,
+    Macos,
+    Linux,
+}
+
+impl Platform {
+    pub fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            Platform::Macos
+        } else if cfg!(target_os = "windows") {
+            Platform::Windows
+        } else {
+            Platform::Linux
+        }
+    }
+}
+
+// How `render` spells modifiers and keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyStyle {
+    // "Cmd+Shift+P"
+    #[default]
+    Text,
+    // "⌘⇧P" on macOS; other platforms have no modifier glyphs and get text
+    Symbols,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum KeyComboError {
+    #[error("Key combination is empty")]
+    Empty,
+    #[error("\"{step}\" has no key after its modifiers")]
+    MissingKey { step: String },
+    #[error("\"{name}\" is not a modifier")]
+    UnknownModifier { name: String },
+    #[error("\"{key}\" is not a key name")]
+    UnknownKey { key: String },
+}
+
+// One step of a combination: modifiers (sorted, no duplicates) plus a key
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub modifiers: Vec<Modifier>,
+    pub key: String,
+}
+
+// One or more chords pressed in sequence
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyCombo {
+    pub steps: Vec<KeyChord>,
+}
+
+impl KeyChord {
+    pub fn new(modifiers: impl IntoIterator<Item = Modifier>, key: &str) -> Self {
+        let mut modifiers: Vec<Modifier> = modifiers.into_iter().collect();
+        modifiers.sort();
+        modifiers.dedup();
+        KeyChord {
+            modifiers,
+            key: canonical_key(key),
+        }
+    }
+
+    fn parse(step: &str) -> Result<Self, KeyComboError> {
+        // Leading macOS symbols: "⌘⇧P", "⌃⌥Delete"
+        let mut modifiers = Vec::new();
+        let mut rest = step;
+        while let Some(c) = rest.chars().next() {
+            match Modifier::from_symbol(c) {
+                Some(modifier) if rest.len() > c.len_utf8() => {
+                    modifiers.push(modifier);
+                    rest = rest[c.len_utf8()..].trim_start_matches('+');
+                }
+                _ => break,
+            }
+        }
+
+        // A trailing "+" after a separator is the plus key: "Ctrl++"
+        let (names, key) = if rest == "+" {
+            ("", "+")
+        } else if let Some(names) = rest.strip_suffix("++") {
+            (names, "+")
+        } else {
+            match rest.rsplit_once('+') {
+                Some((names, key)) => (names, key),
+                None => ("", rest),
+            }
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(KeyComboError::MissingKey {
+                step: step.to_string(),
+            });
+        }
+        // Without modifiers, an unknown word is more likely prose ("Click and
+        // drag") than a key; with them ("Ctrl+Click") anything goes
+        if names.trim().is_empty() && modifiers.is_empty() && !is_key_name(key) {
+            return Err(KeyComboError::UnknownKey {
+                key: key.to_string(),
+            });
+        }
+        for name in names.split('+').filter(|n| !n.trim().is_empty()) {
+            let modifier =
+                Modifier::from_name(name).ok_or_else(|| KeyComboError::UnknownModifier {
+                    name: name.trim().to_string(),
+                })?;
+            modifiers.push(modifier);
+        }
+        Ok(KeyChord::new(modifiers, key))
+    }
+}
+
+impl KeyCombo {
+    pub fn parse(text: &str) -> Result<Self, KeyComboError> {
+        let steps = split_steps(text)
+            .iter()
+            .map(|step| KeyChord::parse(step))
+            .collect::<Result<Vec<_>, _>>()?;
+        if steps.is_empty() {
+            return Err(KeyComboError::Empty);
+        }
+        Ok(KeyCombo { steps })
+    }
+
+    // Replace CmdOrCtrl with the modifier `platform` actually uses
+    pub fn resolve(&self, platform: Platform) -> KeyCombo {
+        let primary = match platform {
+            Platform::Macos => Modifier::Cmd,
+            _ => Modifier::Ctrl,
+        };
+        KeyCombo {
+            steps: self
+                .steps
+                .iter()
+                .map(|step| {
+                    let mut modifiers: Vec<Modifier> = step
+                        .modifiers
+                        .iter()
+                        .map(|&m| if m == Modifier::Primary { primary } else { m })
+                        .collect();
+                    modifiers.sort();
+                    modifiers.dedup();
+                    KeyChord {
+                        modifiers,
+                        key: step.key.clone(),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    // Display form for `platform`, e.g. "Cmd+Shift+P" or "⌘⇧P"
+    pub fn render(&self, platform: Platform, style: KeyStyle) -> String {
+        let symbols = style == KeyStyle::Symbols && platform == Platform::Macos;
+        self.resolve(platform)
+            .steps
+            .iter()
+            .map(|step| {
+                let mut modifiers = step.modifiers.clone();
+                if platform == Platform::Macos {
+                    modifiers.sort_by_key(|m| m.mac_rank());
+                }
+                if symbols {
+                    let glyphs: String = modifiers.iter().map(|m| m.symbol()).collect();
+                    glyphs + key_symbol(&step.key)
+                } else {
+                    let mut parts: Vec<&str> = modifiers
+                        .iter()
+                        .map(|m| m.platform_label(platform))
+                        .collect();
+                    parts.push(&step.key);
+                    parts.join("+")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    // Some lists give alternatives in one entry ("F5 or Ctrl+R")
+    pub fn parse_alternatives(text: &str) -> Result<Vec<Self>, KeyComboError> {
+        text.split(" or ").map(KeyCombo::parse).collect()
+    }
+}
+
+impl FromStr for KeyCombo {
+    type Err = KeyComboError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        KeyCombo::parse(s)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for modifier in &self.modifiers {
+            write!(f, "{}+", modifier.label())?;
+        }
+        f.write_str(&self.key)
+    }
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{step}")?;
+        }
+        Ok(())
+    }
+}
+
+// Canonical spelling of `key_combo`, or the trimmed input if it can't be
+// parsed (so free-text entries still compare equal to themselves)
+pub fn normalize(key_combo: &str) -> String {
+    match KeyCombo::parse_alternatives(key_combo) {
+        Ok(alternatives) => alternatives
+            .iter()
+            .map(KeyCombo::to_string)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        Err(_) => key_combo.trim().to_string(),
+    }
+}
+
+// Canonical spelling with CmdOrCtrl resolved for `platform`, for comparing
+// platform-neutral combos against ones recorded on a specific OS
+pub fn normalize_for(key_combo: &str, platform: Platform) -> String {
+    match KeyCombo::parse_alternatives(key_combo) {
+        Ok(alternatives) => alternatives
+            .iter()
+            .map(|combo| combo.resolve(platform).to_string())
+            .collect::<Vec<_>>()
+            .join(" or "),
+        Err(_) => key_combo.trim().to_string(),
+    }
+}
+
+// Display form of a stored key_combo for `platform`. Text that isn't a key
+// combination is returned unchanged.
+pub fn render(key_combo: &str, platform: Platform, style: KeyStyle) -> String {
+    match KeyCombo::parse_alternatives(key_combo) {
+        Ok(alternatives) => alternatives
+            .iter()
+            .map(|combo| combo.render(platform, style))
+            .collect::<Vec<_>>()
+            .join(" or "),
+        Err(_) => key_combo.to_string(),
+    }
+}
+
+// Whether two key_combo strings describe the same keys
+pub fn same_combo(a: &str, b: &str) -> bool {
+    normalize(a) == normalize(b)
+}
+
+// Split into chord steps on ",", ", " or whitespace ("Ctrl+K Ctrl+S"), while
+// allowing spaces around "+" ("Ctrl + K") and the comma key ("Ctrl+,").
+fn split_steps(text: &str) -> Vec<String> {
+    let mut steps: Vec<String> = Vec::new();
+    for token in text.split_whitespace() {
+        if token == "," {
+            continue;
+        }
+        for piece in split_commas(token) {
+            match steps.last_mut() {
+                Some(previous)
+                    if (previous.ends_with('+') && !previous.ends_with("++"))
+                        || (piece.starts_with('+') && piece != "+") =>
+                {
+                    previous.push_str(piece)
+                }
+                Some(previous) if piece == "+" && !previous.ends_with('+') => previous.push('+'),
+                _ => steps.push(piece.to_string()),
+            }
+        }
+    }
+    steps
+}
+
+// Split a token on the commas that separate steps ("Ctrl+K,Ctrl+S"). A comma
+// that follows a "+" or starts the token is the comma key ("Ctrl+,", ",,").
+fn split_commas(token: &str) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    for (index, c) in token.char_indices() {
+        let piece = &token[start..index];
+        let is_key = piece.is_empty() || (piece.ends_with('+') && !piece.ends_with("++"));
+        if c == ',' && !is_key {
+            pieces.push(piece);
+            start = index + 1;
+        }
+    }
+    if start < token.len() {
+        pieces.push(&token[start..]);
+    }
+    pieces
+}
+
+// Single characters, F-keys and the named keys `canonical_key` knows
+fn is_key_name(raw: &str) -> bool {
+    if raw.chars().count() == 1 {
+        return true;
+    }
+    let canonical = canonical_key(raw);
+    matches!(
+        canonical.as_str(),
+        "↑" | "↓"
+            | "←"
+            | "→"
+            | "Esc"
+            | "Enter"
+            | "Space"
+            | "Tab"
+            | "Backspace"
+            | "Delete"
+            | "Insert"
+            | "Home"
+            | "End"
+            | "PageUp"
+            | "PageDown"
+            | "+"
+            | "-"
+    ) || (canonical.starts_with('F') && canonical[1..].chars().all(|c| c.is_ascii_digit()))
+}
+
+// Map a key name from any source onto the label the app displays
+pub fn canonical_key(raw: &str) -> String {
+    let raw = raw.trim();
+    let lower = raw.to_lowercase();
+    let label = match lower.as_str() {
+        "up" | "arrowup" | "↑" => "↑",
+        "down" | "arrowdown" | "↓" => "↓",
+        "left" | "arrowleft" | "←" => "←",
+        "right" | "arrowright" | "→" => "→",
+        "esc" | "escape" | "⎋" => "Esc",
+        "enter" | "return" | "ret" | "cr" | "↵" | "⏎" | "↩" => "Enter",
+        "space" | "spc" | "spacebar" => "Space",
+        "tab" | "⇥" => "Tab",
+        "backspace" | "bs" | "bksp" | "⌫" => "Backspace",
+        "delete" | "del" | "⌦" => "Delete",
+        "insert" | "ins" => "Insert",
+        "home" => "Home",
+        "end" => "End",
+        "pageup" | "pgup" | "prior" => "PageUp",
+        "pagedown" | "pgdn" | "next" => "PageDown",
+        "plus" => "+",
+        "minus" => "-",
+        _ => "",
+    };
+    if !label.is_empty() {
+        return label.to_string();
+    }
+    if lower.len() > 1 && lower.starts_with('f') && lower[1..].chars().all(|c| c.is_ascii_digit()) {
+        return lower.to_uppercase();
+    }
+    let mut chars = raw.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+// macOS glyphs for named keys; anything else is shown as-is
+fn key_symbol(key: &str) -> &str {
+    match key {
+        "Enter" => "↩",
+        "Esc" => "⎋",
+        "Backspace" => "⌫",
+        "Delete" => "⌦",
+        "Tab" => "⇥",
+        "PageUp" => "⇞",
+        "PageDown" => "⇟",
+        "Home" => "↖",
+        "End" => "↘",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_table() {
+        let cases = [
+            // Modifier order
+            ("Shift+Ctrl+P", "Ctrl+Shift+P"),
+            ("alt+shift+ctrl+k", "Ctrl+Shift+Alt+K"),
+            ("Cmd+Win+Alt+Shift+Ctrl+X", "Ctrl+Shift+Alt+Win+Cmd+X"),
+            ("CmdOrCtrl+Shift+P", "CmdOrCtrl+Shift+P"),
+            // Aliases
+            ("control+s", "Ctrl+S"),
+            ("ctl+s", "Ctrl+S"),
+            ("command+s", "Cmd+S"),
+            ("cmd+s", "Cmd+S"),
+            ("meta+s", "Win+S"),
+            ("super+s", "Win+S"),
+            ("option+s", "Alt+S"),
+            ("mod+s", "CmdOrCtrl+S"),
+            ("⌘⇧P", "Shift+Cmd+P"),
+            ("⌃⌥Delete", "Ctrl+Alt+Delete"),
+            // Duplicate modifiers collapse
+            ("Ctrl+Control+S", "Ctrl+S"),
+            // Key names
+            ("ctrl+escape", "Ctrl+Esc"),
+            ("Alt+ArrowUp", "Alt+↑"),
+            ("shift+f5", "Shift+F5"),
+            ("Ctrl + K", "Ctrl+K"),
+            ("Ctrl++", "Ctrl++"),
+            ("Ctrl+plus", "Ctrl++"),
+            ("Ctrl+,", "Ctrl+,"),
+            // Chords
+            ("Ctrl+K, Ctrl+S", "Ctrl+K, Ctrl+S"),
+            ("ctrl+k ctrl+s", "Ctrl+K, Ctrl+S"),
+            ("Ctrl+K,Ctrl+S", "Ctrl+K, Ctrl+S"),
+            ("Ctrl+K,S", "Ctrl+K, S"),
+            ("Ctrl+K, Ctrl+S,", "Ctrl+K, Ctrl+S"),
+            ("Ctrl+,,Ctrl+S", "Ctrl+,, Ctrl+S"),
+            ("Ctrl++,K", "Ctrl++, K"),
+            (",, W", ",, W"),
+            ("Ctrl+W, S", "Ctrl+W, S"),
+            // Alternatives
+            ("F5 or ctrl+r", "F5 or Ctrl+R"),
+            // Not a combination: kept as typed (trimmed)
+            ("  Click and drag ", "Click and drag"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(normalize(input), expected, "normalize({input:?})");
+        }
+    }
+
+    #[test]
+    fn cmd_or_ctrl_per_platform() {
+        let cases = [
+            (Platform::Macos, "Shift+Cmd+P", "⇧⌘P"),
+            (Platform::Windows, "Ctrl+Shift+P", "Ctrl+Shift+P"),
+            (Platform::Linux, "Ctrl+Shift+P", "Ctrl+Shift+P"),
+        ];
+        for (platform, text, symbols) in cases {
+            assert_eq!(render("CmdOrCtrl+Shift+P", platform, KeyStyle::Text), text);
+            assert_eq!(
+                render("CmdOrCtrl+Shift+P", platform, KeyStyle::Symbols),
+                symbols
+            );
+        }
+        assert_eq!(normalize_for("CmdOrCtrl+C", Platform::Macos), "Cmd+C");
+        assert_eq!(normalize_for("CmdOrCtrl+C", Platform::Windows), "Ctrl+C");
+        // Already having the resolved modifier doesn't duplicate it
+        assert_eq!(normalize_for("CmdOrCtrl+Ctrl+C", Platform::Linux), "Ctrl+C");
+        assert_eq!(
+            render("Alt+Win+K", Platform::Macos, KeyStyle::Text),
+            "Option+Cmd+K"
+        );
+        assert_eq!(render("Win+K", Platform::Linux, KeyStyle::Text), "Super+K");
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        let cases = [
+            ("", KeyComboError::Empty),
+            ("   ", KeyComboError::Empty),
+            (
+                "Ctrl+",
+                KeyComboError::MissingKey {
+                    step: "Ctrl+".to_string(),
+                },
+            ),
+            (
+                "Hyper+K",
+                KeyComboError::UnknownModifier {
+                    name: "Hyper".to_string(),
+                },
+            ),
+            (
+                "Click",
+                KeyComboError::UnknownKey {
+                    key: "Click".to_string(),
+                },
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(KeyCombo::parse(input), Err(expected), "parse({input:?})");
+        }
+    }
+
+    #[test]
+    fn same_combo_round_trips() {
+        let equivalent = [
+            ("ctrl+shift+p", "Shift+Ctrl+P"),
+            ("Control+K Control+S", "Ctrl+K, Ctrl+S"),
+            ("⌘⇧P", "Shift+Cmd+P"),
+            ("Alt+Up", "alt+ArrowUp"),
+            ("F5 or Ctrl+R", "f5 or control+r"),
+            ("Click and drag", " Click and drag"),
+        ];
+        for (a, b) in equivalent {
+            assert!(same_combo(a, b), "{a:?} == {b:?}");
+            // The canonical form parses back to itself
+            assert!(same_combo(&normalize(a), a), "normalize({a:?}) round trip");
+            assert_eq!(normalize(&normalize(a)), normalize(a));
+        }
+        let different = [
+            ("Ctrl+K", "Ctrl+Shift+K"),
+            ("Ctrl+K, Ctrl+S", "Ctrl+S, Ctrl+K"),
+            ("CmdOrCtrl+C", "Ctrl+C"),
+            ("Cmd+C", "Win+C"),
+        ];
+        for (a, b) in different {
+            assert!(!same_combo(a, b), "{a:?} != {b:?}");
+        }
+    }
+}
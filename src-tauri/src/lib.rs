@@ -1,14 +1,25 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+mod bundle;
+mod cheatsheet;
+mod conflicts;
+mod csv_io;
 mod defaults;
+mod focus_watcher;
+mod hotkeys;
+mod importers;
+mod key_combo;
+mod matcher;
+mod popup;
 mod storage;
 mod window_detection;
 
+use storage::StorageError;
 use tauri::Emitter;
 
 // Increment this when The default data needs to be changed/updated. E.g defaults.rs
 // has been changed/updated.
 // Updates will be applied only once (e.g., adding new default shortcuts/lists).
-const DEFAULTS_SEED_VERSION: u32 = 2;
+const DEFAULTS_SEED_VERSION: u32 = 4;
 
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -17,41 +28,59 @@ fn greet(name: &str) -> String {
 
 // Get the currently active application
 #[tauri::command]
-fn get_active_application() -> Result<String, String> {
-    window_detection::get_active_application()
+fn get_active_application() -> Result<String, StorageError> {
+    Ok(window_detection::get_active_application()?)
 }
 
 // Get the currently active application's title
 #[tauri::command]
-fn get_active_window_title() -> Result<String, String> {
-    window_detection::get_active_window_title()
+fn get_active_window_title() -> Result<String, StorageError> {
+    Ok(window_detection::get_active_window_title()?)
 }
 
-// Get all shortcut lists
+// Get the focused window's app name, title, process, bounds and WM class
 #[tauri::command]
-fn get_all_lists() -> Result<Vec<storage::ShortcutList>, String> {
-    storage::load_lists()
+fn get_active_window_info() -> Result<window_detection::ActiveWindowInfo, StorageError> {
+    Ok(window_detection::active_window()?)
+}
+
+// Get all shortcut lists, along with warnings for any lists file that
+// couldn't be loaded (corrupt files are quarantined)
+#[tauri::command]
+fn get_all_lists() -> Result<storage::LoadedLists, StorageError> {
+    storage::load_lists_with_warnings()
 }
 
 // Get shortcut lists for specific app
 #[tauri::command]
-fn get_lists_for_application(app_id: String) -> Result<Vec<storage::ShortcutList>, String> {
+fn get_lists_for_application(app_id: String) -> Result<Vec<storage::ShortcutList>, StorageError> {
     storage::load_lists_for_application(&app_id)
 }
 
 // Get shortcut lists for the currently active application
 #[tauri::command]
-fn get_lists_for_active_application() -> Result<Vec<storage::ShortcutList>, String> {
-    // Determine the active application's identifier (as reported by the OS)
-    let active_name = window_detection::get_active_application()?;
+fn get_lists_for_active_application() -> Result<Vec<storage::ShortcutList>, StorageError> {
+    // Determine the focused window (as reported by the OS)
+    let window = window_detection::active_window()?;
 
-    // Find the matching Application by detection_name or process_name
+    // Find the matching Application by its name and match rules
     let apps = storage::load_applications()?;
-    if let Some(app) = apps
-        .into_iter()
-        .find(|a| a.detection_name == active_name || a.process_name == active_name)
-    {
-        storage::load_lists_for_application(&app.id)
+    if let Some(app) = matcher::match_application(&apps, &window) {
+        let mut lists = storage::load_lists_for_application(&app.id)?;
+
+        // A list picked by the window title (e.g. Gmail in a browser) comes
+        // first, with the application's own lists after it
+        let mut candidates = lists.clone();
+        if app.is_browser {
+            candidates.extend(storage::load_lists_for_application(
+                defaults::WEB_APPS_APP_ID,
+            )?);
+        }
+        if let Some(site_list) = matcher::match_site_list(&candidates, app, &window) {
+            lists.retain(|l| l.id != site_list.id);
+            lists.insert(0, site_list.clone());
+        }
+        Ok(lists)
     } else {
         // If no matching application is registered, return an empty list
         Ok(Vec::new())
@@ -60,7 +89,8 @@ fn get_lists_for_active_application() -> Result<Vec<storage::ShortcutList>, Stri
 
 // Save a shortcut list
 #[tauri::command]
-fn save_list(list: storage::ShortcutList) -> Result<(), String> {
+fn save_list(list: storage::ShortcutList) -> Result<(), StorageError> {
+    matcher::validate_rules(&list.match_rules).map_err(StorageError::validation)?;
     let app_id = list.application_id.clone();
     let mut app_lists = storage::load_lists_for_application(&app_id)?;
 
@@ -75,9 +105,11 @@ fn save_list(list: storage::ShortcutList) -> Result<(), String> {
 
 // Delete a shortcut list
 #[tauri::command]
-fn delete_list(list_id: String) -> Result<(), String> {
+fn delete_list(list_id: String) -> Result<(), StorageError> {
     let all_lists = storage::load_lists()?;
     if let Some(target) = all_lists.into_iter().find(|l| l.id == list_id) {
+        // Always snapshot before a delete so it can be undone with restore_backup
+        storage::create_backup()?;
         let app_id = target.application_id;
         let mut app_lists = storage::load_lists_for_application(&app_id)?;
         app_lists.retain(|l| l.id != list_id);
@@ -89,13 +121,22 @@ fn delete_list(list_id: String) -> Result<(), String> {
 
 // Get all applications
 #[tauri::command]
-fn get_all_applications() -> Result<Vec<storage::Application>, String> {
+fn get_all_applications() -> Result<Vec<storage::Application>, StorageError> {
     storage::load_applications()
 }
 
+// Get the registered application the focused window belongs to, if any
+#[tauri::command]
+fn match_active_application() -> Result<Option<storage::Application>, StorageError> {
+    let window = window_detection::active_window()?;
+    let apps = storage::load_applications()?;
+    Ok(matcher::match_application(&apps, &window).cloned())
+}
+
 // Save an application
 #[tauri::command]
-fn save_application(app: storage::Application) -> Result<(), String> {
+fn save_application(app: storage::Application) -> Result<(), StorageError> {
+    matcher::validate_rules(&app.match_rules).map_err(StorageError::validation)?;
     let mut user_apps = storage::load_user_applications()?;
     if let Some(index) = user_apps.iter().position(|a| a.id == app.id) {
         user_apps[index] = app;
@@ -107,73 +148,198 @@ fn save_application(app: storage::Application) -> Result<(), String> {
 
 // Get settings
 #[tauri::command]
-fn get_settings() -> Result<storage::Settings, String> {
+fn get_settings() -> Result<storage::Settings, StorageError> {
     storage::load_settings()
 }
 
-// Save settings
+// Save settings. The global hotkeys are registered first, so a hotkey that is
+// invalid or taken by another application is rejected before anything is
+// written.
+#[tauri::command]
+fn save_settings(
+    app: tauri::AppHandle,
+    settings: storage::Settings,
+) -> Result<(), hotkeys::HotkeyError> {
+    hotkeys::save_settings(&app, &settings, storage::save_settings)?;
+    focus_watcher::set_enabled(&app, settings.watch_focus);
+    Ok(())
+}
+
+// List the available data backups, newest first
+#[tauri::command]
+fn list_backups() -> Result<Vec<storage::BackupInfo>, StorageError> {
+    storage::list_backups()
+}
+
+// Restore lists, user applications and settings from a backup
+#[tauri::command]
+fn restore_backup(app: tauri::AppHandle, id: String) -> Result<(), StorageError> {
+    storage::restore_backup(&id)?;
+    let _ = app.emit("applications-updated", ());
+    let _ = app.emit("settings-updated", ());
+    Ok(())
+}
+
+// Export applications and their lists (optionally settings) to a bundle file
+#[tauri::command]
+fn export_bundle(
+    path: String,
+    options: bundle::ExportOptions,
+) -> Result<bundle::ExportSummary, StorageError> {
+    bundle::export_bundle(std::path::Path::new(&path), &options)
+}
+
+// Render lists as a printable Markdown or HTML cheat sheet at `path`
+#[tauri::command]
+fn export_cheatsheet(
+    path: String,
+    list_ids: Vec<String>,
+    format: cheatsheet::CheatSheetFormat,
+    options: cheatsheet::CheatSheetOptions,
+) -> Result<(), StorageError> {
+    cheatsheet::export_cheatsheet(std::path::Path::new(&path), &list_ids, format, &options)
+}
+
+// Export lists to a CSV file for editing in a spreadsheet
+#[tauri::command]
+fn export_csv(path: String, list_ids: Vec<String>) -> Result<csv_io::CsvExportSummary, StorageError> {
+    csv_io::export_csv(std::path::Path::new(&path), &list_ids)
+}
+
+// Import shortcuts from a CSV file. Invalid rows are reported in the result
+// (and nothing is saved) rather than failing the whole command.
+#[tauri::command]
+fn import_csv(
+    path: String,
+    target_list: Option<String>,
+) -> Result<csv_io::CsvImportReport, StorageError> {
+    csv_io::import_csv(std::path::Path::new(&path), target_list.as_deref())
+}
+
+// Import a bundle file. Call with `dry_run: true` to preview the report
+// before committing the import.
+#[tauri::command]
+fn import_bundle(
+    app: tauri::AppHandle,
+    path: String,
+    mode: bundle::ImportMode,
+    dry_run: bool,
+) -> Result<bundle::ImportReport, hotkeys::HotkeyError> {
+    // Imported settings go through the same hotkey checks as save_settings
+    let mut watch_focus = None;
+    let report = bundle::import_bundle(std::path::Path::new(&path), mode, dry_run, |settings| {
+        if dry_run {
+            return hotkeys::parse_bindings(&settings.global_bindings()).map(|_| ());
+        }
+        watch_focus = Some(settings.watch_focus);
+        hotkeys::save_settings(&app, settings, storage::save_settings)
+    })?;
+    if !dry_run {
+        let _ = app.emit("applications-updated", ());
+        if let Some(enabled) = watch_focus {
+            focus_watcher::set_enabled(&app, enabled);
+            let _ = app.emit("settings-updated", ());
+        }
+    }
+    Ok(report)
+}
+
+// Import a VS Code keybindings.json into the "My Keybindings" list
+#[tauri::command]
+fn import_vscode_keybindings(path: String) -> Result<importers::ImportSummary, StorageError> {
+    importers::vscode::import_keybindings(std::path::Path::new(&path))
+}
+
+// Import a JetBrains keymap .xml into a list for one of the JetBrains IDEs
+#[tauri::command]
+fn import_jetbrains_keymap(
+    path: String,
+    app_id: String,
+) -> Result<importers::ImportSummary, StorageError> {
+    importers::jetbrains::import_keymap(std::path::Path::new(&path), &app_id)
+}
+
+// Import Vim/Neovim mappings (vimrc or init.lua) into per-mode lists
+#[tauri::command]
+fn import_vim_mappings(path: String) -> Result<Vec<importers::ImportSummary>, StorageError> {
+    importers::vim::import_mappings(std::path::Path::new(&path))
+}
+
+// Import Emacs key bindings from an init.el
+#[tauri::command]
+fn import_emacs_bindings(path: String) -> Result<importers::ImportSummary, StorageError> {
+    importers::emacs::import_init_el(std::path::Path::new(&path))
+}
+
+// Import tmux bindings from a .tmux.conf into a terminal application's "tmux" list
 #[tauri::command]
-fn save_settings(settings: storage::Settings) -> Result<(), String> {
-    storage::save_settings(&settings)
+fn import_tmux_config(path: String, app_id: String) -> Result<importers::ImportSummary, StorageError> {
+    importers::tmux::import_tmux_config(std::path::Path::new(&path), &app_id)
+}
+
+// Import readline (.inputrc) or zsh bindkey bindings into a terminal application
+#[tauri::command]
+fn import_shell_bindings(
+    path: String,
+    app_id: String,
+) -> Result<importers::ImportSummary, StorageError> {
+    importers::shell::import_shell_bindings(std::path::Path::new(&path), &app_id)
+}
+
+// Report key combos an application's lists use twice, or that collide with
+// Will-Shortcut's own hotkeys
+#[tauri::command]
+fn find_conflicts(app_id: String) -> Result<Vec<conflicts::Conflict>, StorageError> {
+    conflicts::find_conflicts(&app_id)
+}
+
+// Display forms of key combos for a platform (defaults to the current one),
+// as text ("Cmd+Shift+P") or macOS symbols ("⌘⇧P")
+#[tauri::command]
+fn render_key_combos(
+    key_combos: Vec<String>,
+    platform: Option<key_combo::Platform>,
+    style: key_combo::KeyStyle,
+) -> Vec<String> {
+    let platform = platform.unwrap_or_else(key_combo::Platform::current);
+    key_combos
+        .iter()
+        .map(|combo| key_combo::render(combo, platform, style))
+        .collect()
 }
 
 // Initialize or merge default data (applications are defined in applications.json).
 // This performs **shortcut-level** additive merging, but only once per
 // DEFAULTS_SEED_VERSION. 
+// Returns warnings for any lists file that was quarantined while merging.
 #[tauri::command]
-fn initialize_defaults() -> Result<(), String> {
+fn initialize_defaults() -> Result<Vec<storage::LoadWarning>, StorageError> {
     // Load settings so we can get seed version
     let mut settings = storage::load_settings()?;
 
     // If the current default seed version is already applied, do nothing.
     if settings.defaults_seed_version >= DEFAULTS_SEED_VERSION {
-        return Ok(());
+        return Ok(Vec::new());
     }
 
-    // Load all existing lists across applications
-    let mut existing_lists = storage::load_lists()?;
+    // Load all existing lists across applications. Corrupt files are moved
+    // aside first, so seeding never overwrites them.
+    let loaded = storage::load_lists_with_warnings()?;
+    let mut existing_lists = loaded.lists;
     let (_default_apps, default_lists) = defaults::create_default_data();
 
-    use std::collections::HashSet;
-
+    let platform = key_combo::Platform::current();
     let mut changed = false;
 
-    for mut default_list in default_lists {
+    for default_list in default_lists {
         // 1) Find an existing list with the same application_id + name
         if let Some(existing) = existing_lists
             .iter_mut()
             .find(|l| l.application_id == default_list.application_id && l.name == default_list.name)
         {
-            // 2) Shortcut-level merge: build a set of existing key_combos
-            let mut existing_keys: HashSet<String> = existing
-                .shortcuts
-                .iter()
-                .map(|s| s.key_combo.clone())
-                .collect();
-
-            // Determine the current max order to append new shortcuts at the end
-            let mut max_order = existing
-                .shortcuts
-                .iter()
-                .map(|s| s.order)
-                .max()
-                .unwrap_or(-1);
-
-            for mut default_shortcut in default_list.shortcuts.drain(..) {
-                // If this key_combo already exists, do NOT overwrite user data
-                if existing_keys.contains(&default_shortcut.key_combo) {
-                    continue;
-                }
-
-                existing_keys.insert(default_shortcut.key_combo.clone());
-                max_order += 1;
-
-                // Ensure a fresh id and append at the end with incremented order
-                default_shortcut.id = uuid::Uuid::new_v4().to_string();
-                default_shortcut.order = max_order;
-                existing.shortcuts.push(default_shortcut);
-                changed = true;
-            }
+            // 2) Shortcut-level merge: only add shortcuts whose key_combo the
+            // list doesn't have yet, so user edits are never overwritten
+            changed |= defaults::merge_shortcuts(existing, default_list.shortcuts, platform);
         } else {
             // 3) Entire list is new: add it as-is (user data integrity preserved)
             existing_lists.push(default_list);
@@ -191,12 +357,12 @@ fn initialize_defaults() -> Result<(), String> {
     settings.defaults_seed_version = DEFAULTS_SEED_VERSION;
     storage::save_settings(&settings)?;
 
-    Ok(())
+    Ok(loaded.warnings)
 }
 
 // Debugging command to print merged applications
 #[tauri::command]
-fn debug_dump_applications() -> Result<Vec<storage::Application>, String> {
+fn debug_dump_applications() -> Result<Vec<storage::Application>, StorageError> {
     let apps = storage::load_applications()?;
     println!("Merged applications: {:#?}", apps);
     Ok(apps)
@@ -222,17 +388,12 @@ fn toggle_window(window: tauri::Window) -> Result<(), String> {
     Ok(())
 }
 
+// Re-register the global hotkeys from saved settings. If any new hotkey
+// can't be registered the previous ones keep working.
 #[tauri::command]
-fn refresh_global_hotkey(app: tauri::AppHandle) -> Result<(), String> {
-    use tauri_plugin_global_shortcut::GlobalShortcutExt;
-
-    let settings = crate::storage::load_settings().map_err(|e| e.to_string())?;
-
-    app.global_shortcut()
-        .unregister_all()
-        .map_err(|e| e.to_string())?;
-
-    register_global_hotkey(&app, settings.global_hotkey.as_str())
+fn refresh_global_hotkey(app: tauri::AppHandle) -> Result<(), hotkeys::HotkeyError> {
+    let settings = crate::storage::load_settings()?;
+    hotkeys::refresh(&app, &settings.global_bindings())
 }
 
 // Position window in bottom-right corner
@@ -274,7 +435,24 @@ pub fn run() {
             initialize_defaults,
             toggle_window,
             debug_dump_applications,
-            refresh_global_hotkey
+            refresh_global_hotkey,
+            list_backups,
+            restore_backup,
+            export_bundle,
+            import_bundle,
+            import_vscode_keybindings,
+            import_jetbrains_keymap,
+            import_vim_mappings,
+            import_emacs_bindings,
+            import_tmux_config,
+            import_shell_bindings,
+            export_cheatsheet,
+            export_csv,
+            import_csv,
+            render_key_combos,
+            find_conflicts,
+            get_active_window_info,
+            match_active_application
         ])
         .setup(|app| {
             #[cfg(desktop)]
@@ -320,174 +498,21 @@ pub fn run() {
                     .expect("failed to build tray icon");
             }
 
-            use active_win_pos_rs::get_active_window;
-            use tauri_plugin_global_shortcut::GlobalShortcutExt;
-
             // Load settings once at startup
-            let settings = crate::storage::load_settings().unwrap_or_else(|_| {
-                storage::Settings {
-                    // fallback if load fails
-                    global_hotkey: "Ctrl+Shift+Alt+K".into(),
-                    always_on_top: true,
-                    run_on_startup: true,
-                    keyboard_shortcuts: storage::KeyboardShortcuts {
-                        move_up: "Alt+Up".into(),
-                        move_down: "Alt+Down".into(),
-                        duplicate: "Ctrl+D".into(),
-                        delete: "Delete".into(),
-                        add_new: "Ctrl+N".into(),
-                        add_above: "Ctrl+Shift+N".into(),
-                        add_below: "Ctrl+Alt+N".into(),
-                    },
-                    window_position: "BottomRight".into(),
-                    show_app_name_in_dropdown: true,
-                    show_all_lists: false,
-                    defaults_seed_version: 0,
-                }
-            });
-
-            // Register global hotkey using helper
-            let app_handle = app.handle().clone();
-            if let Err(e) = register_global_hotkey(&app_handle, settings.global_hotkey.as_str()) {
-                eprintln!("Failed to register global hotkey on startup: {e}");
-            }
+            // (fall back to defaults if load fails)
+            let settings =
+                crate::storage::load_settings().unwrap_or_else(|_| storage::default_settings());
+
+            // Register global hotkeys
+            app.manage(hotkeys::RegisteredHotkeys::default());
+            hotkeys::register_startup(app.handle(), &settings.global_bindings());
+
+            // Follow window focus in the background if enabled
+            app.manage(focus_watcher::FocusWatcher::default());
+            focus_watcher::set_enabled(app.handle(), settings.watch_focus);
 
             Ok(())
         })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
-
-fn register_global_hotkey(app: &tauri::AppHandle, hotkey: &str) -> Result<(), String> {
-    use active_win_pos_rs::get_active_window;
-    use tauri_plugin_global_shortcut::GlobalShortcutExt;
-
-    let handle = app.clone();
-
-    app.global_shortcut()
-        .on_shortcut(hotkey, move |_app, _shortcut, event| {
-            // Only trigger on key press, not release
-            if event.state == tauri_plugin_global_shortcut::ShortcutState::Pressed {
-                if let Some(window) = handle.get_webview_window("main") {
-                    // Toggle window visibility directly
-                    if let Ok(is_visible) = window.is_visible() {
-                        if is_visible {
-                            if let Some(settings_window) = handle.get_webview_window("settings") {
-                                let _ = settings_window.hide();
-                            }
-                            let _ = window.emit("popup-hidden", true);
-                            let _ = window.hide();
-                        } else {
-                            // Get the active app BEFORE showing the window
-                            if let Ok(active_app_name) =
-                                crate::window_detection::get_active_application()
-                            {
-                                let _ = window.emit("active-app-detected", active_app_name);
-                            }
-
-                            // Get the monitor where the active window is located
-                            let target_monitor = if let Ok(active_win) = get_active_window() {
-                                // Get all monitors and find which one contains the active window
-                                if let Ok(monitors) = window.available_monitors() {
-                                    monitors.into_iter().find(|monitor| {
-                                        let pos = monitor.position();
-                                        let size = monitor.size();
-                                        let monitor_x = pos.x;
-                                        let monitor_y = pos.y;
-                                        let monitor_width = size.width as i32;
-                                        let monitor_height = size.height as i32;
-
-                                        // Check if active window center is within this monitor
-                                        let active_center_x = (active_win.position.x
-                                            + active_win.position.width / 2.0)
-                                            as i32;
-                                        let active_center_y = (active_win.position.y
-                                            + active_win.position.height / 2.0)
-                                            as i32;
-
-                                        active_center_x >= monitor_x
-                                            && active_center_x < monitor_x + monitor_width
-                                            && active_center_y >= monitor_y
-                                            && active_center_y < monitor_y + monitor_height
-                                    })
-                                } else {
-                                    None
-                                }
-                            } else {
-                                None
-                            };
-
-                            // Use the detected monitor or fall back to current monitor
-                            let monitor =
-                                target_monitor.or_else(|| window.current_monitor().ok().flatten());
-
-                            // Position and show
-                            if let Some(monitor) = monitor {
-                                let monitor_pos = monitor.position();
-                                let screen_size = monitor.size();
-
-                                if let Ok(window_size) = window.outer_size() {
-                                    let taskbar_height = 40;
-                                    let border_offset = 8;
-
-                                    // Load settings to decide which corner to use
-                                    let settings =
-                                        crate::storage::load_settings().unwrap_or_else(|_| {
-                                            storage::Settings {
-                                                // simple fallback if load fails
-                                                global_hotkey: "Ctrl+Shift+Alt+K".into(),
-                                                always_on_top: true,
-                                                run_on_startup: true,
-                                                keyboard_shortcuts: storage::KeyboardShortcuts {
-                                                    move_up: "Alt+Up".into(),
-                                                    move_down: "Alt+Down".into(),
-                                                    duplicate: "Ctrl+D".into(),
-                                                    delete: "Delete".into(),
-                                                    add_new: "Ctrl+N".into(),
-                                                    add_above: "Ctrl+Shift+N".into(),
-                                                    add_below: "Ctrl+Alt+N".into(),
-                                                },
-                                                window_position: "BottomRight".into(),
-                                                show_app_name_in_dropdown: true,
-                                                show_all_lists: false,
-                                                defaults_seed_version: 0,
-                                            }
-                                        });
-
-                                    let (x, y) = match settings.window_position.as_str() {
-                                        "TopLeft" => (monitor_pos.x - border_offset, monitor_pos.y),
-                                        "TopRight" => (
-                                            monitor_pos.x + screen_size.width as i32
-                                                - window_size.width as i32
-                                                + border_offset,
-                                            monitor_pos.y,
-                                        ),
-                                        "BottomLeft" => (
-                                            monitor_pos.x - border_offset,
-                                            monitor_pos.y + screen_size.height as i32
-                                                - window_size.height as i32
-                                                - taskbar_height,
-                                        ),
-                                        // default: BottomRight
-                                        _ => (
-                                            monitor_pos.x + screen_size.width as i32
-                                                - window_size.width as i32
-                                                + border_offset,
-                                            monitor_pos.y + screen_size.height as i32
-                                                - window_size.height as i32
-                                                - taskbar_height,
-                                        ),
-                                    };
-
-                                    let _ = window.set_position(PhysicalPosition::new(x, y));
-                                }
-                            }
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
-                    }
-                }
-            }
-        })
-        .map_err(|e| e.to_string())
-}
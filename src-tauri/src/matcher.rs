@@ -0,0 +1,392 @@
+// Matching the focused window to an Application.
+//
+// Every application matches when the window's app name equals its
+// detection_name or process_name (the original behaviour); its match_rules
+// add more ways in, e.g. "code" on Linux or a Flatpak id like
+// "org.mozilla.firefox". When several applications match, the highest rule
+// priority wins, then the more specific kind (exact, case-insensitive, glob,
+// regex), then whichever application comes first.
+//
+// Lists can carry match rules too. Inside a matched application, a list whose
+// rules match (say a Gmail list for titles like "Inbox - Gmail - Chrome") is
+// preferred over the application's other lists. Browsers also consider the
+// lists of the shared Web apps application.
+
+use crate::defaults::WEB_APPS_APP_ID;
+use crate::storage::{Application, MatchField, MatchKind, MatchRule, ShortcutList};
+use crate::window_detection::ActiveWindowInfo;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+// Compiled patterns kept at most; the cache starts over when it fills up
+const REGEX_CACHE_LIMIT: usize = 256;
+
+// How well an application matched, compared lexicographically
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Score {
+    priority: i32,
+    specificity: u8,
+}
+
+fn specificity(kind: MatchKind) -> u8 {
+    match kind {
+        MatchKind::Exact => 3,
+        MatchKind::CaseInsensitive => 2,
+        MatchKind::Glob => 1,
+        MatchKind::Regex => 0,
+    }
+}
+
+// The application the focused window belongs to, if any
+pub fn match_application<'a>(
+    apps: &'a [Application],
+    window: &ActiveWindowInfo,
+) -> Option<&'a Application> {
+    let mut best: Option<(Score, &Application)> = None;
+    for app in apps {
+        let Some(score) = score(app, window) else {
+            continue;
+        };
+        // Strictly greater, so earlier applications win ties
+        if best.is_none_or(|(best_score, _)| score > best_score) {
+            best = Some((score, app));
+        }
+    }
+    best.map(|(_, app)| app)
+}
+
+fn score(app: &Application, window: &ActiveWindowInfo) -> Option<Score> {
+    let legacy = (!window.app_name.is_empty()
+        && (window.app_name == app.detection_name || window.app_name == app.process_name))
+        .then_some(Score {
+            priority: 0,
+            specificity: specificity(MatchKind::Exact),
+        });
+    best_rule(&app.match_rules, window).max(legacy)
+}
+
+// The list with matching rules to show for `app`, if any. Ranked like
+// applications; earlier lists win ties.
+pub fn match_site_list<'a>(
+    lists: &'a [ShortcutList],
+    app: &Application,
+    window: &ActiveWindowInfo,
+) -> Option<&'a ShortcutList> {
+    let mut best: Option<(Score, &ShortcutList)> = None;
+    for list in lists {
+        let applies = list.application_id == app.id
+            || (app.is_browser && list.application_id == WEB_APPS_APP_ID);
+        if !applies {
+            continue;
+        }
+        let Some(score) = best_rule(&list.match_rules, window) else {
+            continue;
+        };
+        if best.is_none_or(|(best_score, _)| score > best_score) {
+            best = Some((score, list));
+        }
+    }
+    best.map(|(_, list)| list)
+}
+
+fn best_rule(rules: &[MatchRule], window: &ActiveWindowInfo) -> Option<Score> {
+    rules
+        .iter()
+        .filter(|rule| rule_matches(rule, window))
+        .map(|rule| Score {
+            priority: rule.priority,
+            specificity: specificity(rule.kind),
+        })
+        .max()
+}
+
+pub fn rule_matches(rule: &MatchRule, window: &ActiveWindowInfo) -> bool {
+    let value = match rule.field {
+        MatchField::AppName => Some(window.app_name.as_str()),
+        MatchField::ProcessPath => window.process_path.as_deref(),
+        MatchField::WindowTitle => Some(window.title.as_str()),
+        MatchField::WmClass => window.wm_class.as_deref(),
+    };
+    match value {
+        Some(value) if !value.is_empty() => pattern_matches(rule.kind, &rule.pattern, value),
+        _ => false,
+    }
+}
+
+pub fn pattern_matches(kind: MatchKind, pattern: &str, value: &str) -> bool {
+    match kind {
+        MatchKind::Exact => value == pattern,
+        MatchKind::CaseInsensitive => value.to_lowercase() == pattern.to_lowercase(),
+        MatchKind::Glob => glob_matches(&pattern.to_lowercase(), &value.to_lowercase()),
+        // Invalid patterns are rejected on save; treat any that slip through
+        // as never matching
+        MatchKind::Regex => cached_regex(pattern).is_some_and(|re| re.is_match(value)),
+    }
+}
+
+// Compile a regex pattern once. Rules are reloaded from disk for every
+// lookup, so the cache is keyed by pattern rather than kept on the rule.
+// None marks a pattern that doesn't compile.
+fn cached_regex(pattern: &str) -> Option<Regex> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Option<Regex>>>> = OnceLock::new();
+    let mut cache = CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if cache.len() >= REGEX_CACHE_LIMIT && !cache.contains_key(pattern) {
+        cache.clear();
+    }
+    cache
+        .entry(pattern.to_string())
+        .or_insert_with(|| Regex::new(pattern).ok())
+        .clone()
+}
+
+// `*` matches any run of characters (including none), `?` exactly one
+fn glob_matches(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+    let (mut p, mut v) = (0, 0);
+    // Where to resume after the last `*`: (pattern index after it, value index)
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p + 1, v));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` swallow one more character
+                Some((after_star, start)) => {
+                    p = after_star;
+                    v = start + 1;
+                    backtrack = Some((after_star, start + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+// Check rules before saving an application
+pub fn validate_rules(rules: &[MatchRule]) -> Result<(), String> {
+    for rule in rules {
+        if rule.pattern.trim().is_empty() {
+            return Err("Match rule patterns can't be empty".to_string());
+        }
+        if rule.kind == MatchKind::Regex {
+            Regex::new(&rule.pattern)
+                .map_err(|e| format!("Invalid regex \"{}\": {e}", rule.pattern))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(field: MatchField, kind: MatchKind, pattern: &str, priority: i32) -> MatchRule {
+        MatchRule {
+            field,
+            kind,
+            pattern: pattern.to_string(),
+            priority,
+        }
+    }
+
+    fn app(id: &str, detection_name: &str, match_rules: Vec<MatchRule>) -> Application {
+        Application {
+            id: id.to_string(),
+            name: id.to_string(),
+            process_name: String::new(),
+            detection_name: detection_name.to_string(),
+            icon: None,
+            last_used_list_id: None,
+            match_rules,
+            is_browser: false,
+        }
+    }
+
+    fn window(app_name: &str, title: &str) -> ActiveWindowInfo {
+        ActiveWindowInfo {
+            app_name: app_name.to_string(),
+            title: title.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn matched<'a>(apps: &'a [Application], window: &ActiveWindowInfo) -> Option<&'a str> {
+        match_application(apps, window).map(|app| app.id.as_str())
+    }
+
+    #[test]
+    fn pattern_kinds() {
+        use MatchKind::*;
+        let cases = [
+            (Exact, "Code", "Code", true),
+            (Exact, "code", "Code", false),
+            (CaseInsensitive, "code", "Code", true),
+            (CaseInsensitive, "code", "Code - Insiders", false),
+            (Glob, "* - Gmail*", "Inbox - Gmail — Mozilla Firefox", true),
+            (Glob, "jetbrains-idea*", "JetBrains-IDEA-CE", true),
+            (Glob, "a?c", "abc", true),
+            (Glob, "a?c", "abbc", false),
+            (Glob, "*a*b*", "xxaxxbxx", true),
+            (Glob, "*.exe", "code.exe.bak", false),
+            // Regex special characters are literal in globs
+            (Glob, "a.c", "abc", false),
+            (Regex, "a.c", "abc", true),
+            (Regex, "^fire", "firefox", true),
+            (Regex, "^fire", "Firefox", false),
+            (Regex, "(?i)^fire", "Firefox", true),
+            // Regexes match anywhere unless anchored, globs match the whole value
+            (Regex, "fox", "firefox", true),
+            (Glob, "fox", "firefox", false),
+            (Regex, "(", "(", false),
+        ];
+        for (kind, pattern, value, expected) in cases {
+            assert_eq!(
+                pattern_matches(kind, pattern, value),
+                expected,
+                "{kind:?} {pattern:?} on {value:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn wm_class_and_process_path_rules() {
+        let apps = vec![
+            app(
+                "firefox",
+                "",
+                vec![rule(MatchField::WmClass, MatchKind::Exact, "firefox", 0)],
+            ),
+            app(
+                "code",
+                "",
+                vec![rule(
+                    MatchField::ProcessPath,
+                    MatchKind::Glob,
+                    "*/code/code",
+                    0,
+                )],
+            ),
+        ];
+        let mut firefox = window("Navigator", "New Tab");
+        assert_eq!(matched(&apps, &firefox), None);
+        firefox.wm_class = Some("firefox".to_string());
+        assert_eq!(matched(&apps, &firefox), Some("firefox"));
+
+        let mut code = window("code-oss", "main.rs");
+        code.process_path = Some("/usr/share/code/code".to_string());
+        assert_eq!(matched(&apps, &code), Some("code"));
+
+        // Fields a backend didn't report never match
+        let mut empty = window("x", "y");
+        empty.wm_class = Some(String::new());
+        assert_eq!(matched(&apps, &empty), None);
+    }
+
+    #[test]
+    fn highest_priority_wins_then_specificity_then_order() {
+        let apps = vec![
+            app("chrome", "chrome.exe", vec![]),
+            app(
+                "gmail",
+                "",
+                vec![rule(MatchField::WindowTitle, MatchKind::Glob, "*gmail*", 5)],
+            ),
+            app(
+                "regex",
+                "",
+                vec![rule(MatchField::AppName, MatchKind::Regex, "^chrome", 0)],
+            ),
+            app(
+                "glob-first",
+                "",
+                vec![rule(MatchField::AppName, MatchKind::Glob, "edge*", 1)],
+            ),
+            app(
+                "glob-second",
+                "",
+                vec![rule(MatchField::AppName, MatchKind::Glob, "edge*", 1)],
+            ),
+            app(
+                "exact",
+                "",
+                vec![rule(MatchField::AppName, MatchKind::Exact, "edge", 1)],
+            ),
+        ];
+        // Priority beats everything
+        assert_eq!(
+            matched(&apps, &window("chrome.exe", "Inbox - Gmail")),
+            Some("gmail")
+        );
+        // Same priority: the legacy exact name beats a regex
+        assert_eq!(matched(&apps, &window("chrome.exe", "x")), Some("chrome"));
+        // Same priority: exact beats glob
+        assert_eq!(matched(&apps, &window("edge", "x")), Some("exact"));
+        // Full tie: the earlier application wins
+        assert_eq!(
+            matched(&apps, &window("edge-beta", "x")),
+            Some("glob-first")
+        );
+        assert_eq!(matched(&apps, &window("vim", "x")), None);
+        // Applications with no names never match an empty app name
+        assert_eq!(matched(&apps, &window("", "x")), None);
+    }
+
+    #[test]
+    fn site_lists_only_apply_to_their_app_or_browsers() {
+        let list = |id: &str, app_id: &str, pattern: &str| ShortcutList {
+            id: id.to_string(),
+            name: id.to_string(),
+            application_id: app_id.to_string(),
+            shortcuts: Vec::new(),
+            created_at: String::new(),
+            updated_at: String::new(),
+            match_rules: vec![rule(MatchField::WindowTitle, MatchKind::Glob, pattern, 0)],
+        };
+        let lists = vec![
+            list("gmail", WEB_APPS_APP_ID, "* - Gmail*"),
+            list("own", "chrome", "*settings*"),
+        ];
+        let mut chrome = app("chrome", "chrome.exe", vec![]);
+        let gmail = window("chrome.exe", "Inbox - Gmail - Google Chrome");
+        assert!(match_site_list(&lists, &chrome, &gmail).is_none());
+        chrome.is_browser = true;
+        assert_eq!(
+            match_site_list(&lists, &chrome, &gmail).map(|l| l.id.as_str()),
+            Some("gmail")
+        );
+        let settings = window("chrome.exe", "Settings - Google Chrome");
+        assert_eq!(
+            match_site_list(&lists, &chrome, &settings).map(|l| l.id.as_str()),
+            Some("own")
+        );
+        assert!(match_site_list(&lists, &chrome, &window("chrome.exe", "New Tab")).is_none());
+    }
+
+    #[test]
+    fn validate_rules_rejects_bad_rules() {
+        assert!(validate_rules(&[]).is_ok());
+        assert!(validate_rules(&[
+            rule(MatchField::AppName, MatchKind::Regex, "^code(-oss)?$", 0),
+            rule(MatchField::WindowTitle, MatchKind::Glob, "* - Gmail*", 0),
+        ])
+        .is_ok());
+        let bad_regex = validate_rules(&[rule(MatchField::AppName, MatchKind::Regex, "(", 0)]);
+        assert!(bad_regex.unwrap_err().contains("Invalid regex"));
+        assert!(validate_rules(&[rule(MatchField::AppName, MatchKind::Exact, "  ", 0)]).is_err());
+        // "(" is only special to regexes
+        assert!(validate_rules(&[rule(MatchField::AppName, MatchKind::Glob, "(", 0)]).is_ok());
+    }
+}
@@ -0,0 +1,168 @@
+// Showing and hiding the popup ("main" window) and the settings window in
+// response to global hotkeys.
+
+use crate::storage::{Application, ShortcutList};
+use crate::window_detection::{ActiveWindowInfo, WindowBounds};
+use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, WebviewWindow};
+
+pub fn is_visible(app: &AppHandle) -> bool {
+    app.get_webview_window("main")
+        .and_then(|window| window.is_visible().ok())
+        .unwrap_or(false)
+}
+
+pub fn toggle(app: &AppHandle) {
+    if is_visible(app) {
+        hide(app);
+    } else {
+        show(app);
+    }
+}
+
+pub fn hide(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    if let Some(settings_window) = app.get_webview_window("settings") {
+        let _ = settings_window.hide();
+    }
+    let _ = window.emit("popup-hidden", true);
+    let _ = window.hide();
+}
+
+// Detect the active app, move the popup onto its monitor and show it
+pub fn show(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+
+    // Get the active app BEFORE showing the window
+    let active_window = crate::window_detection::active_window().ok();
+    if let Some(active_window) = &active_window {
+        let (identifier, site_list_id) = detected_context(active_window);
+        // Sent first so the popup knows about the site list when it reacts
+        // to the app change
+        let _ = window.emit("site-list-detected", site_list_id);
+        let _ = window.emit("active-app-detected", identifier);
+    }
+
+    position(&window, active_window.and_then(|w| w.bounds));
+    let _ = window.show();
+    let _ = window.set_focus();
+}
+
+// Show the popup if it's hidden, then send it `event`
+pub fn show_with(app: &AppHandle, event: &str, payload: impl serde::Serialize + Clone) {
+    if !is_visible(app) {
+        show(app);
+    }
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(event, payload);
+    }
+}
+
+// The popup looks applications up by detection_name (or process_name), so
+// send the key of the application the match rules picked rather than the raw
+// app name, which may only match through a rule. Also returns the list whose
+// rules match the window (a site list in a browser), if any.
+pub fn detected_context(active_window: &ActiveWindowInfo) -> (String, Option<String>) {
+    let apps = crate::storage::load_applications().unwrap_or_default();
+    let lists = crate::storage::load_lists().unwrap_or_default();
+    context_for(&apps, &lists, active_window)
+}
+
+// detected_context for already loaded applications and lists
+pub fn context_for(
+    apps: &[Application],
+    lists: &[ShortcutList],
+    active_window: &ActiveWindowInfo,
+) -> (String, Option<String>) {
+    let Some(app) = crate::matcher::match_application(apps, active_window) else {
+        return (active_window.app_name.clone(), None);
+    };
+
+    let identifier = if app.detection_name.is_empty() {
+        app.process_name.clone()
+    } else {
+        app.detection_name.clone()
+    };
+    let site_list_id =
+        crate::matcher::match_site_list(lists, app, active_window).map(|list| list.id.clone());
+    (identifier, site_list_id)
+}
+
+pub fn open_settings(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("settings") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn position(window: &WebviewWindow, active_bounds: Option<WindowBounds>) {
+    // Get the monitor where the active window is located
+    let target_monitor = if let Some(bounds) = active_bounds {
+        // Get all monitors and find which one contains the active window
+        if let Ok(monitors) = window.available_monitors() {
+            monitors.into_iter().find(|monitor| {
+                let pos = monitor.position();
+                let size = monitor.size();
+                let monitor_x = pos.x;
+                let monitor_y = pos.y;
+                let monitor_width = size.width as i32;
+                let monitor_height = size.height as i32;
+
+                // Check if active window center is within this monitor
+                let (active_center_x, active_center_y) = bounds.center();
+
+                active_center_x >= monitor_x
+                    && active_center_x < monitor_x + monitor_width
+                    && active_center_y >= monitor_y
+                    && active_center_y < monitor_y + monitor_height
+            })
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    // Use the detected monitor or fall back to current monitor
+    let Some(monitor) = target_monitor.or_else(|| window.current_monitor().ok().flatten()) else {
+        return;
+    };
+    let monitor_pos = monitor.position();
+    let screen_size = monitor.size();
+
+    if let Ok(window_size) = window.outer_size() {
+        let taskbar_height = 40;
+        let border_offset = 8;
+
+        // Load settings to decide which corner to use
+        // (simple fallback if load fails)
+        let settings =
+            crate::storage::load_settings().unwrap_or_else(|_| crate::storage::default_settings());
+
+        let (x, y) = match settings.window_position.as_str() {
+            "TopLeft" => (monitor_pos.x - border_offset, monitor_pos.y),
+            "TopRight" => (
+                monitor_pos.x + screen_size.width as i32 - window_size.width as i32 + border_offset,
+                monitor_pos.y,
+            ),
+            "BottomLeft" => (
+                monitor_pos.x - border_offset,
+                monitor_pos.y + screen_size.height as i32
+                    - window_size.height as i32
+                    - taskbar_height,
+            ),
+            // default: BottomRight
+            _ => (
+                monitor_pos.x + screen_size.width as i32 - window_size.width as i32 + border_offset,
+                monitor_pos.y + screen_size.height as i32
+                    - window_size.height as i32
+                    - taskbar_height,
+            ),
+        };
+
+        let _ = window.set_position(PhysicalPosition::new(x, y));
+    }
+}
@@ -1,9 +1,68 @@
 use directories::ProjectDirs;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Cow;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::collections::HashMap;
 
+// Errors returned by the storage layer. Serialized to the frontend as a
+// tagged object (`{ "kind": "parse", "path": ..., "line": ... }`) so the UI
+// can tell a missing file from a corrupt one from a permission problem.
+#[derive(Debug, thiserror::Error, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StorageError {
+    #[error("I/O error on {path}: {message}")]
+    Io {
+        path: String,
+        // io::ErrorKind, e.g. "NotFound" or "PermissionDenied"
+        reason: String,
+        message: String,
+    },
+    #[error("Failed to parse {path} at line {line}, column {column}: {message}")]
+    Parse {
+        path: String,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    #[error("Could not determine the application data directory")]
+    MissingDataDir,
+    #[error("Validation failed: {message}")]
+    Validation { message: String },
+    // The focused window couldn't be determined
+    #[error("Failed to get active window ({message})")]
+    Detection { message: String },
+}
+
+impl StorageError {
+    pub fn io(path: &Path, err: std::io::Error) -> Self {
+        StorageError::Io {
+            path: path.display().to_string(),
+            reason: format!("{:?}", err.kind()),
+            message: err.to_string(),
+        }
+    }
+
+    pub fn parse(path: &Path, err: serde_json::Error) -> Self {
+        StorageError::Parse {
+            path: path.display().to_string(),
+            line: err.line(),
+            column: err.column(),
+            message: err.to_string(),
+        }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        StorageError::Validation {
+            message: message.into(),
+        }
+    }
+}
+
 // Data structures matching our design
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Application {
@@ -13,6 +72,43 @@ pub struct Application {
     pub detection_name: String,
     pub icon: Option<String>,
     pub last_used_list_id: Option<String>,
+    // Extra ways to recognise the application besides detection_name /
+    // process_name (see matcher)
+    #[serde(default)]
+    pub match_rules: Vec<MatchRule>,
+    // Web browsers also offer the site lists of the "Web apps" application
+    #[serde(default)]
+    pub is_browser: bool,
+}
+
+// Which property of the focused window a MatchRule looks at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchField {
+    AppName,
+    ProcessPath,
+    WindowTitle,
+    WmClass,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    Exact,
+    CaseInsensitive,
+    // `*` and `?` wildcards, case-insensitive
+    Glob,
+    Regex,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchRule {
+    pub field: MatchField,
+    pub kind: MatchKind,
+    pub pattern: String,
+    // When several applications match, the highest priority wins
+    #[serde(default)]
+    pub priority: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +127,10 @@ pub struct ShortcutList {
     pub shortcuts: Vec<Shortcut>,
     pub created_at: String,
     pub updated_at: String,
+    // Rules (usually on the window title, e.g. "* - Gmail - *") that pick
+    // this list over the application's other lists while they match
+    #[serde(default)]
+    pub match_rules: Vec<MatchRule>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +163,37 @@ fn default_keyboard_shortcuts() -> KeyboardShortcuts {
     }
 }
 
+// What a global hotkey does when pressed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GlobalAction {
+    TogglePopup,
+    // Show the popup on this list instead of the active app's list
+    OpenList { list_id: String },
+    OpenSettings,
+    // Advance the popup to the next list in its dropdown
+    NextList,
+    // Show the popup with the search box focused
+    OpenSearch,
+}
+
+impl GlobalAction {
+    // Actions that just bring up the popup, so holding the hotkey to peek
+    // at it makes sense
+    pub fn supports_peek(&self) -> bool {
+        matches!(self, GlobalAction::TogglePopup | GlobalAction::OpenList { .. })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalHotkey {
+    pub hotkey: String,
+    pub action: GlobalAction,
+    // Show the popup only while the hotkey is held down
+    #[serde(default)]
+    pub peek: bool,
+}
+
 fn default_window_position() -> String {
     "BottomRight".to_string()
 }
@@ -79,6 +210,14 @@ fn default_defaults_seed_version() -> u32 {
     0
 }
 
+fn default_backup_max_count() -> u32 {
+    10
+}
+
+fn default_backup_max_age_days() -> u32 {
+    30
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
     pub global_hotkey: String,
@@ -94,9 +233,37 @@ pub struct Settings {
     pub show_all_lists: bool,
     #[serde(default = "default_defaults_seed_version")]
     pub defaults_seed_version: u32,
+    // How many snapshots to keep in the backups folder
+    #[serde(default = "default_backup_max_count")]
+    pub backup_max_count: u32,
+    // Snapshots older than this are pruned (0 = never expire by age)
+    #[serde(default = "default_backup_max_age_days")]
+    pub backup_max_age_days: u32,
+    // Extra global hotkeys on top of global_hotkey (which toggles the popup)
+    #[serde(default)]
+    pub global_actions: Vec<GlobalHotkey>,
+    // Hold-to-peek for global_hotkey
+    #[serde(default)]
+    pub global_hotkey_peek: bool,
+    // Follow window focus in the background (see focus_watcher)
+    #[serde(default)]
+    pub watch_focus: bool,
+}
+
+impl Settings {
+    // Every global hotkey with its action, global_hotkey first
+    pub fn global_bindings(&self) -> Vec<GlobalHotkey> {
+        let mut bindings = vec![GlobalHotkey {
+            hotkey: self.global_hotkey.clone(),
+            action: GlobalAction::TogglePopup,
+            peek: self.global_hotkey_peek,
+        }];
+        bindings.extend(self.global_actions.iter().cloned());
+        bindings
+    }
 }
 
-fn default_settings() -> Settings {
+pub fn default_settings() -> Settings {
     Settings {
         global_hotkey: "Ctrl+Shift+Alt+K".to_string(),
         always_on_top: true,
@@ -106,93 +273,394 @@ fn default_settings() -> Settings {
         show_app_name_in_dropdown: default_show_app_name_in_dropdown(),
         show_all_lists: default_show_all_lists(),
         defaults_seed_version: default_defaults_seed_version(),
+        backup_max_count: default_backup_max_count(),
+        backup_max_age_days: default_backup_max_age_days(),
+        global_actions: Vec::new(),
+        global_hotkey_peek: false,
+        watch_focus: false,
     }
 }
 
 const APP_APPLICATIONS_JSON: &str = include_str!("applications.json");
 
-fn user_applications_path() -> Result<PathBuf, String> {
+// ---------------------------------------------------------------------------
+// Schema versioning
+//
+// Every persisted file carries a top-level `schema_version`. Files written
+// before versioning existed (bare arrays, settings without the field) are
+// version 0. On load, older files are upgraded one step at a time through
+// MIGRATIONS and written back in the current format.
+//
+// To change an on-disk format: bump SCHEMA_VERSION and register one step per
+// file kind going from the old version to the new one.
+// ---------------------------------------------------------------------------
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileKind {
+    Lists,
+    Applications,
+    Settings,
+}
+
+// One upgrade step for a file kind, from `from` to `from + 1`
+struct Migration {
+    kind: FileKind,
+    from: u32,
+    apply: fn(Value) -> Result<Value, String>,
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        kind: FileKind::Lists,
+        from: 0,
+        apply: lists_v0_to_v1,
+    },
+    Migration {
+        kind: FileKind::Applications,
+        from: 0,
+        apply: applications_v0_to_v1,
+    },
+    Migration {
+        kind: FileKind::Settings,
+        from: 0,
+        apply: settings_v0_to_v1,
+    },
+];
+
+// v0 lists files were a bare array of lists
+fn lists_v0_to_v1(value: Value) -> Result<Value, String> {
+    match value {
+        Value::Array(_) => Ok(serde_json::json!({ "lists": value })),
+        _ => Err("expected an array of lists".to_string()),
+    }
+}
+
+// v0 user-applications.json was a bare array of applications
+fn applications_v0_to_v1(value: Value) -> Result<Value, String> {
+    match value {
+        Value::Array(_) => Ok(serde_json::json!({ "applications": value })),
+        _ => Err("expected an array of applications".to_string()),
+    }
+}
+
+// v0 settings relied on #[serde(default)] for fields added over time; write
+// those defaults out explicitly so the file is complete.
+fn settings_v0_to_v1(value: Value) -> Result<Value, String> {
+    let Value::Object(mut settings) = value else {
+        return Err("expected a settings object".to_string());
+    };
+    let Value::Object(defaults) =
+        serde_json::to_value(default_settings()).map_err(|e| e.to_string())?
+    else {
+        return Err("default settings did not serialize to an object".to_string());
+    };
+    for (key, default) in defaults {
+        settings.entry(key).or_insert(default);
+    }
+    Ok(Value::Object(settings))
+}
+
+fn schema_version_of(value: &Value) -> u32 {
+    value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .map(|v| v as u32)
+        .unwrap_or(0)
+}
+
+// Run every registered step needed to bring `value` up to SCHEMA_VERSION
+fn migrate(kind: FileKind, mut value: Value, path: &Path) -> Result<Value, StorageError> {
+    let mut version = schema_version_of(&value);
+    if version > SCHEMA_VERSION {
+        return Err(StorageError::validation(format!(
+            "{} uses schema version {version}, newer than this app supports ({SCHEMA_VERSION})",
+            path.display()
+        )));
+    }
+    while version < SCHEMA_VERSION {
+        let step = MIGRATIONS
+            .iter()
+            .find(|m| m.kind == kind && m.from == version)
+            .ok_or_else(|| {
+                StorageError::validation(format!(
+                    "No migration registered for {kind:?} files from schema version {version}"
+                ))
+            })?;
+        value = (step.apply)(value).map_err(|message| {
+            StorageError::validation(format!(
+                "Failed to migrate {} from schema version {version}: {message}",
+                path.display()
+            ))
+        })?;
+        version += 1;
+        if let Value::Object(map) = &mut value {
+            map.insert("schema_version".to_string(), Value::from(version));
+        }
+    }
+    Ok(value)
+}
+
+// Read a versioned file, upgrading (and re-saving) it if it was written with
+// an older schema
+fn read_versioned<T: Serialize + DeserializeOwned>(
+    path: &Path,
+    kind: FileKind,
+) -> Result<T, StorageError> {
+    let (parsed, migrated) = parse_versioned(path, kind)?;
+    if migrated {
+        // Snapshot the pre-migration files before rewriting this one
+        backup_if_due();
+        write_json(path, &parsed)?;
+    }
+    Ok(parsed)
+}
+
+// Parse a versioned file, upgrading an older schema in memory only. Also
+// returns whether it was upgraded. Current files are parsed straight from the
+// text so parse errors keep their line/column.
+fn parse_versioned<T: DeserializeOwned>(
+    path: &Path,
+    kind: FileKind,
+) -> Result<(T, bool), StorageError> {
+    let contents = fs::read_to_string(path).map_err(|e| StorageError::io(path, e))?;
+    let value: Value = serde_json::from_str(&contents).map_err(|e| StorageError::parse(path, e))?;
+    if schema_version_of(&value) == SCHEMA_VERSION {
+        let parsed = serde_json::from_str(&contents).map_err(|e| StorageError::parse(path, e))?;
+        return Ok((parsed, false));
+    }
+
+    let migrated = migrate(kind, value, path)?;
+    let parsed = serde_json::from_value(migrated).map_err(|e| StorageError::parse(path, e))?;
+    Ok((parsed, true))
+}
+
+// On-disk layout of lists/<app_id>.json
+#[derive(Serialize, Deserialize)]
+struct ListsFile<'a> {
+    schema_version: u32,
+    lists: Cow<'a, [ShortcutList]>,
+}
+
+// On-disk layout of user-applications.json and applications.json
+#[derive(Serialize, Deserialize)]
+struct ApplicationsFile<'a> {
+    schema_version: u32,
+    applications: Cow<'a, [Application]>,
+}
+
+// On-disk layout of settings.json: the settings fields plus schema_version
+#[derive(Serialize, Deserialize)]
+struct SettingsFile<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    settings: Cow<'a, Settings>,
+}
+
+// UTC timestamp used in backup ids and quarantined file names. Sorts
+// chronologically as a plain string.
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%3fZ";
+
+fn file_timestamp() -> String {
+    chrono::Utc::now().format(TIMESTAMP_FORMAT).to_string()
+}
+
+// Serialize a value as pretty JSON and write it to `path`
+pub(crate) fn write_json<T: Serialize + ?Sized>(
+    path: &Path,
+    value: &T,
+) -> Result<(), StorageError> {
+    let json =
+        serde_json::to_string_pretty(value).map_err(|e| StorageError::validation(e.to_string()))?;
+    write_atomic(path, json.as_bytes())
+}
+
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+// Sibling temp file used while writing `path`. It lives in the same directory
+// so the final rename never crosses filesystems.
+fn temp_path_for(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let counter = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!(".{file_name}.{}.{counter}.tmp", std::process::id()))
+}
+
+// Crash-safe write: write to a temp file, fsync it, then rename it over the
+// live file. A crash at any point leaves either the old or the new contents,
+// never a truncated file.
+pub(crate) fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), StorageError> {
+    write_atomic_with(path, contents, |from, to| fs::rename(from, to))
+}
+
+// write_atomic with the final rename passed in, so tests can make it fail
+fn write_atomic_with(
+    path: &Path,
+    contents: &[u8],
+    rename: impl FnOnce(&Path, &Path) -> std::io::Result<()>,
+) -> Result<(), StorageError> {
+    let tmp_path = temp_path_for(path);
+
+    let written = fs::File::create(&tmp_path).and_then(|mut file| {
+        file.write_all(contents)?;
+        file.sync_all()
+    });
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(StorageError::io(&tmp_path, e));
+    }
+
+    if let Err(e) = rename(&tmp_path, path) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(StorageError::io(path, e));
+    }
+
+    sync_parent_dir(path);
+    remove_stale_temp_files(path);
+    Ok(())
+}
+
+// Delete temp files for `path` left behind by a process that crashed
+// mid-write. Temp files of this process may belong to a write still in
+// progress on another thread, so they are left alone.
+fn remove_stale_temp_files(path: &Path) {
+    let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return;
+    };
+    let prefix = format!(".{}.", file_name.to_string_lossy());
+    let own_prefix = format!("{prefix}{}.", std::process::id());
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with(&prefix) && name.ends_with(".tmp") && !name.starts_with(&own_prefix) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+// Persist the rename itself. Directories can't be opened for syncing on
+// Windows, where the rename is already durable once it returns.
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) {
+    if let Some(parent) = path.parent() {
+        if let Ok(dir) = fs::File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent_dir(_path: &Path) {}
+
+fn create_dir(dir: &Path) -> Result<(), StorageError> {
+    fs::create_dir_all(dir).map_err(|e| StorageError::io(dir, e))
+}
+
+fn user_applications_path() -> Result<PathBuf, StorageError> {
     let data_dir = get_data_dir()?;
     Ok(data_dir.join("user-applications.json"))
 }
 
-pub fn load_user_applications() -> Result<Vec<Application>, String> {
+pub fn load_user_applications() -> Result<Vec<Application>, StorageError> {
     let path = user_applications_path()?;
     if path.exists() {
-        let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
-        serde_json::from_str(&contents).map_err(|e| e.to_string())
+        let file: ApplicationsFile = read_versioned(&path, FileKind::Applications)?;
+        Ok(file.applications.into_owned())
     } else {
         Ok(Vec::new())
     }
 }
 
-pub fn save_user_applications(apps: &Vec<Application>) -> Result<(), String> {
+pub fn save_user_applications(apps: &Vec<Application>) -> Result<(), StorageError> {
+    backup_if_due();
     let path = user_applications_path()?;
-    let json = serde_json::to_string_pretty(apps).map_err(|e| e.to_string())?;
-    fs::write(path, json).map_err(|e| e.to_string())
+    write_json(
+        &path,
+        &ApplicationsFile {
+            schema_version: SCHEMA_VERSION,
+            applications: Cow::Borrowed(apps),
+        },
+    )
 }
 
 // Get the app data directory
-pub fn get_data_dir() -> Result<PathBuf, String> {
+pub fn get_data_dir() -> Result<PathBuf, StorageError> {
+    #[cfg(test)]
+    if let Some(dir) = tests::data_dir_override() {
+        create_dir(&dir)?;
+        return Ok(dir);
+    }
     if let Some(proj_dirs) = ProjectDirs::from("com", "AWilliamson88", "WillShortcut") {
         let data_dir = proj_dirs.data_dir().to_path_buf();
-        fs::create_dir_all(&data_dir).map_err(|e| e.to_string())?;
+        create_dir(&data_dir)?;
         Ok(data_dir)
     } else {
-        Err("Could not determine data directory".to_string())
+        Err(StorageError::MissingDataDir)
     }
 }
 
 // Get the app config directory (for settings, small prefs)
-pub fn get_config_dir() -> Result<PathBuf, String> {
+pub fn get_config_dir() -> Result<PathBuf, StorageError> {
+    #[cfg(test)]
+    if let Some(dir) = tests::data_dir_override() {
+        let dir = dir.join("config");
+        create_dir(&dir)?;
+        return Ok(dir);
+    }
     if let Some(proj_dirs) = ProjectDirs::from("com", "AWilliamson88", "WillShortcut") {
         let config_dir = proj_dirs.config_dir().to_path_buf();
-        fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
+        create_dir(&config_dir)?;
         Ok(config_dir)
     } else {
-        Err("Could not determine config directory".to_string())
+        Err(StorageError::MissingDataDir)
     }
 }
 
 // Get the directory for list files
-fn lists_dir() -> Result<PathBuf, String> {
+fn lists_dir() -> Result<PathBuf, StorageError> {
     let mut dir = get_data_dir()?;
     dir.push("lists");
-    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    create_dir(&dir)?;
     Ok(dir)
 }
 
 // Get the path to the lists file for a specific application
-fn lists_file_path(app_id: &str) -> Result<PathBuf, String> {
+fn lists_file_path(app_id: &str) -> Result<PathBuf, StorageError> {
     let mut path = lists_dir()?;
     path.push(format!("{app_id}.json"));
     Ok(path)
 }
 
 // Load lists for a specific application
-pub fn load_lists_for_application(app_id: &str) -> Result<Vec<ShortcutList>, String> {
+pub fn load_lists_for_application(app_id: &str) -> Result<Vec<ShortcutList>, StorageError> {
     let path = lists_file_path(app_id)?;
     if !path.exists() {
         return Ok(Vec::new());
     }
-    let contents = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    let mut lists: Vec<ShortcutList> =
-        serde_json::from_str(&contents).map_err(|e| e.to_string())?;
+    let file: ListsFile = read_versioned(&path, FileKind::Lists)?;
+    let mut lists = file.lists.into_owned();
     for list in &mut lists {
         list.application_id = app_id.to_string();
     }
     Ok(lists)
 }
 
+fn settings_path() -> Result<PathBuf, StorageError> {
+    Ok(get_config_dir()?.join("settings.json"))
+}
+
 // Load settings from file
-pub fn load_settings() -> Result<Settings, String> {
-    let config_dir = get_config_dir()?;
-    let settings_path = config_dir.join("settings.json");
+pub fn load_settings() -> Result<Settings, StorageError> {
+    let settings_path = settings_path()?;
 
     if settings_path.exists() {
-        let contents = fs::read_to_string(&settings_path).map_err(|e| e.to_string())?;
-        let settings: Settings = serde_json::from_str(&contents).map_err(|e| e.to_string())?;
-        return Ok(settings);
+        let file: SettingsFile = read_versioned(&settings_path, FileKind::Settings)?;
+        return Ok(file.settings.into_owned());
     }
 
     // Nothing on disk → use defaults
@@ -200,26 +668,129 @@ pub fn load_settings() -> Result<Settings, String> {
 }
 
 // Save settings to file
-pub fn save_settings(settings: &Settings) -> Result<(), String> {
-    let config_dir = get_config_dir()?;
-    let settings_path = config_dir.join("settings.json");
-    let json = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
-    fs::write(settings_path, json).map_err(|e| e.to_string())
+pub fn save_settings(settings: &Settings) -> Result<(), StorageError> {
+    backup_if_due();
+    write_json(
+        &settings_path()?,
+        &SettingsFile {
+            schema_version: SCHEMA_VERSION,
+            settings: Cow::Borrowed(settings),
+        },
+    )
 }
 
-// Load all lists
-pub fn load_lists() -> Result<Vec<ShortcutList>, String> {
+// An application whose lists file could not be loaded
+#[derive(Debug, Serialize)]
+pub struct LoadWarning {
+    pub application_id: String,
+    // Where an unreadable file was moved to (None if it was left in place)
+    pub quarantined_to: Option<String>,
+    pub error: StorageError,
+}
+
+// Every list that loaded successfully, plus a warning per unreadable file
+#[derive(Debug, Serialize)]
+pub struct LoadedLists {
+    pub lists: Vec<ShortcutList>,
+    pub warnings: Vec<LoadWarning>,
+}
+
+// Move a lists file that can't be loaded out of the way (to
+// `<app_id>.json.corrupt-<timestamp>`) so the application starts fresh and the
+// next save doesn't overwrite it, while the original bytes are kept for repair.
+fn quarantine_lists_file(app_id: &str) -> Result<PathBuf, StorageError> {
+    let path = lists_file_path(app_id)?;
+    let target = path.with_file_name(format!("{app_id}.json.corrupt-{}", file_timestamp()));
+    fs::rename(&path, &target).map_err(|e| StorageError::io(&path, e))?;
+    Ok(target)
+}
+
+// Load all lists, skipping (and quarantining) any application whose file
+// can't be parsed or migrated instead of failing every application.
+pub fn load_lists_with_warnings() -> Result<LoadedLists, StorageError> {
     let apps = load_applications()?;
-    let mut all: Vec<ShortcutList> = Vec::new();
+    let mut loaded = LoadedLists {
+        lists: Vec::new(),
+        warnings: Vec::new(),
+    };
     for app in apps {
-        let mut app_lists = load_lists_for_application(&app.id)?;
-        all.append(&mut app_lists);
+        match load_lists_for_application(&app.id) {
+            Ok(mut app_lists) => loaded.lists.append(&mut app_lists),
+            Err(error) => {
+                // Files that are corrupt, or valid JSON with a layout or
+                // schema_version this build can't migrate, are quarantined. I/O
+                // errors (e.g. permission denied) may be transient, so the file
+                // is left alone.
+                let quarantined_to = match error {
+                    StorageError::Parse { .. } | StorageError::Validation { .. } => {
+                        match quarantine_lists_file(&app.id) {
+                            Ok(target) => Some(target.display().to_string()),
+                            Err(e) => {
+                                eprintln!("Failed to quarantine lists for {}: {e}", app.id);
+                                None
+                            }
+                        }
+                    }
+                    _ => None,
+                };
+                loaded.warnings.push(LoadWarning {
+                    application_id: app.id,
+                    quarantined_to,
+                    error,
+                });
+            }
+        }
+    }
+    Ok(loaded)
+}
+
+// Load all lists
+pub fn load_lists() -> Result<Vec<ShortcutList>, StorageError> {
+    let loaded = load_lists_with_warnings()?;
+    for warning in &loaded.warnings {
+        eprintln!(
+            "Skipped lists for {}: {}",
+            warning.application_id, warning.error
+        );
+    }
+    Ok(loaded.lists)
+}
+
+// Applications and lists as they are on disk, read without writing anything:
+// older files are upgraded in memory only, no backup is taken and lists files
+// that can't be read are skipped rather than quarantined. For background
+// threads, which leave that upkeep to the regular loads.
+pub fn read_applications_and_lists() -> Result<(Vec<Application>, Vec<ShortcutList>), StorageError>
+{
+    let user_apps_path = user_applications_path()?;
+    let user_apps = if user_apps_path.exists() {
+        let (file, _): (ApplicationsFile, bool) =
+            parse_versioned(&user_apps_path, FileKind::Applications)?;
+        file.applications.into_owned()
+    } else {
+        Vec::new()
+    };
+    let apps = with_bundled_applications(user_apps)?;
+
+    let lists_dir = get_data_dir()?.join("lists");
+    let mut lists = Vec::new();
+    for app in &apps {
+        let path = lists_dir.join(format!("{}.json", app.id));
+        if !path.exists() {
+            continue;
+        }
+        if let Ok((file, _)) = parse_versioned::<ListsFile>(&path, FileKind::Lists) {
+            lists.extend(file.lists.into_owned().into_iter().map(|mut list| {
+                list.application_id = app.id.clone();
+                list
+            }));
+        }
     }
-    Ok(all)
+    Ok((apps, lists))
 }
 
 // Save all lists
-pub fn save_lists(lists: &Vec<ShortcutList>) -> Result<(), String> {
+pub fn save_lists(lists: &Vec<ShortcutList>) -> Result<(), StorageError> {
     use std::collections::HashMap;
 
     let mut by_app: HashMap<String, Vec<ShortcutList>> = HashMap::new();
@@ -236,13 +807,21 @@ pub fn save_lists(lists: &Vec<ShortcutList>) -> Result<(), String> {
 }
 
 // Load all applications
-pub fn load_applications() -> Result<Vec<Application>, String> {
-    let mut apps: Vec<Application> =
-        serde_json::from_str(APP_APPLICATIONS_JSON).map_err(|e| e.to_string())?;
-    for user_app in load_user_applications()? {
+pub fn load_applications() -> Result<Vec<Application>, StorageError> {
+    with_bundled_applications(load_user_applications()?)
+}
+
+// The bundled applications with `user_apps` added or overriding them
+fn with_bundled_applications(
+    user_apps: Vec<Application>,
+) -> Result<Vec<Application>, StorageError> {
+    let mut apps: Vec<Application> = serde_json::from_str(APP_APPLICATIONS_JSON)
+        .map_err(|e| StorageError::parse(Path::new("applications.json (bundled)"), e))?;
+    for user_app in user_apps {
+        // An empty process_name identifies no application
         if let Some(existing) = apps
             .iter_mut()
-            .find(|a| a.process_name == user_app.process_name)
+            .find(|a| !a.process_name.is_empty() && a.process_name == user_app.process_name)
         {
             *existing = user_app; // user overrides bundled app
         } else {
@@ -253,16 +832,579 @@ pub fn load_applications() -> Result<Vec<Application>, String> {
 }
 
 // Save lists for a specific application
-pub fn save_lists_for_application(app_id: &str, lists: &Vec<ShortcutList>) -> Result<(), String> {
+pub fn save_lists_for_application(
+    app_id: &str,
+    lists: &Vec<ShortcutList>,
+) -> Result<(), StorageError> {
+    backup_if_due();
+    let path = lists_file_path(app_id)?;
+    write_json(
+        &path,
+        &ListsFile {
+            schema_version: SCHEMA_VERSION,
+            lists: Cow::Borrowed(lists),
+        },
+    )
+}
+
+// Delete an application's lists file, if it has one
+pub fn remove_lists_for_application(app_id: &str) -> Result<(), StorageError> {
     let path = lists_file_path(app_id)?;
-    let json = serde_json::to_string_pretty(lists).map_err(|e| e.to_string())?;
-    fs::write(path, json).map_err(|e| e.to_string())
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| StorageError::io(&path, e))?;
+    }
+    Ok(())
 }
 
 // Save all applications
-pub fn save_applications(apps: &Vec<Application>) -> Result<(), String> {
+pub fn save_applications(apps: &Vec<Application>) -> Result<(), StorageError> {
     let data_dir = get_data_dir()?;
     let apps_path = data_dir.join("applications.json");
-    let json = serde_json::to_string_pretty(apps).map_err(|e| e.to_string())?;
-    fs::write(apps_path, json).map_err(|e| e.to_string())
+    write_json(
+        &apps_path,
+        &ApplicationsFile {
+            schema_version: SCHEMA_VERSION,
+            applications: Cow::Borrowed(apps),
+        },
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Backups
+//
+// Each backup is a folder under `<data_dir>/backups/<id>` holding a copy of
+// `lists/`, `user-applications.json` and `settings.json`. The id is the UTC
+// creation time, so sorting ids sorts backups oldest → newest.
+// ---------------------------------------------------------------------------
+
+// Automatic backups are taken at most this often; explicit ones always run
+const BACKUP_MIN_INTERVAL_SECS: i64 = 10 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub id: String,
+    pub created_at: String,
+    pub list_file_count: usize,
+    pub size_bytes: u64,
+}
+
+fn backups_dir() -> Result<PathBuf, StorageError> {
+    let dir = get_data_dir()?.join("backups");
+    create_dir(&dir)?;
+    Ok(dir)
+}
+
+fn parse_backup_id(id: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::NaiveDateTime::parse_from_str(id, TIMESTAMP_FORMAT)
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+// Ids of every backup on disk, oldest first
+fn backup_ids() -> Result<Vec<String>, StorageError> {
+    let dir = backups_dir()?;
+    let entries = fs::read_dir(&dir).map_err(|e| StorageError::io(&dir, e))?;
+    let mut ids: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| parse_backup_id(name).is_some())
+        .collect();
+    ids.sort();
+    Ok(ids)
+}
+
+fn copy_file(from: &Path, to: &Path) -> Result<(), StorageError> {
+    let bytes = fs::read(from).map_err(|e| StorageError::io(from, e))?;
+    write_atomic(to, &bytes)
+}
+
+fn json_files_in(dir: &Path) -> Result<Vec<PathBuf>, StorageError> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = fs::read_dir(dir).map_err(|e| StorageError::io(dir, e))?;
+    Ok(entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"))
+        .collect())
+}
+
+// Snapshot the current data into a new backup folder and prune old ones
+pub fn create_backup() -> Result<BackupInfo, StorageError> {
+    let id = snapshot()?;
+    prune_backups()?;
+    backup_info(&id)
+}
+
+// Copy the current data into a new backup folder, returning its id
+fn snapshot() -> Result<String, StorageError> {
+    let id = file_timestamp();
+    let backup_dir = backups_dir()?.join(&id);
+    let backup_lists_dir = backup_dir.join("lists");
+    create_dir(&backup_lists_dir)?;
+
+    for path in json_files_in(&lists_dir()?)? {
+        if let Some(name) = path.file_name() {
+            copy_file(&path, &backup_lists_dir.join(name))?;
+        }
+    }
+
+    let user_apps = user_applications_path()?;
+    if user_apps.exists() {
+        copy_file(&user_apps, &backup_dir.join("user-applications.json"))?;
+    }
+
+    let settings = settings_path()?;
+    if settings.exists() {
+        copy_file(&settings, &backup_dir.join("settings.json"))?;
+    }
+
+    Ok(id)
+}
+
+// Take an automatic backup unless a recent one already exists. Failures are
+// logged rather than returned so a backup problem never blocks a save.
+fn backup_if_due() {
+    let latest = backup_ids()
+        .ok()
+        .and_then(|ids| ids.last().and_then(|id| parse_backup_id(id)));
+    if let Some(latest) = latest {
+        let age = chrono::Utc::now() - latest;
+        if age.num_seconds() < BACKUP_MIN_INTERVAL_SECS {
+            return;
+        }
+    }
+    if let Err(e) = create_backup() {
+        eprintln!("Failed to create automatic backup: {e}");
+    }
+}
+
+// Remove backups beyond the configured count or age. The newest backup is
+// always kept.
+fn prune_backups() -> Result<(), StorageError> {
+    let settings = load_settings().unwrap_or_else(|_| default_settings());
+    let max_count = settings.backup_max_count.max(1) as usize;
+    let max_age = chrono::Duration::days(settings.backup_max_age_days as i64);
+    let now = chrono::Utc::now();
+
+    let mut ids = backup_ids()?;
+    ids.pop(); // newest
+    let keep_from = ids.len().saturating_sub(max_count - 1);
+
+    for (index, id) in ids.iter().enumerate() {
+        let too_many = index < keep_from;
+        let too_old = settings.backup_max_age_days > 0
+            && parse_backup_id(id).is_some_and(|created| now - created > max_age);
+        if too_many || too_old {
+            let dir = backups_dir()?.join(id);
+            fs::remove_dir_all(&dir).map_err(|e| StorageError::io(&dir, e))?;
+        }
+    }
+    Ok(())
+}
+
+fn backup_info(id: &str) -> Result<BackupInfo, StorageError> {
+    let created_at = parse_backup_id(id)
+        .ok_or_else(|| StorageError::validation(format!("Unknown backup id: {id}")))?;
+    let backup_dir = backups_dir()?.join(id);
+    if !backup_dir.is_dir() {
+        return Err(StorageError::validation(format!("Unknown backup id: {id}")));
+    }
+
+    let list_files = json_files_in(&backup_dir.join("lists"))?;
+    let mut files = list_files.clone();
+    files.push(backup_dir.join("user-applications.json"));
+    files.push(backup_dir.join("settings.json"));
+    let size_bytes = files
+        .iter()
+        .filter_map(|path| fs::metadata(path).ok())
+        .map(|meta| meta.len())
+        .sum();
+
+    Ok(BackupInfo {
+        id: id.to_string(),
+        created_at: created_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        list_file_count: list_files.len(),
+        size_bytes,
+    })
+}
+
+// List all backups, newest first
+pub fn list_backups() -> Result<Vec<BackupInfo>, StorageError> {
+    backup_ids()?
+        .iter()
+        .rev()
+        .map(|id| backup_info(id))
+        .collect()
+}
+
+// Replace the current data with the contents of a backup, so afterwards the
+// data files are exactly the ones the backup holds: files that didn't exist
+// when it was taken are removed. The current state is snapshotted first so a
+// restore can itself be undone; pruning waits until the end so the backup
+// being restored can't be removed mid-restore.
+pub fn restore_backup(id: &str) -> Result<(), StorageError> {
+    // Validates the id (and rejects anything that isn't a backup folder name)
+    backup_info(id)?;
+    let backup_dir = backups_dir()?.join(id);
+
+    snapshot()?;
+
+    // Mirror lists/: restore every snapshotted file and drop files that
+    // didn't exist when the backup was taken.
+    let live_lists_dir = lists_dir()?;
+    let backup_lists = json_files_in(&backup_dir.join("lists"))?;
+    for path in json_files_in(&live_lists_dir)? {
+        let in_backup = path
+            .file_name()
+            .is_some_and(|name| backup_lists.iter().any(|b| b.file_name() == Some(name)));
+        if !in_backup {
+            fs::remove_file(&path).map_err(|e| StorageError::io(&path, e))?;
+        }
+    }
+    for path in &backup_lists {
+        if let Some(name) = path.file_name() {
+            copy_file(path, &live_lists_dir.join(name))?;
+        }
+    }
+
+    restore_file(
+        &backup_dir.join("user-applications.json"),
+        &user_applications_path()?,
+    )?;
+    restore_file(&backup_dir.join("settings.json"), &settings_path()?)?;
+
+    prune_backups()
+}
+
+// Copy a snapshotted file back, or remove the live one if the backup has none
+fn restore_file(backup: &Path, live: &Path) -> Result<(), StorageError> {
+    if backup.exists() {
+        copy_file(backup, live)
+    } else if live.exists() {
+        fs::remove_file(live).map_err(|e| StorageError::io(live, e))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    thread_local! {
+        // Data directory for storage calls made from the current test thread
+        static DATA_DIR: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+    }
+
+    pub(crate) fn data_dir_override() -> Option<PathBuf> {
+        DATA_DIR.with(|dir| dir.borrow().clone())
+    }
+
+    // Point this thread's storage calls at a fresh scratch directory
+    pub(crate) fn use_scratch_data_dir(name: &str) -> PathBuf {
+        let dir = scratch_dir(name);
+        DATA_DIR.with(|d| *d.borrow_mut() = Some(dir.clone()));
+        dir
+    }
+
+    // A fresh, empty directory under the system temp dir
+    pub(crate) fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "will-shortcut-{name}-{}-{}",
+            std::process::id(),
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn failed_rename_keeps_the_original_and_removes_the_temp_file() {
+        let dir = scratch_dir("rename-fails");
+        let path = dir.join("settings.json");
+        write_atomic(&path, b"old").unwrap();
+
+        let result = write_atomic_with(&path, b"new", |_, _| {
+            Err(std::io::Error::other("simulated crash before rename"))
+        });
+
+        assert!(matches!(result, Err(StorageError::Io { .. })));
+        assert_eq!(fs::read(&path).unwrap(), b"old");
+        assert_eq!(file_names(&dir), vec!["settings.json"]);
+    }
+
+    #[test]
+    fn interrupted_write_leaves_the_original_and_is_cleaned_up_next_time() {
+        let dir = scratch_dir("interrupted");
+        let path = dir.join("app.json");
+        write_atomic(&path, b"old").unwrap();
+
+        // What a process killed between writing and renaming leaves behind
+        let stale = dir.join(".app.json.999999999.0.tmp");
+        fs::write(&stale, b"truncat").unwrap();
+        // Temp files of other files in the directory are not ours to remove
+        let other = dir.join(".other.json.999999999.0.tmp");
+        fs::write(&other, b"other").unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), b"old");
+
+        write_atomic(&path, b"new").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+        assert!(!stale.exists());
+        assert!(other.exists());
+    }
+
+    #[test]
+    fn successful_write_replaces_contents_without_leftovers() {
+        let dir = scratch_dir("replace");
+        let path = dir.join("lists.json");
+        write_atomic(&path, b"first").unwrap();
+        write_atomic(&path, b"second").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"second");
+        assert_eq!(file_names(&dir), vec!["lists.json"]);
+    }
+
+    fn read_back(path: &Path) -> Value {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn lists_v0_is_wrapped_in_an_object() {
+        let v0 = serde_json::json!([{ "id": "l1" }]);
+        assert_eq!(
+            lists_v0_to_v1(v0).unwrap(),
+            serde_json::json!({ "lists": [{ "id": "l1" }] })
+        );
+        assert!(lists_v0_to_v1(serde_json::json!({ "lists": [] })).is_err());
+    }
+
+    #[test]
+    fn applications_v0_is_wrapped_in_an_object() {
+        let v0 = serde_json::json!([{ "id": "app" }]);
+        assert_eq!(
+            applications_v0_to_v1(v0).unwrap(),
+            serde_json::json!({ "applications": [{ "id": "app" }] })
+        );
+        assert!(applications_v0_to_v1(serde_json::json!("nope")).is_err());
+    }
+
+    #[test]
+    fn settings_v0_gets_missing_defaults_and_keeps_its_values() {
+        let v0 = serde_json::json!({
+            "global_hotkey": "Ctrl+Alt+J",
+            "always_on_top": false,
+            "run_on_startup": false,
+        });
+        let v1 = settings_v0_to_v1(v0).unwrap();
+        assert_eq!(v1["global_hotkey"], "Ctrl+Alt+J");
+        assert_eq!(v1["always_on_top"], false);
+        assert_eq!(v1["window_position"], "BottomRight");
+        assert_eq!(v1["backup_max_count"], default_backup_max_count());
+        assert!(settings_v0_to_v1(serde_json::json!([])).is_err());
+    }
+
+    #[test]
+    fn migrate_sets_the_current_version() {
+        let migrated = migrate(
+            FileKind::Lists,
+            serde_json::json!([]),
+            Path::new("lists/app.json"),
+        )
+        .unwrap();
+        assert_eq!(schema_version_of(&migrated), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn future_schema_version_is_rejected_and_the_file_left_alone() {
+        let dir = use_scratch_data_dir("future-version");
+        let path = dir.join("user-applications.json");
+        let future = serde_json::json!({
+            "schema_version": SCHEMA_VERSION + 1,
+            "applications": [],
+        });
+        fs::write(&path, future.to_string()).unwrap();
+
+        let result = read_versioned::<ApplicationsFile>(&path, FileKind::Applications);
+
+        assert!(matches!(result, Err(StorageError::Validation { .. })));
+        assert_eq!(read_back(&path), future);
+    }
+
+    #[test]
+    fn migrated_files_are_rewritten_with_the_current_version() {
+        let dir = use_scratch_data_dir("rewrite");
+        let lists_path = dir.join("lists.json");
+        fs::write(
+            &lists_path,
+            r#"[{"id":"l1","name":"General","application_id":"app","shortcuts":[],
+                "created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-01T00:00:00Z"}]"#,
+        )
+        .unwrap();
+        let apps_path = dir.join("user-applications.json");
+        fs::write(
+            &apps_path,
+            r#"[{"id":"app","name":"App","process_name":"app.exe","detection_name":"App",
+                "icon":null,"last_used_list_id":null}]"#,
+        )
+        .unwrap();
+        let settings_path = dir.join("settings.json");
+        fs::write(
+            &settings_path,
+            r#"{"global_hotkey":"Ctrl+Alt+J","always_on_top":true,"run_on_startup":false}"#,
+        )
+        .unwrap();
+
+        let lists: ListsFile = read_versioned(&lists_path, FileKind::Lists).unwrap();
+        assert_eq!(lists.lists[0].name, "General");
+        let apps: ApplicationsFile = read_versioned(&apps_path, FileKind::Applications).unwrap();
+        assert_eq!(apps.applications[0].process_name, "app.exe");
+        let settings: SettingsFile = read_versioned(&settings_path, FileKind::Settings).unwrap();
+        assert_eq!(settings.settings.global_hotkey, "Ctrl+Alt+J");
+
+        for path in [&lists_path, &apps_path, &settings_path] {
+            assert_eq!(schema_version_of(&read_back(path)), SCHEMA_VERSION);
+        }
+        assert!(read_back(&settings_path).get("window_position").is_some());
+    }
+
+    fn sample_list(app_id: &str, name: &str) -> ShortcutList {
+        ShortcutList {
+            id: format!("{app_id}-{name}"),
+            name: name.to_string(),
+            application_id: app_id.to_string(),
+            shortcuts: Vec::new(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: "2025-01-01T00:00:00Z".to_string(),
+            match_rules: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn user_apps_without_a_process_name_override_nothing() {
+        use crate::defaults::{WEB_APPS_APP_ID, WEB_APPS_PROCESS_NAME};
+
+        use_scratch_data_dir("user-apps-empty-process");
+        let mut app = load_applications()
+            .unwrap()
+            .into_iter()
+            .find(|a| a.id == WEB_APPS_APP_ID)
+            .unwrap();
+        assert_eq!(app.process_name, WEB_APPS_PROCESS_NAME);
+
+        app.id = "user-app".to_string();
+        app.process_name = String::new();
+        save_user_applications(&vec![app]).unwrap();
+        let apps = load_applications().unwrap();
+        assert!(apps.iter().any(|a| a.id == WEB_APPS_APP_ID));
+        assert!(apps.iter().any(|a| a.id == "user-app"));
+    }
+
+    // Every file under `dir` with its contents
+    fn tree(dir: &Path) -> Vec<(PathBuf, Vec<u8>)> {
+        let mut files = Vec::new();
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                files.extend(tree(&path));
+            } else {
+                let contents = fs::read(&path).unwrap();
+                files.push((path, contents));
+            }
+        }
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn background_reads_leave_the_data_dir_alone() {
+        let dir = use_scratch_data_dir("read-only");
+        fs::write(
+            dir.join("user-applications.json"),
+            r#"[{"id":"app","name":"App","process_name":"app.exe","detection_name":"App",
+                "icon":null,"last_used_list_id":null}]"#,
+        )
+        .unwrap();
+        // An old-schema lists file and a corrupt one
+        fs::create_dir_all(dir.join("lists")).unwrap();
+        fs::write(
+            dir.join("lists").join("app.json"),
+            r#"[{"id":"l1","name":"General","application_id":"app","shortcuts":[],
+                "created_at":"2025-01-01T00:00:00Z","updated_at":"2025-01-01T00:00:00Z"}]"#,
+        )
+        .unwrap();
+        let bundled_id = load_applications().unwrap()[0].id.clone();
+        fs::write(
+            dir.join("lists").join(format!("{bundled_id}.json")),
+            "{ not json",
+        )
+        .unwrap();
+        let before = tree(&dir);
+
+        let (apps, lists) = read_applications_and_lists().unwrap();
+        assert!(apps.iter().any(|a| a.id == "app"));
+        assert_eq!(lists.len(), 1);
+        assert_eq!(lists[0].name, "General");
+        assert_eq!(lists[0].application_id, "app");
+        assert_eq!(tree(&dir), before);
+    }
+
+    #[test]
+    fn lists_files_that_cannot_be_migrated_are_quarantined() {
+        use_scratch_data_dir("quarantine-unmigratable");
+        let app_id = load_applications().unwrap()[0].id.clone();
+        let path = lists_file_path(&app_id).unwrap();
+        let future = serde_json::json!({ "schema_version": SCHEMA_VERSION + 1, "lists": [] });
+        fs::write(&path, future.to_string()).unwrap();
+
+        let loaded = load_lists_with_warnings().unwrap();
+        assert_eq!(loaded.warnings.len(), 1);
+        let warning = &loaded.warnings[0];
+        assert!(matches!(warning.error, StorageError::Validation { .. }));
+        let quarantined = PathBuf::from(warning.quarantined_to.as_ref().unwrap());
+        assert_eq!(read_back(&quarantined), future);
+        assert!(!path.exists());
+
+        // Saving the application's lists no longer overwrites the original
+        save_lists(&vec![sample_list(&app_id, "General")]).unwrap();
+        assert_eq!(read_back(&quarantined), future);
+    }
+
+    #[test]
+    fn restore_removes_files_the_backup_does_not_have() {
+        use_scratch_data_dir("restore-mirror");
+        save_lists_for_application("app-a", &vec![sample_list("app-a", "General")]).unwrap();
+        let backup = create_backup().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+
+        save_lists_for_application("app-b", &vec![sample_list("app-b", "General")]).unwrap();
+        save_user_applications(&Vec::new()).unwrap();
+        let mut settings = default_settings();
+        settings.global_hotkey = "Ctrl+Alt+K".to_string();
+        save_settings(&settings).unwrap();
+
+        restore_backup(&backup.id).unwrap();
+
+        assert!(lists_file_path("app-a").unwrap().exists());
+        assert!(!lists_file_path("app-b").unwrap().exists());
+        assert!(!user_applications_path().unwrap().exists());
+        assert!(!settings_path().unwrap().exists());
+        assert_eq!(
+            load_settings().unwrap().global_hotkey,
+            default_settings().global_hotkey
+        );
+    }
 }
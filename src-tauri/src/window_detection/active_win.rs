@@ -0,0 +1,36 @@
+// active_win_pos_rs: Win32 on Windows, Core Graphics on macOS, and
+// _NET_ACTIVE_WINDOW / WM_CLASS over xcb on Linux (X11 and XWayland).
+
+use super::{ActiveWindowInfo, DetectionError, WindowBounds, WindowDetector};
+
+pub struct ActiveWinDetector;
+
+impl WindowDetector for ActiveWinDetector {
+    fn name(&self) -> &'static str {
+        "x11"
+    }
+
+    fn active_window(&self) -> Result<ActiveWindowInfo, DetectionError> {
+        // The crate doesn't say why it failed
+        let window = active_win_pos_rs::get_active_window()
+            .map_err(|()| DetectionError::backend(self.name(), "no active window reported"))?;
+        let position = window.position;
+        let process_path = window.process_path.to_string_lossy().into_owned();
+        Ok(ActiveWindowInfo {
+            // On Linux the crate reports the WM_CLASS class as the app name
+            wm_class: cfg!(target_os = "linux").then(|| window.app_name.clone()),
+            app_name: window.app_name,
+            title: window.title,
+            process_path: (!process_path.is_empty()).then_some(process_path),
+            pid: u32::try_from(window.process_id)
+                .ok()
+                .filter(|&pid| pid != 0),
+            bounds: Some(WindowBounds {
+                x: position.x as i32,
+                y: position.y as i32,
+                width: position.width.max(0.0) as u32,
+                height: position.height.max(0.0) as u32,
+            }),
+        })
+    }
+}
@@ -0,0 +1,40 @@
+// A backend that reports a fixed window, for tests and for trying out
+// application matching without switching windows. Selected with
+// WILL_SHORTCUT_WINDOW_DETECTOR=fake; the window comes from
+// WILL_SHORTCUT_FAKE_APP, _TITLE, _WM_CLASS and _PROCESS_PATH.
+
+use super::{ActiveWindowInfo, DetectionError, WindowDetector};
+
+pub struct FakeDetector {
+    window: Option<ActiveWindowInfo>,
+}
+
+impl FakeDetector {
+    // `None` behaves like a desktop with nothing focused
+    pub fn new(window: Option<ActiveWindowInfo>) -> Self {
+        FakeDetector { window }
+    }
+
+    pub fn from_env(env: &impl Fn(&str) -> Option<String>) -> Self {
+        let window = env("WILL_SHORTCUT_FAKE_APP").map(|app_name| ActiveWindowInfo {
+            app_name,
+            title: env("WILL_SHORTCUT_FAKE_TITLE").unwrap_or_default(),
+            wm_class: env("WILL_SHORTCUT_FAKE_WM_CLASS"),
+            process_path: env("WILL_SHORTCUT_FAKE_PROCESS_PATH"),
+            ..ActiveWindowInfo::default()
+        });
+        FakeDetector::new(window)
+    }
+}
+
+impl WindowDetector for FakeDetector {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    fn active_window(&self) -> Result<ActiveWindowInfo, DetectionError> {
+        self.window.clone().ok_or(DetectionError::NoActiveWindow {
+            backend: self.name(),
+        })
+    }
+}
@@ -0,0 +1,170 @@
+// GNOME Shell over D-Bus. Mutter has no public API for the focused window on
+// Wayland (and Shell.Eval is locked down since GNOME 41), so this relies on
+// the "Window Calls" extension, which exports the window list on the session
+// bus.
+
+use super::{ActiveWindowInfo, DetectionError, WindowBounds, WindowDetector};
+use serde::Deserialize;
+use zbus::blocking::Connection;
+
+const DESTINATION: &str = "org.gnome.Shell";
+const PATH: &str = "/org/gnome/Shell/Extensions/Windows";
+const INTERFACE: &str = "org.gnome.Shell.Extensions.Windows";
+
+// One entry of the extension's List reply (a JSON string)
+#[derive(Deserialize)]
+struct ShellWindow {
+    id: u32,
+    #[serde(default)]
+    wm_class: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    pid: Option<u32>,
+    #[serde(default)]
+    focus: bool,
+}
+
+// The extension's Details reply; only the geometry is used
+#[derive(Deserialize)]
+struct ShellWindowDetails {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+pub struct GnomeDetector;
+
+impl GnomeDetector {
+    pub fn is_session(env: &impl Fn(&str) -> Option<String>) -> bool {
+        let gnome = env("XDG_CURRENT_DESKTOP")
+            .is_some_and(|desktop| desktop.split(':').any(|d| d.eq_ignore_ascii_case("gnome")));
+        gnome && env("WAYLAND_DISPLAY").is_some()
+    }
+
+    fn call(&self, connection: &Connection, method: &str, id: Option<u32>) -> zbus::Result<String> {
+        let reply = match id {
+            Some(id) => {
+                connection.call_method(Some(DESTINATION), PATH, Some(INTERFACE), method, &(id,))?
+            }
+            None => {
+                connection.call_method(Some(DESTINATION), PATH, Some(INTERFACE), method, &())?
+            }
+        };
+        reply.body().deserialize::<String>()
+    }
+}
+
+impl WindowDetector for GnomeDetector {
+    fn name(&self) -> &'static str {
+        "gnome"
+    }
+
+    fn active_window(&self) -> Result<ActiveWindowInfo, DetectionError> {
+        let error = |e: zbus::Error| {
+            DetectionError::backend(
+                self.name(),
+                format!("{e} (is the Window Calls extension installed?)"),
+            )
+        };
+        let connection = Connection::session().map_err(error)?;
+        let list = self.call(&connection, "List", None).map_err(error)?;
+        let window = focused_window(&list)
+            .map_err(|e| DetectionError::backend(self.name(), e))?
+            .ok_or(DetectionError::NoActiveWindow {
+                backend: self.name(),
+            })?;
+
+        // Older versions of the extension leave the title out of List
+        let title = match window.title {
+            Some(title) => title,
+            None => self
+                .call(&connection, "GetTitle", Some(window.id))
+                .unwrap_or_default(),
+        };
+        let bounds = self
+            .call(&connection, "Details", Some(window.id))
+            .ok()
+            .and_then(|details| parse_bounds(&details));
+        Ok(ActiveWindowInfo {
+            app_name: window.wm_class.clone().unwrap_or_default(),
+            title,
+            process_path: window.pid.and_then(super::process_path),
+            pid: window.pid,
+            bounds,
+            wm_class: window.wm_class,
+        })
+    }
+}
+
+// The focused entry of a List reply; None when no window has focus (the
+// desktop, the overview or an empty workspace)
+fn focused_window(list: &str) -> serde_json::Result<Option<ShellWindow>> {
+    let windows: Vec<ShellWindow> = serde_json::from_str(list)?;
+    Ok(windows.into_iter().find(|w| w.focus))
+}
+
+// Window geometry from a Details reply
+fn parse_bounds(details: &str) -> Option<WindowBounds> {
+    let details: ShellWindowDetails = serde_json::from_str(details).ok()?;
+    Some(WindowBounds {
+        x: details.x,
+        y: details.y,
+        width: details.width,
+        height: details.height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The extension's List reply with Firefox focused
+    const LIST: &str = r#"[{"in_current_workspace":true,"wm_class":"org.gnome.Nautilus","wm_class_instance":"org.gnome.Nautilus","title":"Downloads","pid":2871,"id":2739284650,"frame_type":0,"window_type":0,"focus":false},{"in_current_workspace":true,"wm_class":"firefox","wm_class_instance":"firefox","title":"Inbox - Mozilla Firefox","pid":3120,"id":2739284652,"frame_type":0,"window_type":0,"focus":true}]"#;
+
+    // List from versions of the extension without titles
+    const LIST_WITHOUT_TITLES: &str =
+        r#"[{"wm_class":"gnome-terminal-server","pid":4410,"id":1170452310,"focus":true}]"#;
+
+    // The extension's Details reply for the Firefox window
+    const DETAILS: &str = r#"{"wm_class":"firefox","wm_class_instance":"firefox","pid":3120,"id":2739284652,"width":1280,"height":992,"x":640,"y":32,"maximized":0,"focus":true,"in_current_workspace":true,"moveable":true,"resizeable":true,"canclose":true,"canmaximize":true,"canminimize":true,"canshade":false,"display":{},"frame_type":0,"window_type":0,"layer":2,"monitor":0,"role":"browser","title":"Inbox - Mozilla Firefox"}"#;
+
+    #[test]
+    fn finds_the_focused_window() {
+        let window = focused_window(LIST).unwrap().unwrap();
+        assert_eq!(window.id, 2739284652);
+        assert_eq!(window.wm_class.as_deref(), Some("firefox"));
+        assert_eq!(window.title.as_deref(), Some("Inbox - Mozilla Firefox"));
+        assert_eq!(window.pid, Some(3120));
+    }
+
+    #[test]
+    fn missing_titles_are_left_for_get_title() {
+        let window = focused_window(LIST_WITHOUT_TITLES).unwrap().unwrap();
+        assert_eq!(window.wm_class.as_deref(), Some("gnome-terminal-server"));
+        assert_eq!(window.title, None);
+    }
+
+    #[test]
+    fn nothing_focused() {
+        assert!(focused_window("[]").unwrap().is_none());
+        let unfocused = LIST.replace(r#""focus":true"#, r#""focus":false"#);
+        assert!(focused_window(&unfocused).unwrap().is_none());
+    }
+
+    #[test]
+    fn malformed_list_is_an_error() {
+        assert!(focused_window("").is_err());
+    }
+
+    #[test]
+    fn reads_bounds_from_details() {
+        let bounds = parse_bounds(DETAILS).unwrap();
+        assert_eq!(
+            (bounds.x, bounds.y, bounds.width, bounds.height),
+            (640, 32, 1280, 992)
+        );
+        assert!(parse_bounds("{}").is_none());
+    }
+}
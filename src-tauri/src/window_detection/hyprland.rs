@@ -0,0 +1,160 @@
+// Hyprland over its request socket: "j/activewindow" returns the focused
+// window as JSON, or {} when nothing is focused.
+
+use super::{ActiveWindowInfo, DetectionError, WindowBounds, WindowDetector};
+use serde_json::Value;
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+pub struct HyprlandDetector {
+    socket: PathBuf,
+}
+
+impl HyprlandDetector {
+    pub fn from_env(env: &impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let signature = env("HYPRLAND_INSTANCE_SIGNATURE")?;
+        // Hyprland 0.40 moved its sockets from /tmp/hypr to the runtime dir
+        let runtime_socket = env("XDG_RUNTIME_DIR").map(|dir| {
+            PathBuf::from(dir)
+                .join("hypr")
+                .join(&signature)
+                .join(".socket.sock")
+        });
+        let socket = match runtime_socket {
+            Some(socket) if socket.exists() => socket,
+            _ => PathBuf::from("/tmp/hypr")
+                .join(&signature)
+                .join(".socket.sock"),
+        };
+        Some(HyprlandDetector { socket })
+    }
+
+    fn request(&self, command: &str) -> io::Result<String> {
+        let mut stream = UnixStream::connect(&self.socket)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.write_all(command.as_bytes())?;
+        // Hyprland closes the connection after replying
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply)?;
+        Ok(reply)
+    }
+}
+
+impl WindowDetector for HyprlandDetector {
+    fn name(&self) -> &'static str {
+        "hyprland"
+    }
+
+    fn active_window(&self) -> Result<ActiveWindowInfo, DetectionError> {
+        let reply = self
+            .request("j/activewindow")
+            .map_err(|e| DetectionError::backend(self.name(), e))?;
+        let mut window = parse_active_window(&reply)
+            .map_err(|e| DetectionError::backend(self.name(), e))?
+            .ok_or(DetectionError::NoActiveWindow {
+                backend: self.name(),
+            })?;
+        window.process_path = window.pid.and_then(super::process_path);
+        Ok(window)
+    }
+}
+
+// The window in an activewindow reply, or None for {} (nothing focused).
+// process_path is left for the caller, since it reads /proc.
+fn parse_active_window(reply: &str) -> serde_json::Result<Option<ActiveWindowInfo>> {
+    let window: Value = serde_json::from_str(reply)?;
+    let Some(app_name) = window["class"].as_str().filter(|class| !class.is_empty()) else {
+        return Ok(None);
+    };
+    let pid = window["pid"]
+        .as_u64()
+        .and_then(|pid| u32::try_from(pid).ok());
+    let bounds = match (
+        window["at"][0].as_i64(),
+        window["at"][1].as_i64(),
+        window["size"][0].as_u64(),
+        window["size"][1].as_u64(),
+    ) {
+        (Some(x), Some(y), Some(width), Some(height)) => Some(WindowBounds {
+            x: x as i32,
+            y: y as i32,
+            width: width as u32,
+            height: height as u32,
+        }),
+        _ => None,
+    };
+    Ok(Some(ActiveWindowInfo {
+        app_name: app_name.to_string(),
+        title: window["title"].as_str().unwrap_or_default().to_string(),
+        process_path: None,
+        pid,
+        bounds,
+        wm_class: Some(app_name.to_string()),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `hyprctl activewindow -j` with a kitty window focused
+    const ACTIVE_WINDOW: &str = r#"{
+    "address": "0x5612c5d3a8f0",
+    "mapped": true,
+    "hidden": false,
+    "at": [10, 52],
+    "size": [1900, 1018],
+    "workspace": {
+        "id": 1,
+        "name": "1"
+    },
+    "floating": false,
+    "pseudo": false,
+    "monitor": 0,
+    "class": "kitty",
+    "title": "nvim ~/notes.md",
+    "initialClass": "kitty",
+    "initialTitle": "kitty",
+    "pid": 48213,
+    "xwayland": false,
+    "pinned": false,
+    "fullscreen": 0,
+    "fullscreenClient": 0,
+    "grouped": [],
+    "tags": [],
+    "swallowing": "0x0",
+    "focusHistoryID": 0
+}"#;
+
+    #[test]
+    fn parses_the_focused_window() {
+        let window = parse_active_window(ACTIVE_WINDOW).unwrap().unwrap();
+        assert_eq!(window.app_name, "kitty");
+        assert_eq!(window.wm_class.as_deref(), Some("kitty"));
+        assert_eq!(window.title, "nvim ~/notes.md");
+        assert_eq!(window.pid, Some(48213));
+        assert_eq!(window.process_path, None);
+        let bounds = window.bounds.unwrap();
+        assert_eq!(
+            (bounds.x, bounds.y, bounds.width, bounds.height),
+            (10, 52, 1900, 1018)
+        );
+    }
+
+    #[test]
+    fn empty_reply_means_nothing_is_focused() {
+        assert!(parse_active_window("{}").unwrap().is_none());
+        assert!(parse_active_window(r#"{"class": "", "title": ""}"#)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn malformed_reply_is_an_error() {
+        assert!(parse_active_window("Invalid request").is_err());
+    }
+}
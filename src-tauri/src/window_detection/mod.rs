@@ -0,0 +1,308 @@
+// Active window detection.
+//
+// active_win_pos_rs covers Windows, macOS and X11 (_NET_ACTIVE_WINDOW /
+// WM_CLASS) but comes back empty on most Wayland compositors. On Linux the
+// compositor is asked first when we can tell which one is running: Hyprland
+// and Sway/i3 over their IPC sockets, GNOME Shell over D-Bus. The X11 backend
+// is always tried last, which also covers XWayland windows.
+//
+// Setting WILL_SHORTCUT_WINDOW_DETECTOR to a backend name (x11, sway,
+// hyprland, gnome, fake) uses only that backend.
+
+mod active_win;
+mod fake;
+#[cfg(target_os = "linux")]
+mod gnome;
+#[cfg(target_os = "linux")]
+mod hyprland;
+#[cfg(target_os = "linux")]
+mod sway;
+
+use crate::storage::StorageError;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+// Window position and size in physical pixels
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl WindowBounds {
+    pub fn center(&self) -> (i32, i32) {
+        (
+            self.x + (self.width / 2) as i32,
+            self.y + (self.height / 2) as i32,
+        )
+    }
+}
+
+// The focused window as reported by a backend. Fields a backend can't
+// provide are left empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveWindowInfo {
+    // What applications are matched on: the process name on Windows/macOS,
+    // the WM_CLASS class or Wayland app_id on Linux
+    pub app_name: String,
+    pub title: String,
+    pub process_path: Option<String>,
+    pub pid: Option<u32>,
+    pub bounds: Option<WindowBounds>,
+    // X11 WM_CLASS class (or Wayland app_id); Linux only
+    pub wm_class: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DetectionError {
+    #[error("{backend}: {message}")]
+    Backend {
+        backend: &'static str,
+        message: String,
+    },
+    #[error("{backend}: no window is focused")]
+    NoActiveWindow { backend: &'static str },
+}
+
+impl DetectionError {
+    fn backend(backend: &'static str, message: impl ToString) -> Self {
+        DetectionError::Backend {
+            backend,
+            message: message.to_string(),
+        }
+    }
+}
+
+pub trait WindowDetector: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn active_window(&self) -> Result<ActiveWindowInfo, DetectionError>;
+}
+
+// Backends for this session, in the order they are tried
+fn detectors() -> &'static [Box<dyn WindowDetector>] {
+    static DETECTORS: OnceLock<Vec<Box<dyn WindowDetector>>> = OnceLock::new();
+    DETECTORS.get_or_init(|| select_detectors(|name| std::env::var(name).ok()))
+}
+
+// Pick backends from the environment (passed in so it can be faked)
+fn select_detectors(env: impl Fn(&str) -> Option<String>) -> Vec<Box<dyn WindowDetector>> {
+    if let Some(forced) = env("WILL_SHORTCUT_WINDOW_DETECTOR") {
+        match detector_named(&forced, &env) {
+            Some(detector) => return vec![detector],
+            None => eprintln!("Unknown WILL_SHORTCUT_WINDOW_DETECTOR \"{forced}\", detecting"),
+        }
+    }
+
+    let mut detectors: Vec<Box<dyn WindowDetector>> = Vec::new();
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(detector) = hyprland::HyprlandDetector::from_env(&env) {
+            detectors.push(Box::new(detector));
+        } else if let Some(detector) = sway::SwayDetector::from_env(&env) {
+            detectors.push(Box::new(detector));
+        } else if gnome::GnomeDetector::is_session(&env) {
+            detectors.push(Box::new(gnome::GnomeDetector));
+        }
+    }
+    detectors.push(Box::new(active_win::ActiveWinDetector));
+    detectors
+}
+
+fn detector_named(
+    name: &str,
+    env: &impl Fn(&str) -> Option<String>,
+) -> Option<Box<dyn WindowDetector>> {
+    match name.to_ascii_lowercase().as_str() {
+        "x11" | "active-win" => Some(Box::new(active_win::ActiveWinDetector)),
+        "fake" => Some(Box::new(fake::FakeDetector::from_env(env))),
+        #[cfg(target_os = "linux")]
+        "hyprland" => hyprland::HyprlandDetector::from_env(env)
+            .map(|d| Box::new(d) as Box<dyn WindowDetector>),
+        #[cfg(target_os = "linux")]
+        "sway" | "i3" => {
+            sway::SwayDetector::from_env(env).map(|d| Box::new(d) as Box<dyn WindowDetector>)
+        }
+        #[cfg(target_os = "linux")]
+        "gnome" => Some(Box::new(gnome::GnomeDetector)),
+        _ => None,
+    }
+}
+
+// Ask each backend in turn; the first one that finds a window wins
+pub fn active_window() -> Result<ActiveWindowInfo, DetectionError> {
+    first_window(detectors())
+}
+
+fn first_window(detectors: &[Box<dyn WindowDetector>]) -> Result<ActiveWindowInfo, DetectionError> {
+    let mut last_error = None;
+    for detector in detectors {
+        match detector.active_window() {
+            Ok(window) if !window.app_name.is_empty() => return Ok(window),
+            Ok(_) => {
+                last_error = Some(DetectionError::NoActiveWindow {
+                    backend: detector.name(),
+                })
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or(DetectionError::NoActiveWindow { backend: "none" }))
+}
+
+// Get the currently active application's name
+pub fn get_active_application() -> Result<String, DetectionError> {
+    active_window().map(|window| window.app_name)
+}
+
+// Get the currently active application's window title
+pub fn get_active_window_title() -> Result<String, DetectionError> {
+    active_window().map(|window| window.title)
+}
+
+impl From<DetectionError> for StorageError {
+    fn from(error: DetectionError) -> Self {
+        StorageError::Detection {
+            message: error.to_string(),
+        }
+    }
+}
+
+// Executable of a process, for backends that only report a PID
+#[cfg(target_os = "linux")]
+fn process_path(pid: u32) -> Option<String> {
+    std::fs::read_link(format!("/proc/{pid}/exe"))
+        .ok()
+        .map(|path| path.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    fn names(vars: &[(&str, &str)]) -> Vec<&'static str> {
+        select_detectors(env(vars))
+            .iter()
+            .map(|detector| detector.name())
+            .collect()
+    }
+
+    #[test]
+    fn x11_is_the_fallback() {
+        assert_eq!(names(&[]), vec!["x11"]);
+        assert_eq!(names(&[("DISPLAY", ":0")]), vec!["x11"]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn sway_and_i3_are_detected_from_their_sockets() {
+        assert_eq!(
+            names(&[("SWAYSOCK", "/run/user/1000/sway-ipc.sock")]),
+            vec!["sway", "x11"]
+        );
+        assert_eq!(
+            names(&[("I3SOCK", "/run/user/1000/i3/ipc-socket")]),
+            vec!["sway", "x11"]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn hyprland_is_detected_and_wins_over_sway() {
+        assert_eq!(
+            names(&[("HYPRLAND_INSTANCE_SIGNATURE", "abc_123")]),
+            vec!["hyprland", "x11"]
+        );
+        assert_eq!(
+            names(&[
+                ("SWAYSOCK", "/run/sway.sock"),
+                ("HYPRLAND_INSTANCE_SIGNATURE", "abc_123"),
+            ]),
+            vec!["hyprland", "x11"]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn gnome_is_only_used_on_wayland() {
+        assert_eq!(
+            names(&[
+                ("XDG_CURRENT_DESKTOP", "ubuntu:GNOME"),
+                ("WAYLAND_DISPLAY", "wayland-0"),
+            ]),
+            vec!["gnome", "x11"]
+        );
+        // GNOME on Xorg is covered by the X11 backend
+        assert_eq!(names(&[("XDG_CURRENT_DESKTOP", "GNOME")]), vec!["x11"]);
+        assert_eq!(
+            names(&[
+                ("XDG_CURRENT_DESKTOP", "KDE"),
+                ("WAYLAND_DISPLAY", "wayland-0")
+            ]),
+            vec!["x11"]
+        );
+    }
+
+    #[test]
+    fn override_selects_a_single_backend() {
+        let vars = [
+            ("WILL_SHORTCUT_WINDOW_DETECTOR", "fake"),
+            ("WILL_SHORTCUT_FAKE_APP", "firefox"),
+            ("WILL_SHORTCUT_FAKE_TITLE", "Inbox - Gmail"),
+            ("WILL_SHORTCUT_FAKE_WM_CLASS", "firefox"),
+            ("SWAYSOCK", "/run/sway.sock"),
+        ];
+        let detectors = select_detectors(env(&vars));
+        assert_eq!(detectors.len(), 1);
+        let window = first_window(&detectors).unwrap();
+        assert_eq!(window.app_name, "firefox");
+        assert_eq!(window.title, "Inbox - Gmail");
+        assert_eq!(window.wm_class.as_deref(), Some("firefox"));
+
+        assert_eq!(
+            names(&[("WILL_SHORTCUT_WINDOW_DETECTOR", "X11")]),
+            vec!["x11"]
+        );
+        // Unknown names fall back to normal detection
+        assert_eq!(
+            names(&[("WILL_SHORTCUT_WINDOW_DETECTOR", "wayfire")]),
+            vec!["x11"]
+        );
+    }
+
+    #[test]
+    fn first_backend_with_a_window_wins() {
+        let window = |app: &str| {
+            Some(ActiveWindowInfo {
+                app_name: app.to_string(),
+                ..Default::default()
+            })
+        };
+        let detectors: Vec<Box<dyn WindowDetector>> = vec![
+            Box::new(fake::FakeDetector::new(None)),
+            Box::new(fake::FakeDetector::new(window(""))),
+            Box::new(fake::FakeDetector::new(window("code"))),
+            Box::new(fake::FakeDetector::new(window("other"))),
+        ];
+        assert_eq!(first_window(&detectors).unwrap().app_name, "code");
+
+        let nothing: Vec<Box<dyn WindowDetector>> = vec![Box::new(fake::FakeDetector::new(None))];
+        assert!(matches!(
+            first_window(&nothing),
+            Err(DetectionError::NoActiveWindow { backend: "fake" })
+        ));
+        assert!(matches!(
+            first_window(&[]),
+            Err(DetectionError::NoActiveWindow { backend: "none" })
+        ));
+    }
+}
@@ -0,0 +1,179 @@
+// Sway and i3 over their shared IPC protocol: a GET_TREE request on the
+// socket from $SWAYSOCK / $I3SOCK, then a walk to the focused container.
+
+use super::{ActiveWindowInfo, DetectionError, WindowBounds, WindowDetector};
+use serde_json::Value;
+use std::io::{self, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const MAGIC: &[u8; 6] = b"i3-ipc";
+const GET_TREE: u32 = 4;
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+pub struct SwayDetector {
+    socket: PathBuf,
+}
+
+impl SwayDetector {
+    pub fn from_env(env: &impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let socket = env("SWAYSOCK").or_else(|| env("I3SOCK"))?;
+        Some(SwayDetector {
+            socket: PathBuf::from(socket),
+        })
+    }
+
+    fn request(&self, kind: u32) -> io::Result<Vec<u8>> {
+        let mut stream = UnixStream::connect(&self.socket)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+
+        let mut message = MAGIC.to_vec();
+        message.extend_from_slice(&0u32.to_ne_bytes());
+        message.extend_from_slice(&kind.to_ne_bytes());
+        stream.write_all(&message)?;
+
+        // Reply: magic, payload length, message type, payload
+        let mut header = [0u8; 14];
+        stream.read_exact(&mut header)?;
+        if &header[..6] != MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not an i3 IPC reply",
+            ));
+        }
+        let length = u32::from_ne_bytes([header[6], header[7], header[8], header[9]]);
+        let mut payload = vec![0; length as usize];
+        stream.read_exact(&mut payload)?;
+        Ok(payload)
+    }
+}
+
+impl WindowDetector for SwayDetector {
+    fn name(&self) -> &'static str {
+        "sway"
+    }
+
+    fn active_window(&self) -> Result<ActiveWindowInfo, DetectionError> {
+        let payload = self
+            .request(GET_TREE)
+            .map_err(|e| DetectionError::backend(self.name(), e))?;
+        let tree: Value = serde_json::from_slice(&payload)
+            .map_err(|e| DetectionError::backend(self.name(), e))?;
+        let node = focused(&tree).ok_or(DetectionError::NoActiveWindow {
+            backend: self.name(),
+        })?;
+
+        // Native Wayland windows have an app_id; XWayland ones a WM_CLASS
+        let app_name = node["app_id"]
+            .as_str()
+            .filter(|id| !id.is_empty())
+            .or_else(|| node["window_properties"]["class"].as_str())
+            .unwrap_or_default();
+        // i3 doesn't report the pid
+        let pid = node["pid"].as_u64().and_then(|pid| u32::try_from(pid).ok());
+        let rect = &node["rect"];
+        let bounds = match (
+            rect["x"].as_i64(),
+            rect["y"].as_i64(),
+            rect["width"].as_u64(),
+            rect["height"].as_u64(),
+        ) {
+            (Some(x), Some(y), Some(width), Some(height)) => Some(WindowBounds {
+                x: x as i32,
+                y: y as i32,
+                width: width as u32,
+                height: height as u32,
+            }),
+            _ => None,
+        };
+        Ok(ActiveWindowInfo {
+            app_name: app_name.to_string(),
+            title: node["name"].as_str().unwrap_or_default().to_string(),
+            process_path: pid.and_then(super::process_path),
+            pid,
+            bounds,
+            wm_class: (!app_name.is_empty()).then(|| app_name.to_string()),
+        })
+    }
+}
+
+// The focused window container. An empty workspace can be focused too, which
+// means there is no window.
+fn focused(node: &Value) -> Option<&Value> {
+    if node["focused"].as_bool() == Some(true) {
+        let is_window = matches!(node["type"].as_str(), Some("con" | "floating_con"));
+        return is_window.then_some(node);
+    }
+    ["nodes", "floating_nodes"]
+        .iter()
+        .filter_map(|key| node[*key].as_array())
+        .flatten()
+        .find_map(focused)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tree(workspace_focused: bool, window_focused: bool) -> Value {
+        json!({
+            "type": "root",
+            "focused": false,
+            "nodes": [{
+                "type": "output",
+                "name": "eDP-1",
+                "focused": false,
+                "nodes": [
+                    {
+                        "type": "workspace",
+                        "name": "1",
+                        "focused": false,
+                        "nodes": [{
+                            "type": "con",
+                            "name": "main.rs - Visual Studio Code",
+                            "app_id": "code",
+                            "focused": false,
+                            "nodes": [],
+                        }],
+                        "floating_nodes": [{
+                            "type": "floating_con",
+                            "name": "Picture-in-Picture",
+                            "app_id": null,
+                            "window_properties": { "class": "firefox" },
+                            "focused": window_focused,
+                            "nodes": [],
+                        }],
+                    },
+                    {
+                        "type": "workspace",
+                        "name": "2",
+                        "focused": workspace_focused,
+                        "nodes": [],
+                        "floating_nodes": [],
+                    },
+                ],
+            }],
+        })
+    }
+
+    #[test]
+    fn finds_the_focused_window_in_nested_and_floating_nodes() {
+        let tree = tree(false, true);
+        let node = focused(&tree).unwrap();
+        assert_eq!(node["name"], "Picture-in-Picture");
+        assert_eq!(node["window_properties"]["class"], "firefox");
+    }
+
+    #[test]
+    fn focused_empty_workspace_has_no_window() {
+        assert!(focused(&tree(true, false)).is_none());
+    }
+
+    #[test]
+    fn nothing_focused() {
+        assert!(focused(&tree(false, false)).is_none());
+        assert!(focused(&json!({})).is_none());
+    }
+}
@@ -3,7 +3,7 @@ import { useShortcuts } from "./hooks/useShortcuts";
 import { SettingsModal } from "./components/SettingsModal";
 
 export function SettingsApp() {
-	  const { settings, applications, saveSettings, saveApplication, loading, error } = useShortcuts();
+	  const { settings, applications, shortcutLists, saveSettings, saveApplication, loading, error } = useShortcuts();
 	  if (loading || !settings) return <div className="p-4 text-white">Loading settings...</div>;
 	  if (error) return <div className="p-4 text-red-400">Error: {String(error)}</div>;
 	  return (
@@ -11,6 +11,7 @@ export function SettingsApp() {
 	      isOpen={true}
 	      settings={settings}
 	      applications={applications}
+	      shortcutLists={shortcutLists}
 	      onClose={() => getCurrentWebviewWindow().hide()}
 	      onSave={async s => { await saveSettings(s); getCurrentWebviewWindow().hide(); }}
 	      onSaveApplication={saveApplication}
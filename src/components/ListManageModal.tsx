@@ -1,13 +1,14 @@
 import { useState, useEffect } from 'react';
 import { X, Trash2 } from 'lucide-react';
 import type { ShortcutList } from '../types';
+import { getTitlePattern } from '../utils/shortcutListUtils';
 
 interface ListManageModalProps {
   isOpen: boolean;
   selectedList: ShortcutList | null;
   onClose: () => void;
-  onCreate: (name: string) => void;
-  onRename: (name: string) => void;
+  onCreate: (name: string, titlePattern: string) => void;
+  onRename: (name: string, titlePattern: string) => void;
   onDelete: () => void;
   dumpApps: () => void; // Debugging
 }
@@ -16,15 +17,18 @@ export function ListManageModal(props: ListManageModalProps) {
   const { isOpen, selectedList, onClose, onCreate, onRename, onDelete, dumpApps } = props;
   const [mode, setMode] = useState<'edit' | 'create'>('create');
   const [name, setName] = useState('');
+  const [titlePattern, setTitlePattern] = useState('');
 
   useEffect(() => {
     if (!isOpen) return;
     if (selectedList) {
       setMode('edit');
       setName(selectedList.name);
+      setTitlePattern(getTitlePattern(selectedList));
     } else {
       setMode('create');
       setName('');
+      setTitlePattern('');
     }
   }, [isOpen, selectedList]);
 
@@ -32,8 +36,8 @@ export function ListManageModal(props: ListManageModalProps) {
     e.preventDefault();
     const trimmed = name.trim();
     if (!trimmed) return;
-    if (mode === 'edit' && selectedList) onRename(trimmed);
-    else onCreate(trimmed);
+    if (mode === 'edit' && selectedList) onRename(trimmed, titlePattern);
+    else onCreate(trimmed, titlePattern);
     onClose();
   };
 
@@ -46,6 +50,7 @@ export function ListManageModal(props: ListManageModalProps) {
   const switchToCreate = () => {
     setMode('create');
     setName('');
+    setTitlePattern('');
   };
 
   if (!isOpen) return null;
@@ -76,6 +81,19 @@ export function ListManageModal(props: ListManageModalProps) {
                          focus:outline-none focus:border-blue-500 text-sm"
             />
           </div>
+          <div>
+            <label className="block text-xs text-gray-300 mb-1">Window title pattern (optional)</label>
+            <input
+              value={titlePattern}
+              onChange={(e) => setTitlePattern(e.target.value)}
+              placeholder="* - Gmail*"
+              className="w-full bg-gray-900 text-white px-3 py-1.5 rounded border border-gray-700
+                         focus:outline-none focus:border-blue-500 text-sm"
+            />
+            <p className="mt-1 text-xs text-gray-400">
+              Show this list first while the window title matches, e.g. a website open in a browser.
+            </p>
+          </div>
           {mode === 'edit' && (
             <button
               type="button"
@@ -1,9 +1,10 @@
 import type React from 'react';
-import { useState, useEffect } from 'react';
+import { useState, useEffect, useRef } from 'react';
 import { listen } from '@tauri-apps/api/event';
 import { useShortcuts } from '../hooks/useShortcuts';
-import { ShortcutList, Shortcut, Application } from '../types';
-import { Keyboard, Settings as SettingsIcon, Plus, Edit2 } from 'lucide-react';
+import { useKeyComboDisplay } from '../hooks/useKeyComboDisplay';
+import { ShortcutList, Shortcut, Application, ActiveAppChanged } from '../types';
+import { Keyboard, Settings as SettingsIcon, Plus, Edit2, Search, X } from 'lucide-react';
 import { ShortcutModal } from './ShortcutModal';
 import { ShortcutRow } from './ShortcutRow';
 import { ShortcutContextMenu } from './ShortcutContextMenu';
@@ -18,6 +19,8 @@ import {
 	updateShortcut as updateShortcutInList,
 	insertShortcutAt,
 	moveShortcut,
+	getTitlePattern,
+	withTitlePattern,
 } from '../utils/shortcutListUtils';
 import { eventMatchesCombo } from '../utils/hotkeyUtils';
 
@@ -40,6 +43,13 @@ export function Popup() {
 	const [isListModalOpen, setIsListModalOpen] = useState(false);
 	const [insertIndex, setInsertIndex] = useState<number | null>(null);
 	const [contextMenu, setContextMenu] = useState<ContextMenuState>({ isOpen: false });
+	// Set by an "open list" global hotkey; overrides auto-selection until the popup hides
+	const [pinnedListId, setPinnedListId] = useState<string | null>(null);
+	// List whose rules match the active window (e.g. Gmail in a browser)
+	const [siteListId, setSiteListId] = useState<string | null>(null);
+	// null while the search box is closed
+	const [searchQuery, setSearchQuery] = useState<string | null>(null);
+	const searchInputRef = useRef<HTMLInputElement>(null);
 
 	// Listen for active app detection event
 	useEffect(() => {
@@ -48,9 +58,36 @@ export function Popup() {
 			// by the autoSelectList effect that reacts to detectedActiveApp changes.
 			setDetectedActiveApp(event.payload);
 		});
+		const unlistenSiteList = listen<string | null>('site-list-detected', (event) => {
+			setSiteListId(event.payload);
+		});
+		// Sent by the focus watcher (if enabled) so an open popup follows focus
+		const unlistenChanged = listen<ActiveAppChanged>('active-app-changed', (event) => {
+			setSiteListId(event.payload.site_list_id);
+			setDetectedActiveApp(event.payload.identifier);
+		});
 
 		return () => {
 			unlisten.then((fn) => fn());
+			unlistenSiteList.then((fn) => fn());
+			unlistenChanged.then((fn) => fn());
+		};
+	}, []);
+
+	// Global hotkey actions sent by the backend
+	useEffect(() => {
+		const unlistenOpenList = listen<string>('open-list', (event) => {
+			setPinnedListId(event.payload);
+			setSelectedListId(event.payload);
+		});
+		const unlistenOpenSearch = listen('open-search', () => {
+			setSearchQuery('');
+			requestAnimationFrame(() => searchInputRef.current?.focus());
+		});
+
+		return () => {
+			unlistenOpenList.then((fn) => fn());
+			unlistenOpenSearch.then((fn) => fn());
 		};
 	}, []);
 
@@ -58,7 +95,7 @@ export function Popup() {
 	useEffect(() => {
 		console.log("Auto-selecting list...");
 		autoSelectList();
-	}, [shortcutLists, applications, activeApp, detectedActiveApp]);
+	}, [shortcutLists, applications, activeApp, detectedActiveApp, pinnedListId, siteListId]);
 
 	// Close modals when popup is hidden and persist the *current* app's last used list
 	useEffect(() => {
@@ -68,8 +105,17 @@ export function Popup() {
 			setIsListModalOpen(false);
 			setInsertIndex(null);
 			setContextMenu({ isOpen: false });
+			setSearchQuery(null);
 
-			if (!selectedListId) {
+			// A pinned list was a one-off; don't remember it as the app's last used list
+			if (pinnedListId) {
+				setPinnedListId(null);
+				return;
+			}
+
+			// The site list is picked from the window title each time, so
+			// don't remember it either
+			if (!selectedListId || selectedListId === siteListId) {
 				return;
 			}
 
@@ -101,11 +147,22 @@ export function Popup() {
 		return () => {
 			unlistenPromise.then((unlisten) => unlisten());
 		};
-	}, [applications, activeApp, detectedActiveApp, selectedListId, saveApplication]);
+	}, [applications, activeApp, detectedActiveApp, selectedListId, pinnedListId, siteListId, saveApplication]);
 
 	// Auto-select the appropriate list for the current app when data or
 	// the detected app changes
 	const autoSelectList = (identifier?: string) => {
+		if (pinnedListId && shortcutLists.some((l) => l.id === pinnedListId)) {
+			setSelectedListId(pinnedListId);
+			return;
+		}
+
+		// A list matched by the window title wins over the app's own lists
+		if (siteListId && shortcutLists.some((l) => l.id === siteListId)) {
+			setSelectedListId(siteListId);
+			return;
+		}
+
 		const currentIdentifier =
 			identifier ||
 			detectedActiveApp ||
@@ -340,7 +397,7 @@ export function Popup() {
 		setContextMenu({ isOpen: false });
 	};
 
-	const handleCreateList = async (name: string) => {
+	const handleCreateList = async (name: string, titlePattern: string) => {
 		const currentActiveApp = detectedActiveApp || activeApp?.process_name;
 		if (!currentActiveApp) return;
 
@@ -377,6 +434,7 @@ export function Popup() {
 			shortcuts: [],
 			created_at: now,
 			updated_at: now,
+			match_rules: withTitlePattern(undefined, titlePattern),
 		};
 
 		await saveList(newList);
@@ -395,17 +453,19 @@ export function Popup() {
 		autoSelectList();
 	};
 
-	const handleRenameList = async (name: string) => {
+	const handleRenameList = async (name: string, titlePattern: string) => {
 		if (!selectedListId) return;
 		const selectedList = shortcutLists.find(l => l.id === selectedListId);
 		if (!selectedList) return;
 
 		const trimmed = name.trim();
-		if (!trimmed || trimmed === selectedList.name) return;
+		if (!trimmed) return;
+		if (trimmed === selectedList.name && titlePattern.trim() === getTitlePattern(selectedList)) return;
 
 		const updatedList: ShortcutList = {
 			...selectedList,
 			name: trimmed,
+			match_rules: withTitlePattern(selectedList.match_rules, titlePattern),
 			updated_at: new Date().toISOString(),
 		};
 
@@ -432,6 +492,32 @@ export function Popup() {
 		return matchKey === activeIdentifier;
 	});
 
+	const showAllLists = settings?.show_all_lists ?? false;
+
+	const dropdownLists: ShortcutList[] = showAllLists
+		? shortcutLists
+		: shortcutLists.filter((list) => {
+			if (list.id === siteListId) return true;
+			const app = applications.find((a) => a.id === list.application_id);
+			if (!app) return false;
+			const matchKey = app.detection_name || app.process_name;
+			return matchKey === activeIdentifier;
+		});
+
+	// "Next list" global hotkey: cycle through the dropdown's lists
+	useEffect(() => {
+		const unlisten = listen('next-list', () => {
+			if (dropdownLists.length === 0) return;
+			const current = dropdownLists.findIndex((l) => l.id === selectedListId);
+			setPinnedListId(null);
+			setSelectedListId(dropdownLists[(current + 1) % dropdownLists.length].id);
+		});
+
+		return () => {
+			unlisten.then((fn) => fn());
+		};
+	}, [dropdownLists, selectedListId]);
+
 	const keyboardShortcuts = settings?.keyboard_shortcuts;
 	const addNewHotkey = keyboardShortcuts?.add_new;
 
@@ -460,6 +546,11 @@ export function Popup() {
 		};
 	}, [addNewHotkey, hasListForCurrentApp, isModalOpen, isListModalOpen]);
 
+	// Rendered once for every list so switching lists doesn't flash the stored form
+	const displayKeyCombo = useKeyComboDisplay(
+		shortcutLists.flatMap((list) => list.shortcuts.map((s) => s.key_combo))
+	);
+
 	if (loading) {
 		return (
 			<div className="flex items-center justify-center h-screen bg-gray-900 text-white">
@@ -484,16 +575,6 @@ export function Popup() {
 
 
 	const showAppNameInDropdown = settings?.show_app_name_in_dropdown ?? true;
-	const showAllLists = settings?.show_all_lists ?? false;
-
-	const dropdownLists: ShortcutList[] = showAllLists
-		? shortcutLists
-		: shortcutLists.filter((list) => {
-			const app = applications.find((a) => a.id === list.application_id);
-			if (!app) return false;
-			const matchKey = app.detection_name || app.process_name;
-			return matchKey === activeIdentifier;
-		});
 
 	const defaultNewListName = (() => {
 		if (!activeIdentifier) return 'New list';
@@ -513,6 +594,12 @@ export function Popup() {
 
 	const sortedShortcuts = selectedList ? sortShortcuts(selectedList.shortcuts) : [];
 
+	const normalizedQuery = searchQuery?.trim().toLowerCase() ?? '';
+	const matchesSearch = (shortcut: Shortcut) =>
+		!normalizedQuery ||
+		shortcut.description.toLowerCase().includes(normalizedQuery) ||
+		shortcut.key_combo.toLowerCase().includes(normalizedQuery);
+
 	const nextOrder = selectedList ? selectedList.shortcuts.length : 0;
 
 	const contextMenuIndex =
@@ -544,6 +631,7 @@ export function Popup() {
           focus:outline-none focus:border-blue-500 w-10"
 					value={selectedListId || ''}
 					onChange={(e) => {
+						setPinnedListId(null);
 						setSelectedListId(e.target.value)
 					}}
 				>
@@ -585,14 +673,44 @@ export function Popup() {
 				</button>
 			</div>
 
+			{/* Search */}
+			{searchQuery !== null && (
+				<div className="p-1 border-b border-gray-700 flex items-center gap-2">
+					<Search className="w-4 h-4 text-gray-400" />
+					<input
+						ref={searchInputRef}
+						className="flex-1 bg-gray-800 text-white px-2 py-1 rounded border border-gray-700
+          focus:outline-none focus:border-blue-500 text-sm"
+						placeholder="Search shortcuts"
+						value={searchQuery}
+						onChange={(e) => setSearchQuery(e.target.value)}
+						onKeyDown={(e) => {
+							if (e.key === 'Escape') {
+								e.stopPropagation();
+								setSearchQuery(null);
+							}
+						}}
+					/>
+					<button
+						type="button"
+						onClick={() => setSearchQuery(null)}
+						className="p-1 hover:bg-gray-700 rounded"
+						title="Close search"
+					>
+						<X className="w-4 h-4 text-gray-400" />
+					</button>
+				</div>
+			)}
+
 			{/* Shortcuts List */}
 			<div className="flex-1 overflow-y-auto py-1">
 				{selectedList ? sortedShortcuts.length > 0 ? (
 					<div>
-						{sortedShortcuts.map((shortcut, index) => (
+						{sortedShortcuts.map((shortcut, index) => matchesSearch(shortcut) && (
 							<ShortcutRow
 								key={shortcut.id}
 								shortcut={shortcut}
+								keyComboText={displayKeyCombo(shortcut.key_combo)}
 								index={index}
 								onClick={handleEditShortcut}
 								onContextMenu={handleShortcutContextMenu}
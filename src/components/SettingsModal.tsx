@@ -1,13 +1,42 @@
-import { useEffect, useState } from 'react';
-import { X } from 'lucide-react';
-import type { Settings, Application } from '../types';
+import { Fragment, useEffect, useState } from 'react';
+import { Plus, X } from 'lucide-react';
+import type {
+	Settings,
+	Application,
+	GlobalHotkey,
+	ShortcutList,
+	MatchRule,
+	MatchField,
+	MatchKind,
+} from '../types';
 import { KeyCaptureInput } from './KeyCaptureInput';
 import { SHORTCUT_ACTIONS } from '../utils/shortcutActions';
+import {
+	GLOBAL_ACTIONS,
+	makeGlobalAction,
+	supportsPeek,
+	type GlobalActionKind,
+} from '../utils/globalActions';
+
+const MATCH_FIELDS: { value: MatchField; label: string }[] = [
+	{ value: 'app_name', label: 'App name' },
+	{ value: 'process_path', label: 'Process path' },
+	{ value: 'window_title', label: 'Window title' },
+	{ value: 'wm_class', label: 'WM class' },
+];
+
+const MATCH_KINDS: { value: MatchKind; label: string }[] = [
+	{ value: 'exact', label: 'Exact' },
+	{ value: 'case_insensitive', label: 'Ignore case' },
+	{ value: 'glob', label: 'Glob' },
+	{ value: 'regex', label: 'Regex' },
+];
 
 interface SettingsModalProps {
 	isOpen: boolean;
 	settings: Settings | null;
 	applications: Application[];
+	shortcutLists: ShortcutList[];
 	onClose: () => void;
 	onSave: (settings: Settings) => Promise<void> | void;
 	onSaveApplication: (app: Application) => Promise<void> | void;
@@ -17,6 +46,7 @@ export function SettingsModal({
 	isOpen,
 	settings,
 	applications,
+	shortcutLists,
 	onClose,
 	onSave,
 	onSaveApplication,
@@ -25,6 +55,7 @@ export function SettingsModal({
 	const [windowPosition, setWindowPosition] = useState<string>('BottomRight');
 	const [localApplications, setLocalApplications] = useState<Application[]>(applications);
 	const [activeTab, setActiveTab] = useState<'general' | 'applications' | 'shortcuts'>('general');
+	const [expandedAppId, setExpandedAppId] = useState<string | null>(null);
 
 	useEffect(() => {
 		if (isOpen && settings) {
@@ -50,6 +81,34 @@ export function SettingsModal({
 		setLocalSettings(prev => (prev ? { ...prev, window_position: pos } : prev));
 	};
 
+	const globalActions = localSettings.global_actions ?? [];
+
+	const updateGlobalActions = (update: (actions: GlobalHotkey[]) => GlobalHotkey[]) => {
+		setLocalSettings(prev =>
+			prev ? { ...prev, global_actions: update(prev.global_actions ?? []) } : prev,
+		);
+	};
+
+	const handleAddGlobalAction = () => {
+		updateGlobalActions(actions => [
+			...actions,
+			{ hotkey: '', action: makeGlobalAction('open_search') },
+		]);
+	};
+
+	const handleGlobalActionChange = (index: number, binding: GlobalHotkey) => {
+		updateGlobalActions(actions => actions.map((a, i) => (i === index ? binding : a)));
+	};
+
+	const handleRemoveGlobalAction = (index: number) => {
+		updateGlobalActions(actions => actions.filter((_, i) => i !== index));
+	};
+
+	const listLabel = (list: ShortcutList) => {
+		const app = applications.find(a => a.id === list.application_id);
+		return app ? `${app.name} - ${list.name}` : list.name;
+	};
+
 	const handleAppNameChange = (id: string, name: string) => {
 		setLocalApplications(prev =>
 			prev.map(app => (app.id === id ? { ...app, name } : app)),
@@ -62,16 +121,41 @@ export function SettingsModal({
 		);
 	};
 
+	const updateAppRules = (id: string, update: (rules: MatchRule[]) => MatchRule[]) => {
+		setLocalApplications(prev =>
+			prev.map(app =>
+				app.id === id ? { ...app, match_rules: update(app.match_rules ?? []) } : app,
+			),
+		);
+	};
+
+	const handleAddRule = (id: string) => {
+		updateAppRules(id, rules => [
+			...rules,
+			{ field: 'window_title', kind: 'glob', pattern: '', priority: 0 },
+		]);
+	};
+
+	const handleRuleChange = (id: string, index: number, rule: MatchRule) => {
+		updateAppRules(id, rules => rules.map((r, i) => (i === index ? rule : r)));
+	};
+
+	const handleRemoveRule = (id: string, index: number) => {
+		updateAppRules(id, rules => rules.filter((_, i) => i !== index));
+	};
+
 	const handleSave = async (e: React.FormEvent) => {
 		e.preventDefault();
 		if (!localSettings) return;
 
-		// Persist any application changes (name/icon)
+		// Persist any application changes (name/icon/match rules)
 		const changedApps = localApplications.filter(local => {
 			const original = applications.find(app => app.id === local.id);
 			if (!original) return true;
 			return (
-				original.name !== local.name || (original.icon ?? '') !== (local.icon ?? '')
+				original.name !== local.name ||
+				(original.icon ?? '') !== (local.icon ?? '') ||
+				JSON.stringify(original.match_rules ?? []) !== JSON.stringify(local.match_rules ?? [])
 			);
 		});
 
@@ -146,9 +230,113 @@ export function SettingsModal({
 										disableToggle={true}
 									/>
 									<p className="mt-1 text-[11px] text-gray-400">
-										Changes to the global hotkey take effect the next time you restart the
-										app.
+										Shows or hides the popup from any application.
 									</p>
+									<label
+										className="mt-1 flex items-center gap-2 text-xs text-gray-300"
+										title="Show the popup while the hotkey is held down and hide it when released."
+									>
+										<input
+											type="checkbox"
+											checked={localSettings.global_hotkey_peek ?? false}
+											onChange={e =>
+												setLocalSettings(prev =>
+													prev ? { ...prev, global_hotkey_peek: e.target.checked } : prev,
+												)
+											}
+										/>
+										Hold to peek
+									</label>
+								</div>
+
+								{/* Other global hotkeys */}
+								<div>
+									<label className="block text-xs text-gray-300 mb-1">Other global hotkeys</label>
+									<div className="space-y-1">
+										{globalActions.map((binding, index) => (
+											<div key={index} className="flex items-center gap-2">
+												<select
+													className="bg-gray-900 text-white px-2 py-1 rounded border border-gray-700 focus:outline-none focus:border-blue-500 text-xs"
+													value={binding.action.kind}
+													title={GLOBAL_ACTIONS.find(a => a.kind === binding.action.kind)?.description}
+													onChange={e =>
+														handleGlobalActionChange(index, {
+															...binding,
+															action: makeGlobalAction(
+																e.target.value as GlobalActionKind,
+																shortcutLists[0]?.id,
+															),
+														})
+													}
+												>
+													{GLOBAL_ACTIONS.map(action => (
+														<option key={action.kind} value={action.kind}>
+															{action.label}
+														</option>
+													))}
+												</select>
+												{binding.action.kind === 'open_list' && (
+													<select
+														className="w-40 bg-gray-900 text-white px-2 py-1 rounded border border-gray-700 focus:outline-none focus:border-blue-500 text-xs"
+														value={binding.action.list_id}
+														onChange={e =>
+															handleGlobalActionChange(index, {
+																...binding,
+																action: makeGlobalAction('open_list', e.target.value),
+															})
+														}
+													>
+														{shortcutLists.map(list => (
+															<option key={list.id} value={list.id}>
+																{listLabel(list)}
+															</option>
+														))}
+													</select>
+												)}
+												<KeyCaptureInput
+													value={binding.hotkey}
+													onChange={value =>
+														handleGlobalActionChange(index, { ...binding, hotkey: value })
+													}
+													disableToggle={true}
+												/>
+												{supportsPeek(binding.action.kind) && (
+													<label
+														className="flex items-center gap-1 text-xs text-gray-300 whitespace-nowrap"
+														title="Show the popup while the hotkey is held down and hide it when released."
+													>
+														<input
+															type="checkbox"
+															checked={binding.peek ?? false}
+															onChange={e =>
+																handleGlobalActionChange(index, {
+																	...binding,
+																	peek: e.target.checked,
+																})
+															}
+														/>
+														Peek
+													</label>
+												)}
+												<button
+													type="button"
+													onClick={() => handleRemoveGlobalAction(index)}
+													className="p-1 hover:bg-gray-700 rounded"
+													title="Remove hotkey"
+												>
+													<X className="w-3 h-3 text-gray-400" />
+												</button>
+											</div>
+										))}
+									</div>
+									<button
+										type="button"
+										onClick={handleAddGlobalAction}
+										className="mt-1 flex items-center gap-1 px-2 py-1 text-xs text-gray-300 bg-gray-700 rounded hover:bg-gray-600"
+									>
+										<Plus className="w-3 h-3" />
+										Add global hotkey
+									</button>
 								</div>
 
 								{/* Window position */}
@@ -278,6 +466,33 @@ export function SettingsModal({
 											}
 										/>
 									</div>
+									<div
+										className="flex items-center justify-between px-3 py-2"
+										title="When enabled, a background watcher keeps the popup on the lists for whichever app is focused, while it stays open."
+									>
+										<label
+											className="text-xs text-gray-300"
+											htmlFor="watchFocus"
+										>
+											Follow the active app while open
+										</label>
+										<input
+											type="checkbox"
+											name="watchFocus"
+											id="watchFocus"
+											checked={localSettings.watch_focus ?? false}
+											onChange={e =>
+												setLocalSettings(prev =>
+													prev
+														? {
+															...prev,
+															watch_focus: e.target.checked,
+														}
+														: prev,
+												)
+											}
+										/>
+									</div>
 								</div>
 							</>
 						)}
@@ -285,7 +500,10 @@ export function SettingsModal({
 						{activeTab === 'applications' && (
 							<div className="space-y-2">
 								<p className="text-xs text-gray-400">
-									Edit the display name and icon for each detected application.
+									Edit the display name, icon and match rules for each detected application.
+								</p>
+								<p className="text-xs text-gray-400">
+									Match rules recognise an application by its app name, process path, window title or WM class. When several applications match, the highest priority rule wins.
 								</p>
 								<div className="border border-gray-700 rounded">
 									<table className="w-full text-xs overflow-y-auto">
@@ -303,11 +521,15 @@ export function SettingsModal({
 												<th className="px-2 py-1 text-left text-gray-300 font-medium">
 													Icon
 												</th>
+												<th className="px-2 py-1 text-left text-gray-300 font-medium">
+													Rules
+												</th>
 											</tr>
 										</thead>
 										<tbody>
 											{localApplications.map(app => (
-												<tr key={app.id} className="border-t border-gray-800">
+												<Fragment key={app.id}>
+												<tr className="border-t border-gray-800">
 													<td className="px-2 py-1 text-gray-300">
 														{app.detection_name}
 													</td>
@@ -333,7 +555,100 @@ export function SettingsModal({
 															placeholder="icon path"
 														/>
 													</td>
+													<td className="px-2 py-1">
+														<button
+															type="button"
+															onClick={() =>
+																setExpandedAppId(expandedAppId === app.id ? null : app.id)
+															}
+															className="px-2 py-1 rounded bg-gray-700 hover:bg-gray-600 text-gray-200 whitespace-nowrap"
+														>
+															{(app.match_rules ?? []).length} rule
+															{(app.match_rules ?? []).length === 1 ? '' : 's'}
+														</button>
+													</td>
 												</tr>
+												{expandedAppId === app.id && (
+													<tr className="bg-gray-900">
+														<td colSpan={5} className="px-2 py-2 space-y-1">
+															{(app.match_rules ?? []).map((rule, index) => (
+																<div key={index} className="flex gap-1 items-center">
+																	<select
+																		className="bg-gray-800 text-white px-1 py-1 rounded border border-gray-700 text-xs"
+																		value={rule.field}
+																		onChange={e =>
+																			handleRuleChange(app.id, index, {
+																				...rule,
+																				field: e.target.value as MatchField,
+																			})
+																		}
+																	>
+																		{MATCH_FIELDS.map(field => (
+																			<option key={field.value} value={field.value}>
+																				{field.label}
+																			</option>
+																		))}
+																	</select>
+																	<select
+																		className="bg-gray-800 text-white px-1 py-1 rounded border border-gray-700 text-xs"
+																		value={rule.kind}
+																		onChange={e =>
+																			handleRuleChange(app.id, index, {
+																				...rule,
+																				kind: e.target.value as MatchKind,
+																			})
+																		}
+																	>
+																		{MATCH_KINDS.map(kind => (
+																			<option key={kind.value} value={kind.value}>
+																				{kind.label}
+																			</option>
+																		))}
+																	</select>
+																	<input
+																		className="flex-1 bg-gray-800 text-white px-2 py-1 rounded border border-gray-700 focus:outline-none focus:border-blue-500 text-xs"
+																		value={rule.pattern}
+																		onChange={e =>
+																			handleRuleChange(app.id, index, {
+																				...rule,
+																				pattern: e.target.value,
+																			})
+																		}
+																		placeholder="pattern"
+																	/>
+																	<input
+																		type="number"
+																		className="w-14 bg-gray-800 text-white px-2 py-1 rounded border border-gray-700 focus:outline-none focus:border-blue-500 text-xs"
+																		value={rule.priority ?? 0}
+																		onChange={e =>
+																			handleRuleChange(app.id, index, {
+																				...rule,
+																				priority: Number(e.target.value) || 0,
+																			})
+																		}
+																		title="Priority"
+																	/>
+																	<button
+																		type="button"
+																		onClick={() => handleRemoveRule(app.id, index)}
+																		className="p-1 hover:bg-gray-700 rounded"
+																	>
+																		<X className="w-3 h-3 text-gray-400" />
+																	</button>
+																</div>
+															))}
+															<button
+																type="button"
+																onClick={() => handleAddRule(app.id)}
+																className="flex items-center gap-1 px-2 py-1 text-xs text-gray-300 bg-gray-700 rounded hover:bg-gray-600"
+															>
+																<Plus className="w-3 h-3" />
+																Add rule
+															</button>
+														</td>
+													</tr>
+												)}
+												</Fragment>
 											))}
 										</tbody>
 									</table>
@@ -4,6 +4,8 @@ import { eventMatchesCombo } from '../utils/hotkeyUtils';
 
 interface ShortcutRowProps {
 	shortcut: Shortcut;
+	// key_combo as rendered for this platform (see useKeyComboDisplay)
+	keyComboText: string;
 	index: number;
 	onClick: (shortcut: Shortcut) => void;
 	onContextMenu?: (
@@ -26,6 +28,7 @@ interface ShortcutRowProps {
 
 export function ShortcutRow({
 	shortcut,
+	keyComboText,
 	index,
 	onClick,
 	onContextMenu,
@@ -122,7 +125,7 @@ export function ShortcutRow({
 			<div className="flex items-center justify-between gap-1">
 				<span className="text-sm text-gray-300">{shortcut.description}</span>
 				<kbd className="px-2 py-1 rounded text-base font-mono text-right">
-					{shortcut.key_combo.split(',').map((part, idx, arr) => (
+					{keyComboText.split(',').map((part, idx, arr) => (
 						<span key={idx} className="whitespace-nowrap">
 							{part.trim()}
 							{idx < arr.length - 1 ? ', ' : ''}
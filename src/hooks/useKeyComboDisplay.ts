@@ -0,0 +1,35 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import type { KeyStyle } from '../types';
+
+// Rendered forms don't change while the app runs, so they're shared across renders and components
+const renderedCache = new Map<string, string>();
+
+/**
+ * Display forms of stored key combos for this platform, rendered by the backend
+ * (render_key_combos) so the UI and exports agree ("CmdOrCtrl+Shift+P" → "Shift+Cmd+P"
+ * on macOS). Returns a lookup that falls back to the stored text until rendering finishes.
+ */
+export function useKeyComboDisplay(keyCombos: string[], style: KeyStyle = 'text') {
+	const [, setVersion] = useState(0);
+	const cacheKey = (combo: string) => `${style}\u0000${combo}`;
+	const missing = Array.from(new Set(keyCombos.filter((combo) => !renderedCache.has(cacheKey(combo)))));
+	const missingKey = missing.join('\u0000');
+
+	useEffect(() => {
+		if (missing.length === 0) return;
+		let cancelled = false;
+		invoke<string[]>('render_key_combos', { keyCombos: missing, style })
+			.then((rendered) => {
+				missing.forEach((combo, i) => renderedCache.set(cacheKey(combo), rendered[i]));
+				if (!cancelled) setVersion((v) => v + 1);
+			})
+			.catch((err) => console.error('Failed to render key combos:', err));
+		return () => {
+			cancelled = true;
+		};
+		// missingKey stands in for the contents of `missing`
+	}, [missingKey, style]);
+
+	return (combo: string) => renderedCache.get(cacheKey(combo)) ?? combo;
+}
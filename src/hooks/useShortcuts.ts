@@ -1,16 +1,19 @@
 import { useState, useEffect, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen, emit } from '@tauri-apps/api/event';
-import { ShortcutList, Application, Settings } from '../types';
+import { ShortcutList, Application, Settings, LoadedLists, LoadWarning, ActiveWindowInfo } from '../types';
 import { enable, disable } from '@tauri-apps/plugin-autostart';
+import { describeError } from '../utils/errorUtils';
 
 export function useShortcuts() {
 	  const [shortcutLists, setShortcutLists] = useState<ShortcutList[]>([]);
 	  const [applications, setApplications] = useState<Application[]>([]);
 	  const [settings, setSettings] = useState<Settings | null>(null);
 	  const [activeApp, setActiveApp] = useState<Application | null>(null);
+	  const [activeWindow, setActiveWindow] = useState<ActiveWindowInfo | null>(null);
 	  const [loading, setLoading] = useState(true);
 	  const [error, setError] = useState<string | null>(null);
+	  const [loadWarnings, setLoadWarnings] = useState<LoadWarning[]>([]);
 
 	  // For debounced, list saving (should be used for quick reorders only)
 	  const pendingSaveTimeoutRef = useRef<number | null>(null);
@@ -44,23 +47,28 @@ export function useShortcuts() {
 	      setLoading(true);
 	      
 	      // Initialize defaults if no data exists
-	      await invoke('initialize_defaults');
+	      const seedWarnings = await invoke<LoadWarning[]>('initialize_defaults');
 	
 	      const [
-	        listsData,
+	        loadedLists,
 	        appsData,
 	        settingsData,
-	        activeAppData,
-	        _activeWindowTitle,
+	        activeWindowData,
+	        matchedApp,
 	      ] = await Promise.all([
-	        invoke<ShortcutList[]>('get_all_lists'),
+	        invoke<LoadedLists>('get_all_lists'),
 	        invoke<Application[]>('get_all_applications'),
 	        invoke<Settings>('get_settings'),
-	        invoke<string>('get_active_application'),
-	        invoke<string>('get_active_window_title'),
+	        invoke<ActiveWindowInfo>('get_active_window_info'),
+	        invoke<Application | null>('match_active_application'),
 	      ]);
 	
-	      setShortcutLists(listsData);
+	      setShortcutLists(loadedLists.lists);
+	      const warnings = [...seedWarnings, ...loadedLists.warnings];
+	      setLoadWarnings(warnings);
+	      warnings.forEach((warning) =>
+	        console.warn(`Skipped lists for ${warning.application_id}:`, warning.error)
+	      );
 	      setApplications(appsData);
 	      setSettings(settingsData);
 
@@ -76,10 +84,11 @@ export function useShortcuts() {
           console.error('Failed to sync autostart on load:', e);
         }
       }
-      setActiveApp(appsData.find(app => app.detection_name === activeAppData) || null);
+      setActiveWindow(activeWindowData);
+      setActiveApp(matchedApp);
       setError(null);
     } catch (err) {
-      setError(err as string);
+      setError(describeError(err));
       console.error('Failed to load data:', err);
     } finally {
       setLoading(false);
@@ -91,7 +100,7 @@ export function useShortcuts() {
 	      await invoke('save_list', { list });
 	      await loadData(); // Reload to get updated data
 	    } catch (err) {
-	      setError(err as string);
+	      setError(describeError(err));
 	      console.error('Failed to save list:', err);
 	    }
 	  };
@@ -122,7 +131,7 @@ export function useShortcuts() {
 	      try {
 	        await invoke('save_list', { list: latest });
 	      } catch (err) {
-	        setError(err as string);
+	        setError(describeError(err));
 	        console.error('Failed to save list (using debounce):', err);
 	      } finally {
 	        pendingSaveTimeoutRef.current = null;
@@ -144,7 +153,7 @@ export function useShortcuts() {
       await invoke('delete_list', { listId });
       await loadData();
     } catch (err) {
-      setError(err as string);
+      setError(describeError(err));
       console.error('Failed to delete list:', err);
     }
   };
@@ -159,15 +168,15 @@ export function useShortcuts() {
 	        console.error('Failed to emit applications-updated event:', eventError);
 	      }
 	    } catch (err) {
-	      setError(err as string);
+	      setError(describeError(err));
 	      console.error('Failed to save application:', err);
 	    }
 	  };
 
 	  const saveSettings = async (newSettings: Settings) => {
 	    try {
+	      // Registers the global hotkeys before saving; nothing is saved if one is taken
 	      await invoke('save_settings', { settings: newSettings });
-	      await invoke('refresh_global_hotkey');
 	      setSettings(newSettings);
 	
 	      try {
@@ -189,17 +198,17 @@ export function useShortcuts() {
 	        }
 	      }
 	    } catch (err) {
-	      setError(err as string);
+	      setError(describeError(err));
 	      console.error('Failed to save settings:', err);
 	    }
 	  };
 
   const refreshActiveApp = async () => {
     try {
-      const activeApp = await invoke<string>('get_active_application');
-      setActiveApp(applications.find(app => app.detection_name === activeApp) || null);
+      const matchedApp = await invoke<Application | null>('match_active_application');
+      setActiveApp(matchedApp);
     } catch (err) {
-      console.error('Failed to get active app:', err);
+      console.error('Failed to get active app:', describeError(err));
     }
   };
 
@@ -208,7 +217,7 @@ export function useShortcuts() {
       const app = await invoke<string>('get_active_window_title');
       return app;
     } catch (err) {
-      console.error('Failed to get active app:', err);
+      console.error('Failed to get active window title:', describeError(err));
     }
   };
 
@@ -222,8 +231,10 @@ export function useShortcuts() {
     applications,
     settings,
     activeApp,
+    activeWindow,
     loading,
     error,
+    loadWarnings,
     saveList,
 	saveListWithDebounce,
     deleteList,
@@ -7,6 +7,23 @@ export interface Application {
   detection_name: string;
   icon?: string;
   last_used_list_id?: string;
+  match_rules?: MatchRule[];
+  // Browsers also offer the site lists of the "Web apps" application
+  is_browser?: boolean;
+}
+
+// Which part of the active window a rule looks at (mirrors storage::MatchField)
+export type MatchField = 'app_name' | 'process_path' | 'window_title' | 'wm_class';
+
+// How a rule's pattern is compared (mirrors storage::MatchKind)
+export type MatchKind = 'exact' | 'case_insensitive' | 'glob' | 'regex';
+
+// Extra ways of recognising an application; the highest priority match wins
+export interface MatchRule {
+  field: MatchField;
+  kind: MatchKind;
+  pattern: string;
+  priority?: number;
 }
 
 export interface Shortcut {
@@ -23,6 +40,8 @@ export interface ShortcutList {
   shortcuts: Shortcut[];
   created_at: string;
   updated_at: string;
+  // Picks this list over the app's other lists while a rule matches
+  match_rules?: MatchRule[];
 }
 
 export interface KeyboardShortcuts {
@@ -35,6 +54,21 @@ export interface KeyboardShortcuts {
   add_below: string;
 }
 
+// What a global hotkey does (mirrors storage::GlobalAction)
+export type GlobalAction =
+  | { kind: 'toggle_popup' }
+  | { kind: 'open_list'; list_id: string }
+  | { kind: 'open_settings' }
+  | { kind: 'next_list' }
+  | { kind: 'open_search' };
+
+export interface GlobalHotkey {
+  hotkey: string;
+  action: GlobalAction;
+  // Show the popup only while the hotkey is held
+  peek?: boolean;
+}
+
 export interface Settings {
   global_hotkey: string;
   always_on_top: boolean;
@@ -43,4 +77,179 @@ export interface Settings {
   window_position: string;
   show_app_name_in_dropdown?: boolean;
   show_all_lists?: boolean;
-}
\ No newline at end of file
+  backup_max_count?: number;
+  backup_max_age_days?: number;
+  // Extra global hotkeys besides global_hotkey
+  global_actions?: GlobalHotkey[];
+  // Hold-to-peek for global_hotkey
+  global_hotkey_peek?: boolean;
+  // Follow window focus in the background
+  watch_focus?: boolean;
+}
+
+export interface BackupInfo {
+  id: string;
+  created_at: string;
+  list_file_count: number;
+  size_bytes: number;
+}
+// Error returned by storage-backed commands (mirrors storage::StorageError)
+export type StorageError =
+  | { kind: 'io'; path: string; reason: string; message: string }
+  | { kind: 'parse'; path: string; line: number; column: number; message: string }
+  | { kind: 'missing_data_dir' }
+  | { kind: 'validation'; message: string }
+  | { kind: 'detection'; message: string };
+
+// Error returned by save_settings and refresh_global_hotkey (mirrors hotkeys::HotkeyError)
+export type HotkeyError =
+  | { kind: 'invalid_hotkey'; hotkey: string; message: string }
+  | { kind: 'hotkey_taken'; hotkey: string; message: string }
+  | { kind: 'hotkey_register_failed'; hotkey: string; message: string }
+  | { kind: 'storage'; error: StorageError };
+
+// A lists file that failed to load (mirrors storage::LoadWarning)
+export interface LoadWarning {
+  application_id: string;
+  quarantined_to: string | null;
+  error: StorageError;
+}
+
+// Result of get_all_lists (mirrors storage::LoadedLists)
+export interface LoadedLists {
+  lists: ShortcutList[];
+  warnings: LoadWarning[];
+}
+
+// Options for export_bundle (mirrors bundle::ExportOptions)
+export interface ExportOptions {
+  application_ids?: string[] | null;
+  include_settings?: boolean;
+}
+
+// Returned by export_bundle (mirrors bundle::ExportSummary)
+export interface ExportSummary {
+  application_count: number;
+  list_count: number;
+  shortcut_count: number;
+}
+
+// Conflict handling for import_bundle (mirrors bundle::ImportMode)
+export type ImportMode = 'skip_existing' | 'overwrite' | 'merge_by_key_combo' | 'import_as_copy';
+
+export interface ListImportReport {
+  process_name: string;
+  list_name: string;
+  action: 'created' | 'updated' | 'skipped' | 'copied';
+  added: number;
+  updated: number;
+  skipped: number;
+  removed: number;
+}
+
+// Returned by import_bundle (mirrors bundle::ImportReport)
+export interface ImportReport {
+  dry_run: boolean;
+  mode: ImportMode;
+  new_applications: string[];
+  lists: ListImportReport[];
+  settings_imported: boolean;
+  dropped_hotkeys: string[];
+}
+
+// Returned by the keybinding importers (mirrors importers::ImportSummary)
+export interface ImportSummary {
+  application_id: string;
+  list_id: string;
+  list_name: string;
+  created: boolean;
+  shortcut_count: number;
+  skipped: number;
+}
+
+// Output format for export_cheatsheet (mirrors cheatsheet::CheatSheetFormat)
+export type CheatSheetFormat = 'markdown' | 'html';
+
+// Options for export_cheatsheet (mirrors cheatsheet::CheatSheetOptions)
+export interface CheatSheetOptions {
+  title?: string | null;
+  // HTML only, 1-4
+  columns?: number;
+  // Defaults to the current platform
+  platform?: Platform | null;
+}
+
+// Returned by export_csv (mirrors csv_io::CsvExportSummary)
+export interface CsvExportSummary {
+  list_count: number;
+  row_count: number;
+}
+
+// One invalid row reported by import_csv
+export interface CsvRowError {
+  line: number;
+  reason: string;
+}
+
+export interface CsvListSummary {
+  application_id: string;
+  list_id: string;
+  list_name: string;
+  created: boolean;
+  shortcut_count: number;
+}
+
+// Returned by import_csv (mirrors csv_io::CsvImportReport). When `errors` is
+// non-empty nothing was saved and `lists` is empty.
+export interface CsvImportReport {
+  row_count: number;
+  lists: CsvListSummary[];
+  errors: CsvRowError[];
+}
+
+// Platform to render keys for (mirrors key_combo::Platform)
+export type Platform = 'windows' | 'macos' | 'linux';
+
+// render_key_combos output style: "Cmd+Shift+P" or "⌘⇧P" (mirrors key_combo::KeyStyle)
+export type KeyStyle = 'text' | 'symbols';
+
+// A shortcut involved in a conflict reported by find_conflicts
+export interface ConflictEntry {
+  list_id: string;
+  list_name: string;
+  shortcut_id: string;
+  key_combo: string;
+  description: string;
+}
+
+// Returned by find_conflicts (mirrors conflicts::Conflict)
+export type Conflict =
+  | { kind: 'duplicate_in_list'; key_combo: string; shortcuts: ConflictEntry[] }
+  | { kind: 'across_lists'; key_combo: string; shortcuts: ConflictEntry[] }
+  | { kind: 'global_hotkey'; key_combo: string; hotkey: string; shortcuts: ConflictEntry[] }
+  | { kind: 'app_binding'; key_combo: string; binding: string; shortcuts: ConflictEntry[] };
+
+// Window position and size in physical pixels (mirrors window_detection::WindowBounds)
+export interface WindowBounds {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+// Returned by get_active_window_info (mirrors window_detection::ActiveWindowInfo)
+export interface ActiveWindowInfo {
+  app_name: string;
+  title: string;
+  process_path: string | null;
+  pid: number | null;
+  bounds: WindowBounds | null;
+  wm_class: string | null;
+}
+
+// Payload of the "active-app-changed" event (mirrors focus_watcher::ActiveAppChanged)
+export interface ActiveAppChanged {
+  identifier: string;
+  site_list_id: string | null;
+  window: ActiveWindowInfo;
+}
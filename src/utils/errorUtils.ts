@@ -0,0 +1,37 @@
+import type { HotkeyError, StorageError } from '../types';
+
+/**
+ * Type guard for the tagged error objects returned by storage and hotkey commands.
+ */
+export function isStorageError(err: unknown): err is StorageError | HotkeyError {
+	return typeof err === 'object' && err !== null && 'kind' in err;
+}
+
+/**
+ * Turn a command error (plain string, StorageError or HotkeyError) into display text.
+ */
+export function describeError(err: unknown): string {
+	if (!isStorageError(err)) {
+		return String(err);
+	}
+	switch (err.kind) {
+		case 'io':
+			return `Could not access ${err.path}: ${err.message}`;
+		case 'parse':
+			return `${err.path} is corrupt (line ${err.line}, column ${err.column}): ${err.message}`;
+		case 'missing_data_dir':
+			return 'Could not determine the application data directory';
+		case 'validation':
+			return err.message;
+		case 'detection':
+			return `Could not detect the active window: ${err.message}`;
+		case 'invalid_hotkey':
+			return `"${err.hotkey}" is not a valid hotkey: ${err.message}`;
+		case 'hotkey_taken':
+			return `"${err.hotkey}" is already in use by another application; the previous hotkey was kept`;
+		case 'hotkey_register_failed':
+			return `Could not register "${err.hotkey}": ${err.message}; the previous hotkey was kept`;
+		case 'storage':
+			return describeError(err.error);
+	}
+}
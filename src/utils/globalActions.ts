@@ -0,0 +1,47 @@
+import type { GlobalAction } from '../types';
+
+export type GlobalActionKind = GlobalAction['kind'];
+
+export interface GlobalActionConfig {
+	kind: GlobalActionKind;
+	label: string;
+	description: string;
+}
+
+// Actions available for the extra global hotkeys (toggle_popup is global_hotkey itself)
+export const GLOBAL_ACTIONS: GlobalActionConfig[] = [
+	{
+		kind: 'open_list',
+		label: 'Open list',
+		description: 'Shows the popup on a specific list, whatever app is active.',
+	},
+	{
+		kind: 'open_settings',
+		label: 'Open settings',
+		description: 'Opens the settings window.',
+	},
+	{
+		kind: 'next_list',
+		label: 'Next list',
+		description: 'Switches the popup to the next list in its dropdown.',
+	},
+	{
+		kind: 'open_search',
+		label: 'Search',
+		description: 'Shows the popup with the search box focused.',
+	},
+	{
+		kind: 'toggle_popup',
+		label: 'Toggle popup',
+		description: 'Shows or hides the popup, like the main global hotkey.',
+	},
+];
+
+// Mirrors storage::GlobalAction::supports_peek
+export function supportsPeek(kind: GlobalActionKind): boolean {
+	return kind === 'toggle_popup' || kind === 'open_list';
+}
+
+export function makeGlobalAction(kind: GlobalActionKind, listId = ''): GlobalAction {
+	return kind === 'open_list' ? { kind, list_id: listId } : { kind };
+}
@@ -12,6 +12,13 @@ export type NormalizedHotkey = {
 // Internal helper type that works for both DOM and React keyboard events
 export type KeyboardLikeEvent = KeyboardEvent | React.KeyboardEvent<any>;
 
+// macOS shows Cmd/Option where other platforms show Ctrl/Alt
+export const isMac =
+  typeof navigator !== 'undefined' && /mac/i.test(navigator.platform || navigator.userAgent);
+
+// Platform-neutral modifier token stored by shared lists ("CmdOrCtrl+T")
+const PRIMARY_TOKENS = ['cmdorctrl', 'commandorcontrol', 'mod', 'primary'];
+
 function parseCombo(raw: string | undefined | null): NormalizedHotkey | null {
   if (!raw) return null;
 
@@ -34,6 +41,11 @@ function parseCombo(raw: string | undefined | null): NormalizedHotkey | null {
     const p = part.toLowerCase();
 
     // Modifiers
+    if (PRIMARY_TOKENS.includes(p)) {
+      if (isMac) meta = true;
+      else ctrl = true;
+      continue;
+    }
     if (p === 'ctrl' || p === 'control') {
       ctrl = true;
       continue;
@@ -1,4 +1,4 @@
-import type { Shortcut } from '../types';
+import type { Shortcut, ShortcutList, MatchRule } from '../types';
 
 /**
  * Return a new array of shortcuts sorted by their `order` value.
@@ -78,3 +78,22 @@ export function moveShortcut(shortcuts: Shortcut[], fromIndex: number, toIndex:
 	next.splice(toIndex, 0, moved);
 	return normalizeShortcutOrder(next);
 }
+
+/**
+ * The window title glob that picks this list (e.g. "* - Gmail*"), or '' if it has none.
+ */
+export function getTitlePattern(list: ShortcutList): string {
+	const rule = list.match_rules?.find((r) => r.field === 'window_title' && r.kind === 'glob');
+	return rule?.pattern ?? '';
+}
+
+/**
+ * Return `rules` with the window title glob replaced by `pattern` (removed when
+ * `pattern` is empty). Other rules are kept as they are.
+ */
+export function withTitlePattern(rules: MatchRule[] | undefined, pattern: string): MatchRule[] {
+	const others = (rules ?? []).filter((r) => !(r.field === 'window_title' && r.kind === 'glob'));
+	const trimmed = pattern.trim();
+	if (!trimmed) return others;
+	return [...others, { field: 'window_title', kind: 'glob', pattern: trimmed, priority: 0 }];
+}